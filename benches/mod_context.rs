@@ -0,0 +1,108 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use curv::arithmetic::*;
+
+const BIT_SIZES: [usize; 3] = [1024, 2048, 3072];
+
+/// Moduli sizes for which Montgomery products fit into the fixed width backend
+#[cfg(feature = "crypto-bigint")]
+fn bit_sizes() -> Vec<usize> {
+    BIT_SIZES
+        .iter()
+        .copied()
+        .filter(|bits| 2 * (bits + 64) <= BigInt::BITS)
+        .collect()
+}
+
+#[cfg(not(feature = "crypto-bigint"))]
+fn bit_sizes() -> Vec<usize> {
+    BIT_SIZES.to_vec()
+}
+
+fn odd_modulus(bits: usize) -> BigInt {
+    BigInt::sample(bits - 2) * 2 + (BigInt::one() << (bits - 1)) + 1
+}
+
+fn bench_mod_mul(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mod_mul");
+    for bits in bit_sizes() {
+        let n = odd_modulus(bits);
+        let ctx = ModContext::new(&n);
+        let (a, b) = (BigInt::sample_below(&n), BigInt::sample_below(&n));
+        group.bench_with_input(BenchmarkId::new("backend", bits), &bits, |bench, _| {
+            bench.iter(|| BigInt::mod_mul(&a, &b, &n))
+        });
+        group.bench_with_input(BenchmarkId::new("mod_context", bits), &bits, |bench, _| {
+            bench.iter(|| ctx.mul(&a, &b))
+        });
+    }
+    group.finish();
+}
+
+fn bench_mod_pow(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mod_pow");
+    group.sample_size(20);
+    for bits in bit_sizes() {
+        let n = odd_modulus(bits);
+        let ctx = ModContext::new(&n);
+        let (a, e) = (BigInt::sample_below(&n), BigInt::sample_below(&n));
+        group.bench_with_input(BenchmarkId::new("backend", bits), &bits, |bench, _| {
+            bench.iter(|| BigInt::mod_pow(&a, &e, &n))
+        });
+        group.bench_with_input(BenchmarkId::new("mod_context", bits), &bits, |bench, _| {
+            bench.iter(|| ctx.pow(&a, &e))
+        });
+    }
+    group.finish();
+}
+
+fn bench_multi_pow(c: &mut Criterion) {
+    let mut group = c.benchmark_group("multi_pow_3");
+    group.sample_size(20);
+    for bits in bit_sizes() {
+        let n = odd_modulus(bits);
+        let ctx = ModContext::new(&n);
+        let pairs: Vec<(BigInt, BigInt)> = (0..3)
+            .map(|_| (BigInt::sample_below(&n), BigInt::sample_below(&n)))
+            .collect();
+        let pair_refs: Vec<(&BigInt, &BigInt)> = pairs.iter().map(|(b, e)| (b, e)).collect();
+        group.bench_with_input(BenchmarkId::new("backend", bits), &bits, |bench, _| {
+            bench.iter(|| {
+                pairs.iter().fold(BigInt::one(), |acc, (b, e)| {
+                    BigInt::mod_mul(&acc, &BigInt::mod_pow(b, e, &n), &n)
+                })
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("mod_context", bits), &bits, |bench, _| {
+            bench.iter(|| ctx.multi_pow(&pair_refs))
+        });
+    }
+    group.finish();
+}
+
+fn bench_fixed_base_pow(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fixed_base_pow");
+    group.sample_size(20);
+    for bits in bit_sizes() {
+        let n = odd_modulus(bits);
+        let ctx = ModContext::new(&n);
+        let (a, e) = (BigInt::sample_below(&n), BigInt::sample_below(&n));
+        let table = ctx.fixed_base_table(&a, bits);
+        group.bench_with_input(BenchmarkId::new("backend", bits), &bits, |bench, _| {
+            bench.iter(|| BigInt::mod_pow(&a, &e, &n))
+        });
+        group.bench_with_input(BenchmarkId::new("mod_context", bits), &bits, |bench, _| {
+            bench.iter(|| table.pow(&e))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    mod_context,
+    bench_mod_mul,
+    bench_mod_pow,
+    bench_multi_pow,
+    bench_fixed_base_pow
+);
+criterion_main!(mod_context);
@@ -0,0 +1,58 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use curv::arithmetic::*;
+
+const BIT_SIZES: [usize; 3] = [1024, 1536, 2048];
+
+fn bench_sample_prime(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sample_prime");
+    group.sample_size(10);
+    for &bits in BIT_SIZES.iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(bits), &bits, |b, &bits| {
+            b.iter(|| BigInt::sample_prime(bits))
+        });
+    }
+    group.finish();
+}
+
+fn bench_sample_blum_prime(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sample_blum_prime");
+    group.sample_size(10);
+    for &bits in BIT_SIZES.iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(bits), &bits, |b, &bits| {
+            b.iter(|| BigInt::sample_blum_prime(bits))
+        });
+    }
+    group.finish();
+}
+
+fn bench_sample_safe_prime(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sample_safe_prime");
+    group.sample_size(10);
+    for &bits in BIT_SIZES.iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(bits), &bits, |b, &bits| {
+            b.iter(|| BigInt::sample_safe_prime(bits))
+        });
+    }
+    group.finish();
+}
+
+fn bench_sample_paillier_modulus(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sample_paillier_modulus");
+    group.sample_size(10);
+    for &bits in BIT_SIZES.iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(bits), &bits, |b, &bits| {
+            b.iter(|| BigInt::sample_paillier_modulus(bits))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    primes,
+    bench_sample_prime,
+    bench_sample_blum_prime,
+    bench_sample_safe_prime,
+    bench_sample_paillier_modulus
+);
+criterion_main!(primes);
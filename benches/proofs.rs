@@ -0,0 +1,87 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use curv::cryptographic_primitives::proofs::inner_product::Generators;
+use curv::cryptographic_primitives::proofs::range_proof::RangeProof;
+use curv::cryptographic_primitives::proofs::sigma_dlog::DLogProof;
+use curv::cryptographic_primitives::proofs::sigma_ec_ddh::{
+    ECDDHProof, ECDDHStatement, ECDDHWitness,
+};
+use curv::cryptographic_primitives::proofs::sigma_valid_pedersen::PedersenProof;
+use curv::elliptic::curves::traits::{ECPoint, ECScalar};
+use curv::BigInt;
+use zeroize::Zeroize;
+
+fn bench_proofs<P>(c: &mut Criterion, curve: &str)
+where
+    P: ECPoint + Clone,
+    P::Scalar: Zeroize + Clone,
+{
+    let mut group = c.benchmark_group(curve);
+    group.sample_size(10);
+
+    let x: P::Scalar = ECScalar::new_random();
+    group.bench_function("dlog_proof", |b| {
+        b.iter(|| DLogProof::<P>::verify(&DLogProof::prove(&x)).unwrap())
+    });
+
+    let r: P::Scalar = ECScalar::new_random();
+    group.bench_function("pedersen_proof", |b| {
+        b.iter(|| PedersenProof::<P>::verify(&PedersenProof::prove(&x, &r)).unwrap())
+    });
+
+    let (g1, g2): (P, P) = (ECPoint::generator(), ECPoint::base_point2());
+    let delta = ECDDHStatement {
+        h1: g1.clone() * x.clone(),
+        h2: g2.clone() * x.clone(),
+        g1,
+        g2,
+    };
+    let w = ECDDHWitness { x: x.clone() };
+    group.bench_function("ecddh_proof", |b| {
+        b.iter(|| ECDDHProof::prove(&w, &delta).verify(&delta).unwrap())
+    });
+
+    let gens = Generators::<P>::new(64);
+    let value: P::Scalar = ECScalar::from(&BigInt::from(1_000_000));
+    let blinding: P::Scalar = ECScalar::new_random();
+    let commitment = P::multi_scalar_mul(
+        &[value.clone(), blinding.clone()],
+        &[ECPoint::generator(), ECPoint::base_point2()],
+    );
+    let proof = RangeProof::prove(
+        &gens,
+        64,
+        std::slice::from_ref(&value),
+        std::slice::from_ref(&blinding),
+    );
+    group.bench_function("range_proof_64_prove", |b| {
+        b.iter(|| {
+            RangeProof::prove(
+                &gens,
+                64,
+                std::slice::from_ref(&value),
+                std::slice::from_ref(&blinding),
+            )
+        })
+    });
+    group.bench_function("range_proof_64_verify", |b| {
+        b.iter(|| {
+            proof
+                .verify(&gens, 64, std::slice::from_ref(&commitment))
+                .unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_ristretto(c: &mut Criterion) {
+    bench_proofs::<curv::elliptic::curves::curve_ristretto::GE>(c, "ristretto");
+}
+
+fn bench_secp256k1(c: &mut Criterion) {
+    bench_proofs::<curv::elliptic::curves::secp256_k1::GE>(c, "secp256k1");
+}
+
+criterion_group!(proofs, bench_ristretto, bench_secp256k1);
+criterion_main!(proofs);
@@ -42,7 +42,14 @@ fn main() {
         Some("ristretto") => ecdh::<curv::elliptic::curves::curve_ristretto::GE>(),
         Some("ed25519") => ecdh::<curv::elliptic::curves::ed25519::GE>(),
         Some("bls12_381") => ecdh::<curv::elliptic::curves::bls12_381::g1::GE>(),
+        Some("bn254") => ecdh::<curv::elliptic::curves::bn254::g1::GE>(),
         Some("p256") => ecdh::<curv::elliptic::curves::p256::GE>(),
+        Some("p384") => ecdh::<curv::elliptic::curves::p384::GE>(),
+        Some("p521") => ecdh::<curv::elliptic::curves::p521::GE>(),
+        Some("jubjub") => ecdh::<curv::elliptic::curves::jubjub::GE>(),
+        Some("baby_jubjub") => ecdh::<curv::elliptic::curves::baby_jubjub::GE>(),
+        Some("pallas") => ecdh::<curv::elliptic::curves::pasta::pallas::GE>(),
+        Some("vesta") => ecdh::<curv::elliptic::curves::pasta::vesta::GE>(),
         Some(unknown_curve) => eprintln!("Unknown curve: {}", unknown_curve),
         None => eprintln!("Missing curve name"),
     }
@@ -44,7 +44,14 @@ fn main() {
         Some("ristretto") => ped_com::<curv::elliptic::curves::curve_ristretto::GE>(&_message_bn),
         Some("ed25519") => ped_com::<curv::elliptic::curves::ed25519::GE>(&_message_bn),
         Some("bls12_381") => ped_com::<curv::elliptic::curves::bls12_381::g1::GE>(&_message_bn),
+        Some("bn254") => ped_com::<curv::elliptic::curves::bn254::g1::GE>(&_message_bn),
         Some("p256") => ped_com::<curv::elliptic::curves::p256::GE>(&_message_bn),
+        Some("p384") => ped_com::<curv::elliptic::curves::p384::GE>(&_message_bn),
+        Some("p521") => ped_com::<curv::elliptic::curves::p521::GE>(&_message_bn),
+        Some("jubjub") => ped_com::<curv::elliptic::curves::jubjub::GE>(&_message_bn),
+        Some("baby_jubjub") => ped_com::<curv::elliptic::curves::baby_jubjub::GE>(&_message_bn),
+        Some("pallas") => ped_com::<curv::elliptic::curves::pasta::pallas::GE>(&_message_bn),
+        Some("vesta") => ped_com::<curv::elliptic::curves::pasta::vesta::GE>(&_message_bn),
         Some(unknown_curve) => eprintln!("Unknown curve: {}", unknown_curve),
         None => eprintln!("Missing curve name"),
     }
@@ -30,7 +30,14 @@ fn main() {
         Some("ristretto") => dlog_proof::<curv::elliptic::curves::curve_ristretto::GE>(),
         Some("ed25519") => dlog_proof::<curv::elliptic::curves::ed25519::GE>(),
         Some("bls12_381") => dlog_proof::<curv::elliptic::curves::bls12_381::g1::GE>(),
+        Some("bn254") => dlog_proof::<curv::elliptic::curves::bn254::g1::GE>(),
         Some("p256") => dlog_proof::<curv::elliptic::curves::p256::GE>(),
+        Some("p384") => dlog_proof::<curv::elliptic::curves::p384::GE>(),
+        Some("p521") => dlog_proof::<curv::elliptic::curves::p521::GE>(),
+        Some("jubjub") => dlog_proof::<curv::elliptic::curves::jubjub::GE>(),
+        Some("baby_jubjub") => dlog_proof::<curv::elliptic::curves::baby_jubjub::GE>(),
+        Some("pallas") => dlog_proof::<curv::elliptic::curves::pasta::pallas::GE>(),
+        Some("vesta") => dlog_proof::<curv::elliptic::curves::pasta::vesta::GE>(),
         Some(unknown_curve) => eprintln!("Unknown curve: {}", unknown_curve),
         None => eprintln!("Missing curve name"),
     }
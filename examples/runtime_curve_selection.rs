@@ -0,0 +1,39 @@
+use curv::cryptographic_primitives::proofs::sigma_dlog::DynDLogProof;
+use curv::cryptographic_primitives::secret_sharing::feldman_vss::DynVerifiableSS;
+use curv::elliptic::curves::dynamic::{CurveId, DynScalar};
+
+/// Proof of knowledge of discrete log and Feldman VSS over a curve chosen at runtime
+/// TO RUN:
+/// cargo run --example runtime_curve_selection -- CURVE_NAME
+/// CURVE_NAME is any of the supported curves: i.e.:
+/// cargo run --example runtime_curve_selection -- jubjub
+///
+/// proof and VSS commitments are serialized along with the curve name, so the verifier
+/// doesn't need to know the curve in advance
+pub fn dlog_proof_and_vss(curve: CurveId) {
+    let secret = DynScalar::new_random(curve);
+
+    let proof = DynDLogProof::prove(&secret);
+    let proof_json = serde_json::to_string(&proof).unwrap();
+    let proof: DynDLogProof = serde_json::from_str(&proof_json).unwrap();
+    assert_eq!(proof.curve(), curve);
+    assert!(proof.verify().is_ok());
+
+    let (vss, shares) = DynVerifiableSS::share(1, 3, &secret);
+    let vss_json = serde_json::to_string(&vss).unwrap();
+    let vss: DynVerifiableSS = serde_json::from_str(&vss_json).unwrap();
+    for (i, share) in shares.iter().enumerate() {
+        assert!(vss.validate_share(share, i + 1).is_ok());
+    }
+    let reconstructed = vss.reconstruct(&[0, 2], &[shares[0].clone(), shares[2].clone()]);
+    assert_eq!(reconstructed, Ok(secret));
+}
+
+fn main() {
+    let curve_name = std::env::args().nth(1);
+    match curve_name.as_deref().map(str::parse::<CurveId>) {
+        Some(Ok(curve)) => dlog_proof_and_vss(curve),
+        Some(Err(err)) => eprintln!("{}", err),
+        None => eprintln!("Missing curve name"),
+    }
+}
@@ -74,7 +74,16 @@ fn main() {
         Some("bls12_381") => {
             secret_sharing_3_out_of_5::<curv::elliptic::curves::bls12_381::g1::GE>()
         }
+        Some("bn254") => secret_sharing_3_out_of_5::<curv::elliptic::curves::bn254::g1::GE>(),
         Some("p256") => secret_sharing_3_out_of_5::<curv::elliptic::curves::p256::GE>(),
+        Some("p384") => secret_sharing_3_out_of_5::<curv::elliptic::curves::p384::GE>(),
+        Some("p521") => secret_sharing_3_out_of_5::<curv::elliptic::curves::p521::GE>(),
+        Some("jubjub") => secret_sharing_3_out_of_5::<curv::elliptic::curves::jubjub::GE>(),
+        Some("baby_jubjub") => {
+            secret_sharing_3_out_of_5::<curv::elliptic::curves::baby_jubjub::GE>()
+        }
+        Some("pallas") => secret_sharing_3_out_of_5::<curv::elliptic::curves::pasta::pallas::GE>(),
+        Some("vesta") => secret_sharing_3_out_of_5::<curv::elliptic::curves::pasta::vesta::GE>(),
         Some(unknown_curve) => eprintln!("Unknown curve: {}", unknown_curve),
         None => eprintln!("Missing curve name"),
     }
@@ -0,0 +1,616 @@
+use std::convert::{TryFrom, TryInto};
+use std::sync::atomic;
+use std::{fmt, ops, ptr};
+
+use crypto_bigint::modular::runtime_mod::{DynResidue, DynResidueParams};
+use crypto_bigint::subtle::{ConditionallySelectable, ConstantTimeEq, ConstantTimeLess};
+
+use super::errors::*;
+use super::traits::*;
+
+mod int;
+mod limbs;
+mod primes;
+
+type BN = int::Int;
+
+#[cfg(feature = "crypto-bigint-8192")]
+type Uint = crypto_bigint::U8192;
+#[cfg(all(feature = "crypto-bigint-4096", not(feature = "crypto-bigint-8192")))]
+type Uint = crypto_bigint::U4096;
+#[cfg(all(
+    feature = "crypto-bigint-2048",
+    not(any(feature = "crypto-bigint-4096", feature = "crypto-bigint-8192"))
+))]
+type Uint = crypto_bigint::U2048;
+#[cfg(not(any(
+    feature = "crypto-bigint-2048",
+    feature = "crypto-bigint-4096",
+    feature = "crypto-bigint-8192"
+)))]
+type Uint = crypto_bigint::U4096;
+
+/// Big integer
+///
+/// Wraps underlying BigInt implementation (either GMP bindings, num-bigint or crypto-bigint),
+/// exposes only very limited API that allows easily switching between implementations.
+///
+/// Set of traits implemented on BigInt remains the same regardless of underlying implementation.
+/// Serialization format doesn't depend on it either, see [serde_support](super::serde_support).
+///
+/// This implementation keeps sign and magnitude of a fixed width ([BigInt::BITS] bits, chosen by
+/// `crypto-bigint-*` features). Any operation which result doesn't fit into the width panics.
+///
+/// This backend is not constant-time. Only [ConstantTime] operations are; general arithmetic
+/// (including [Modulo]) skips leading zero limbs and its division is data-dependent, so its
+/// running time varies with operands, as with other backends.
+#[derive(PartialOrd, PartialEq, Ord, Eq, Clone)]
+pub struct BigInt {
+    int: BN,
+}
+
+impl BigInt {
+    /// Maximum bit length of the numbers that can be represented
+    pub const BITS: usize = BN::BITS;
+
+    fn inner_ref(&self) -> &BN {
+        &self.int
+    }
+    fn inner_mut(&mut self) -> &mut BN {
+        &mut self.int
+    }
+    fn into_inner(self) -> BN {
+        self.int
+    }
+    fn magnitude(&self) -> &Uint {
+        self.int.magnitude()
+    }
+}
+
+#[allow(deprecated)]
+impl ZeroizeBN for BigInt {
+    fn zeroize_bn(&mut self) {
+        zeroize::Zeroize::zeroize(self)
+    }
+}
+
+impl zeroize::Zeroize for BigInt {
+    fn zeroize(&mut self) {
+        unsafe { ptr::write_volatile(&mut self.int, BN::zero()) };
+        atomic::fence(atomic::Ordering::SeqCst);
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+    }
+}
+
+impl Converter for BigInt {
+    fn to_bytes(&self) -> Vec<u8> {
+        let bytes: Vec<u8> = self
+            .magnitude()
+            .as_words()
+            .iter()
+            .rev()
+            .flat_map(|w| w.to_be_bytes())
+            .skip_while(|b| *b == 0)
+            .collect();
+        if bytes.is_empty() {
+            vec![0]
+        } else {
+            bytes
+        }
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self::try_from_bytes(bytes).unwrap_or_else(|| {
+            panic!(
+                "BigInt overflow: {} bytes don't fit into {}-bit integer",
+                bytes.len(),
+                Self::BITS
+            )
+        })
+    }
+
+    fn try_from_bytes(bytes: &[u8]) -> Option<Self> {
+        let leading_zeros = bytes.iter().take_while(|b| **b == 0).count();
+        let bytes = &bytes[leading_zeros..];
+        if bytes.len() > Uint::BYTES {
+            return None;
+        }
+        let mut padded = vec![0u8; Uint::BYTES - bytes.len()];
+        padded.extend_from_slice(bytes);
+        Some(BN::new(false, Uint::from_be_slice(&padded)).wrap())
+    }
+
+    fn to_hex(&self) -> String {
+        self.int.to_str_radix(16)
+    }
+
+    fn from_hex(n: &str) -> Result<Self, ParseBigIntError> {
+        <Self as Converter>::from_str_radix(n, 16)
+    }
+
+    fn to_str_radix(&self, radix: u8) -> String {
+        self.int.to_str_radix(radix)
+    }
+
+    fn from_str_radix(str: &str, radix: u8) -> Result<Self, ParseBigIntError> {
+        BN::from_str_radix(str, radix)
+            .map(Wrap::wrap)
+            .ok_or(ParseBigIntError {
+                reason: ParseErrorReason::CryptoBigint,
+                radix: radix.into(),
+            })
+    }
+
+    fn to_signed_bytes_be(&self) -> Vec<u8> {
+        super::signed_bytes::to_signed_bytes_be(self)
+    }
+
+    fn from_signed_bytes_be(bytes: &[u8]) -> Self {
+        super::signed_bytes::from_signed_bytes_be(bytes)
+    }
+}
+
+impl num_traits::Num for BigInt {
+    type FromStrRadixErr = ParseBigIntError;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        <Self as Converter>::from_str_radix(str, radix.try_into().unwrap())
+    }
+}
+
+crate::__bigint_impl_from! { u32, i32, u64 }
+
+impl BasicOps for BigInt {
+    fn pow(&self, exponent: u32) -> Self {
+        (0..32).rev().fold(BigInt::one(), |acc, i| {
+            let acc = &acc * &acc;
+            if exponent >> i & 1 == 1 {
+                acc * self
+            } else {
+                acc
+            }
+        })
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        self * other
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        self - other
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn abs(&self) -> Self {
+        self.int.abs().wrap()
+    }
+}
+
+impl Primes for BigInt {
+    fn next_prime(&self) -> BigInt {
+        primes::next_prime(self)
+    }
+
+    fn is_probable_prime(&self, n: u32) -> bool {
+        primes::probably_prime(self, n as usize)
+    }
+
+    fn sample_prime(bit_size: usize) -> Self {
+        super::primes::sample_prime(bit_size)
+    }
+
+    fn sample_safe_prime(bit_size: usize) -> Self {
+        super::primes::sample_safe_prime(bit_size)
+    }
+
+    fn sample_blum_prime(bit_size: usize) -> Self {
+        super::primes::sample_blum_prime(bit_size)
+    }
+
+    fn sample_rsa_modulus(bit_size: usize) -> (Self, Self, Self) {
+        super::primes::sample_rsa_modulus(bit_size)
+    }
+
+    fn sample_paillier_modulus(bit_size: usize) -> (Self, Self, Self) {
+        super::primes::sample_paillier_modulus(bit_size)
+    }
+}
+
+impl Modulo for BigInt {
+    fn mod_pow(base: &Self, exponent: &Self, modulus: &Self) -> Self {
+        assert!(exponent >= &BigInt::zero(), "exponent must be non-negative");
+        assert!(modulus > &BigInt::zero(), "modulus must be positive");
+        if modulus == &BigInt::one() {
+            return BigInt::zero();
+        }
+        let base = base.modulus(modulus);
+        // variable-time square-and-multiply, see ConstantTime::mod_pow_sec for constant-time one
+        (0..exponent.bit_length())
+            .rev()
+            .fold(BigInt::one(), |acc, i| {
+                let acc = BigInt::mod_mul(&acc, &acc, modulus);
+                if exponent.test_bit(i) {
+                    BigInt::mod_mul(&acc, &base, modulus)
+                } else {
+                    acc
+                }
+            })
+    }
+
+    fn mod_mul(a: &Self, b: &Self, modulus: &Self) -> Self {
+        let (a, b) = (a.modulus(modulus), b.modulus(modulus));
+        BN::mul_rem(&a.int, &b.int, &modulus.int).wrap()
+    }
+
+    fn mod_sub(a: &Self, b: &Self, modulus: &Self) -> Self {
+        (a.modulus(modulus) - b.modulus(modulus)).modulus(modulus)
+    }
+
+    fn mod_add(a: &Self, b: &Self, modulus: &Self) -> Self {
+        (a.modulus(modulus) + b.modulus(modulus)).modulus(modulus)
+    }
+
+    fn mod_inv(a: &Self, modulus: &Self) -> Option<Self> {
+        ring_algorithm::modulo_inverse(a.clone(), modulus.clone()).map(|inv| inv.modulus(modulus))
+    }
+
+    fn modulus(&self, modulus: &Self) -> Self {
+        let n = self % modulus;
+        if n.int.is_negative() {
+            modulus + n
+        } else {
+            n
+        }
+    }
+}
+
+impl ConstantTime for BigInt {
+    fn mod_pow_sec(base: &Self, exponent: &Self, modulus: &Self) -> Self {
+        assert!(exponent >= &BigInt::zero(), "exponent must be non-negative");
+        assert!(
+            modulus > &BigInt::one() && modulus.is_odd(),
+            "modulus must be odd and greater than 1"
+        );
+        // exponent length is only padded up to the modulus length, so that it doesn't leak
+        // anything for exponents below the modulus
+        let bits = exponent.bit_length().max(modulus.bit_length());
+        let params = DynResidueParams::new(modulus.magnitude());
+        let power = DynResidue::new(base.modulus(modulus).magnitude(), params)
+            .pow_bounded_exp(exponent.magnitude(), bits);
+        BN::new(false, power.retrieve()).wrap()
+    }
+
+    fn ct_eq(&self, other: &Self) -> Choice {
+        assert_non_negative(self, other);
+        self.magnitude().ct_eq(other.magnitude())
+    }
+
+    fn ct_lt(&self, other: &Self) -> Choice {
+        assert_non_negative(self, other);
+        self.magnitude().ct_lt(other.magnitude())
+    }
+
+    fn ct_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        assert_non_negative(a, b);
+        BN::new(
+            false,
+            Uint::conditional_select(a.magnitude(), b.magnitude(), choice),
+        )
+        .wrap()
+    }
+}
+
+fn assert_non_negative(a: &BigInt, b: &BigInt) {
+    assert!(
+        !a.int.is_negative() && !b.int.is_negative(),
+        "constant-time operations are defined only for non-negative numbers"
+    );
+}
+
+impl NumberTheory for BigInt {
+    fn jacobi(a: &Self, n: &Self) -> Result<i8, NumberTheoryError> {
+        super::number_theory::jacobi(a, n)
+    }
+
+    fn legendre(a: &Self, p: &Self) -> Result<i8, NumberTheoryError> {
+        super::number_theory::legendre(a, p)
+    }
+
+    fn sqrt_mod_prime(a: &Self, p: &Self) -> Result<Self, NumberTheoryError> {
+        super::number_theory::sqrt_mod_prime(a, p)
+    }
+
+    fn sqrt_mod_pq(a: &Self, p: &Self, q: &Self) -> Result<Vec<Self>, NumberTheoryError> {
+        super::number_theory::sqrt_mod_pq(a, p, q)
+    }
+
+    fn is_perfect_square(&self) -> bool {
+        super::number_theory::is_perfect_square(self)
+    }
+
+    fn try_mod_inv(a: &Self, m: &Self) -> Result<Self, NumberTheoryError> {
+        super::number_theory::try_mod_inv(a, m)
+    }
+}
+
+impl BitManipulation for BigInt {
+    fn set_bit(&mut self, bit: usize, bit_val: bool) {
+        self.int.set_bit(bit, bit_val)
+    }
+
+    fn test_bit(&self, bit: usize) -> bool {
+        self.int.bit(bit)
+    }
+
+    fn bit_length(&self) -> usize {
+        self.magnitude().bits_vartime()
+    }
+}
+
+impl NumberTests for BigInt {
+    fn is_zero(n: &Self) -> bool {
+        n.magnitude() == &Uint::ZERO
+    }
+
+    fn is_negative(n: &Self) -> bool {
+        n.int.is_negative()
+    }
+}
+
+impl EGCD for BigInt {
+    fn egcd(a: &Self, b: &Self) -> (Self, Self, Self) {
+        ring_algorithm::normalized_extended_euclidian_algorithm(a.clone(), b.clone())
+    }
+}
+
+impl Integer for BigInt {
+    fn div_floor(&self, other: &Self) -> Self {
+        self.div_mod_floor(other).0
+    }
+
+    fn mod_floor(&self, other: &Self) -> Self {
+        self.div_mod_floor(other).1
+    }
+
+    fn gcd(&self, other: &Self) -> Self {
+        // binary gcd of magnitudes
+        let (mut a, mut b) = (self.abs(), other.abs());
+        if a.is_zero() {
+            return b;
+        }
+        if b.is_zero() {
+            return a;
+        }
+        let shift = trailing_zeros(&a).min(trailing_zeros(&b));
+        a >>= trailing_zeros(&a);
+        while !b.is_zero() {
+            b >>= trailing_zeros(&b);
+            if a > b {
+                std::mem::swap(&mut a, &mut b);
+            }
+            b -= &a;
+        }
+        a << shift
+    }
+
+    fn lcm(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return BigInt::zero();
+        }
+        (self / self.gcd(other) * other).abs()
+    }
+
+    fn is_multiple_of(&self, other: &Self) -> bool {
+        if other.is_zero() {
+            return self.is_zero();
+        }
+        (self % other).is_zero()
+    }
+
+    fn is_even(&self) -> bool {
+        !self.test_bit(0)
+    }
+
+    fn is_odd(&self) -> bool {
+        self.test_bit(0)
+    }
+
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        let (q, r) = BN::div_rem(&self.int, &other.int);
+        (q.wrap(), r.wrap())
+    }
+
+    fn div_mod_floor(&self, other: &Self) -> (Self, Self) {
+        let (q, r) = Integer::div_rem(self, other);
+        // truncated division rounds towards zero, so it's off by one if signs differ
+        if !r.is_zero() && r.int.is_negative() != other.int.is_negative() {
+            (q - 1u64, r + other)
+        } else {
+            (q, r)
+        }
+    }
+}
+
+impl Roots for BigInt {
+    fn nth_root(&self, n: u32) -> Self {
+        assert!(n > 0, "root degree n must be at least 1");
+        if self.int.is_negative() {
+            assert!(n % 2 == 1, "root of degree {} is imaginary", n);
+            return -(-self).nth_root(n);
+        }
+        if n == 1 || self.is_zero() {
+            return self.clone();
+        }
+        if n == 2 {
+            return self.sqrt();
+        }
+        let n = n as usize;
+        // Newton's iterations starting from an overestimate converge to the root from above
+        let mut x = BigInt::one() << usize::div_ceil(self.bit_length(), n);
+        loop {
+            let y = ((&x * (n as u64 - 1)) + self / x.pow(n as u32 - 1)) / n as u64;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn sqrt(&self) -> Self {
+        assert!(!self.int.is_negative(), "square root of negative number");
+        BN::new(false, self.magnitude().sqrt_vartime()).wrap()
+    }
+}
+
+fn trailing_zeros(n: &BigInt) -> usize {
+    (0..n.bit_length()).find(|i| n.test_bit(*i)).unwrap_or(0)
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad_integral(
+            !self.int.is_negative(),
+            "",
+            &self.int.abs().to_str_radix(10),
+        )
+    }
+}
+
+impl fmt::Debug for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+crate::__bigint_impl_ops! {
+    Add add,
+    Sub sub,
+    Mul mul,
+    Div div,
+    Rem rem,
+    BitAnd bitand,
+    BitXor bitxor,
+    Shl shl usize,
+    Shr shr usize,
+
+    Add add u64 [swap],
+    Sub sub u64 [swap],
+    Mul mul u64 [swap],
+    Div div u64,
+    Rem rem u64,
+}
+
+crate::__bigint_impl_assigns! {
+    AddAssign add_assign,
+    AddAssign add_assign u64,
+    BitAndAssign bitand_assign,
+    BitOrAssign bitor_assign,
+    BitXorAssign bitxor_assign,
+    DivAssign div_assign,
+    DivAssign div_assign u64,
+    MulAssign mul_assign,
+    MulAssign mul_assign u64,
+    RemAssign rem_assign,
+    RemAssign rem_assign u64,
+    ShlAssign shl_assign usize,
+    ShrAssign shr_assign usize,
+    SubAssign sub_assign,
+    SubAssign sub_assign u64,
+}
+
+impl ops::Neg for BigInt {
+    type Output = BigInt;
+    fn neg(self) -> Self::Output {
+        self.int.neg().wrap()
+    }
+}
+impl ops::Neg for &BigInt {
+    type Output = BigInt;
+    fn neg(self) -> Self::Output {
+        (&self.int).neg().wrap()
+    }
+}
+
+impl num_traits::Zero for BigInt {
+    fn zero() -> Self {
+        BN::zero().wrap()
+    }
+    fn is_zero(&self) -> bool {
+        self.magnitude() == &Uint::ZERO
+    }
+}
+
+impl num_traits::One for BigInt {
+    fn one() -> Self {
+        BN::one().wrap()
+    }
+}
+
+impl ring_algorithm::RingNormalize for BigInt {
+    fn leading_unit(&self) -> Self {
+        if self.int.is_negative() {
+            -BigInt::one()
+        } else {
+            BigInt::one()
+        }
+    }
+
+    fn normalize_mut(&mut self) {
+        *self = self.abs();
+    }
+}
+
+/// Returns magnitude as u64, or `None` if it doesn't fit into 64 bits
+fn magnitude_u64(n: &BigInt) -> Option<u64> {
+    let bytes = n.to_bytes();
+    if bytes.len() > 8 {
+        return None;
+    }
+    Some(bytes.iter().fold(0, |acc, b| acc << 8 | u64::from(*b)))
+}
+
+impl TryFrom<&BigInt> for u64 {
+    type Error = TryFromBigIntError;
+
+    fn try_from(value: &BigInt) -> Result<Self, Self::Error> {
+        match magnitude_u64(value) {
+            Some(n) if !value.int.is_negative() => Ok(n),
+            _ => Err(TryFromBigIntError { type_name: "u64" }),
+        }
+    }
+}
+
+impl TryFrom<&BigInt> for i64 {
+    type Error = TryFromBigIntError;
+
+    fn try_from(value: &BigInt) -> Result<Self, Self::Error> {
+        let n = magnitude_u64(value);
+        let result = match n {
+            Some(n) if value.int.is_negative() => 0i64.checked_sub_unsigned(n),
+            Some(n) => i64::try_from(n).ok(),
+            None => None,
+        };
+        result.ok_or(TryFromBigIntError { type_name: "i64" })
+    }
+}
+
+#[allow(deprecated)]
+impl ConvertFrom<BigInt> for u64 {
+    fn _from(x: &BigInt) -> u64 {
+        let opt_x: u64 = x.try_into().unwrap();
+        opt_x
+    }
+}
+
+/// Internal helper trait. Creates short-hand for wrapping Int into BigInt.
+trait Wrap {
+    fn wrap(self) -> BigInt;
+}
+impl Wrap for BN {
+    fn wrap(self) -> BigInt {
+        BigInt { int: self }
+    }
+}
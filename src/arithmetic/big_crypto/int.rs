@@ -0,0 +1,439 @@
+//! Signed integer represented as sign and fixed-width magnitude
+//!
+//! Arithmetic panics if result doesn't fit into the magnitude, mirroring overflow behaviour of
+//! primitive integers in debug builds. Bitwise operations follow two's complement semantics with
+//! infinite sign extension, the same as in GMP and num-bigint.
+
+use std::cmp::Ordering;
+use std::ops;
+
+use crypto_bigint::{Limb, NonZero, Word};
+
+use super::{limbs, Uint};
+
+const OVERFLOW: &str = "BigInt overflow: result doesn't fit into fixed-width integer";
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Int {
+    /// Always `false` for zero
+    negative: bool,
+    magnitude: Uint,
+}
+
+impl Int {
+    pub const BITS: usize = Uint::BITS;
+
+    pub fn new(negative: bool, magnitude: Uint) -> Self {
+        let negative = negative && magnitude != Uint::ZERO;
+        Int {
+            negative,
+            magnitude,
+        }
+    }
+
+    pub fn zero() -> Self {
+        Int::new(false, Uint::ZERO)
+    }
+
+    pub fn one() -> Self {
+        Int::new(false, Uint::ONE)
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn magnitude(&self) -> &Uint {
+        &self.magnitude
+    }
+
+    pub fn abs(&self) -> Self {
+        Int::new(false, self.magnitude)
+    }
+
+    fn add(a: &Int, b: &Int) -> Int {
+        if a.negative == b.negative {
+            let (sum, carry) = a.magnitude.adc(&b.magnitude, Limb::ZERO);
+            assert_eq!(carry, Limb::ZERO, "{}", OVERFLOW);
+            return Int::new(a.negative, sum);
+        }
+        // signs differ: subtract smaller magnitude from the larger one
+        match a.magnitude.cmp(&b.magnitude) {
+            Ordering::Less => Int::new(b.negative, b.magnitude.wrapping_sub(&a.magnitude)),
+            _ => Int::new(a.negative, a.magnitude.wrapping_sub(&b.magnitude)),
+        }
+    }
+
+    fn sub(a: &Int, b: &Int) -> Int {
+        Int::add(a, &-b)
+    }
+
+    fn mul(a: &Int, b: &Int) -> Int {
+        let product = limbs::mul(
+            limbs::significant(&a.magnitude),
+            limbs::significant(&b.magnitude),
+        );
+        let product = limbs::to_uint(&product).expect(OVERFLOW);
+        Int::new(a.negative != b.negative, product)
+    }
+
+    /// Calculates `a * b mod m` for non-negative `a`, `b` and positive `m`. Unlike `a * b % m`,
+    /// it doesn't overflow if the product doesn't fit into fixed width.
+    pub fn mul_rem(a: &Int, b: &Int, m: &Int) -> Int {
+        debug_assert!(!a.negative && !b.negative && !m.negative);
+        let product = limbs::mul(
+            limbs::significant(&a.magnitude),
+            limbs::significant(&b.magnitude),
+        );
+        let (_, r) = limbs::div_rem(&product, limbs::significant(&m.magnitude));
+        Int::new(
+            false,
+            limbs::to_uint(&r).expect("remainder is less than modulus"),
+        )
+    }
+
+    /// Truncated division: quotient is rounded towards zero, remainder has sign of the dividend
+    pub fn div_rem(a: &Int, b: &Int) -> (Int, Int) {
+        let (q, r) = div_rem_magnitudes(&a.magnitude, &b.magnitude);
+        (
+            Int::new(a.negative != b.negative, q),
+            Int::new(a.negative, r),
+        )
+    }
+
+    fn div(a: &Int, b: &Int) -> Int {
+        Int::div_rem(a, b).0
+    }
+
+    fn rem(a: &Int, b: &Int) -> Int {
+        Int::div_rem(a, b).1
+    }
+
+    fn shl(a: &Int, n: usize) -> Int {
+        if a.magnitude == Uint::ZERO {
+            return Int::zero();
+        }
+        assert!(a.magnitude.bits_vartime() + n <= Self::BITS, "{}", OVERFLOW);
+        Int::new(a.negative, a.magnitude.shl_vartime(n))
+    }
+
+    /// Shift right rounding towards negative infinity, ie. `floor(a / 2^n)`
+    fn shr(a: &Int, n: usize) -> Int {
+        let shifted = a.magnitude.shr_vartime(n);
+        if !a.negative {
+            return Int::new(false, shifted);
+        }
+        let truncated = n >= Self::BITS || shifted.shl_vartime(n) != a.magnitude;
+        let shifted = if truncated {
+            shifted.wrapping_add(&Uint::ONE)
+        } else {
+            shifted
+        };
+        Int::new(true, shifted)
+    }
+
+    /// Two's complement representation: sign extension bit and the lower bits
+    fn to_twos_complement(&self) -> (bool, Uint) {
+        if self.negative {
+            (true, self.magnitude.wrapping_neg())
+        } else {
+            (false, self.magnitude)
+        }
+    }
+
+    fn from_twos_complement(extension: bool, bits: Uint) -> Int {
+        if extension {
+            // -2^BITS can't be represented
+            assert_ne!(bits, Uint::ZERO, "{}", OVERFLOW);
+            Int::new(true, bits.wrapping_neg())
+        } else {
+            Int::new(false, bits)
+        }
+    }
+
+    fn bitand(a: &Int, b: &Int) -> Int {
+        let ((ea, a), (eb, b)) = (a.to_twos_complement(), b.to_twos_complement());
+        Int::from_twos_complement(ea & eb, a.bitand(&b))
+    }
+
+    fn bitor(a: &Int, b: &Int) -> Int {
+        let ((ea, a), (eb, b)) = (a.to_twos_complement(), b.to_twos_complement());
+        Int::from_twos_complement(ea | eb, a.bitor(&b))
+    }
+
+    fn bitxor(a: &Int, b: &Int) -> Int {
+        let ((ea, a), (eb, b)) = (a.to_twos_complement(), b.to_twos_complement());
+        Int::from_twos_complement(ea ^ eb, a.bitxor(&b))
+    }
+
+    /// Tests bit of two's complement representation
+    pub fn bit(&self, index: usize) -> bool {
+        let (extension, bits) = self.to_twos_complement();
+        if index >= Self::BITS {
+            extension
+        } else {
+            bits.bit_vartime(index)
+        }
+    }
+
+    /// Sets bit of two's complement representation
+    pub fn set_bit(&mut self, index: usize, value: bool) {
+        assert!(index < Self::BITS, "{}", OVERFLOW);
+        let (extension, bits) = self.to_twos_complement();
+        let mask = Uint::ONE.shl_vartime(index);
+        let bits = if value {
+            bits.bitor(&mask)
+        } else {
+            bits.bitand(&mask.not())
+        };
+        *self = Int::from_twos_complement(extension, bits);
+    }
+}
+
+fn div_rem_magnitudes(a: &Uint, b: &Uint) -> (Uint, Uint) {
+    let (q, r) = limbs::div_rem(limbs::significant(a), limbs::significant(b));
+    let to_uint = |x: Vec<Word>| limbs::to_uint(&x).expect("quotient and remainder fit");
+    (to_uint(q), to_uint(r))
+}
+
+impl Ord for Int {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for Int {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<u64> for Int {
+    fn from(n: u64) -> Self {
+        Int::new(false, Uint::from_u64(n))
+    }
+}
+
+impl From<u32> for Int {
+    fn from(n: u32) -> Self {
+        Int::from(u64::from(n))
+    }
+}
+
+impl From<i32> for Int {
+    fn from(n: i32) -> Self {
+        Int::new(n < 0, Uint::from_u64(n.unsigned_abs().into()))
+    }
+}
+
+impl ops::Neg for Int {
+    type Output = Int;
+    fn neg(self) -> Int {
+        -&self
+    }
+}
+
+impl ops::Neg for &Int {
+    type Output = Int;
+    fn neg(self) -> Int {
+        Int::new(!self.negative, self.magnitude)
+    }
+}
+
+macro_rules! impl_binary_ops {
+    ($($trait:ident $fn:ident $assign_trait:ident $assign_fn:ident),*$(,)?) => {$(
+        impl ops::$trait<&Int> for &Int {
+            type Output = Int;
+            fn $fn(self, rhs: &Int) -> Int {
+                Int::$fn(self, rhs)
+            }
+        }
+        impl ops::$trait<Int> for &Int {
+            type Output = Int;
+            fn $fn(self, rhs: Int) -> Int {
+                Int::$fn(self, &rhs)
+            }
+        }
+        impl ops::$trait<&Int> for Int {
+            type Output = Int;
+            fn $fn(self, rhs: &Int) -> Int {
+                Int::$fn(&self, rhs)
+            }
+        }
+        impl ops::$trait<Int> for Int {
+            type Output = Int;
+            fn $fn(self, rhs: Int) -> Int {
+                Int::$fn(&self, &rhs)
+            }
+        }
+        impl ops::$trait<u64> for &Int {
+            type Output = Int;
+            fn $fn(self, rhs: u64) -> Int {
+                Int::$fn(self, &Int::from(rhs))
+            }
+        }
+        impl ops::$trait<u64> for Int {
+            type Output = Int;
+            fn $fn(self, rhs: u64) -> Int {
+                Int::$fn(&self, &Int::from(rhs))
+            }
+        }
+        impl ops::$trait<&Int> for u64 {
+            type Output = Int;
+            fn $fn(self, rhs: &Int) -> Int {
+                Int::$fn(&Int::from(self), rhs)
+            }
+        }
+        impl ops::$trait<Int> for u64 {
+            type Output = Int;
+            fn $fn(self, rhs: Int) -> Int {
+                Int::$fn(&Int::from(self), &rhs)
+            }
+        }
+        impl ops::$assign_trait<&Int> for Int {
+            fn $assign_fn(&mut self, rhs: &Int) {
+                *self = Int::$fn(self, rhs)
+            }
+        }
+        impl ops::$assign_trait<Int> for Int {
+            fn $assign_fn(&mut self, rhs: Int) {
+                *self = Int::$fn(self, &rhs)
+            }
+        }
+        impl ops::$assign_trait<u64> for Int {
+            fn $assign_fn(&mut self, rhs: u64) {
+                *self = Int::$fn(self, &Int::from(rhs))
+            }
+        }
+    )*};
+}
+
+impl_binary_ops! {
+    Add add AddAssign add_assign,
+    Sub sub SubAssign sub_assign,
+    Mul mul MulAssign mul_assign,
+    Div div DivAssign div_assign,
+    Rem rem RemAssign rem_assign,
+    BitAnd bitand BitAndAssign bitand_assign,
+    BitOr bitor BitOrAssign bitor_assign,
+    BitXor bitxor BitXorAssign bitxor_assign,
+}
+
+macro_rules! impl_shift_ops {
+    ($($trait:ident $fn:ident $assign_trait:ident $assign_fn:ident),*$(,)?) => {$(
+        impl ops::$trait<usize> for &Int {
+            type Output = Int;
+            fn $fn(self, rhs: usize) -> Int {
+                Int::$fn(self, rhs)
+            }
+        }
+        impl ops::$trait<usize> for Int {
+            type Output = Int;
+            fn $fn(self, rhs: usize) -> Int {
+                Int::$fn(&self, rhs)
+            }
+        }
+        impl ops::$assign_trait<usize> for Int {
+            fn $assign_fn(&mut self, rhs: usize) {
+                *self = Int::$fn(self, rhs)
+            }
+        }
+    )*};
+}
+
+impl_shift_ops! {
+    Shl shl ShlAssign shl_assign,
+    Shr shr ShrAssign shr_assign,
+}
+
+impl Int {
+    /// Formats number in given radix, using lowercase letters for digits above 9
+    pub fn to_str_radix(&self, radix: u8) -> String {
+        assert!((2..=36).contains(&radix), "radix must be within 2..=36");
+        let (chunk, chunk_digits) = radix_chunk(radix);
+        let chunk = Option::from(NonZero::new(Limb(chunk))).expect("chunk is positive");
+
+        let mut digits = vec![];
+        let mut n = self.magnitude;
+        while n != Uint::ZERO {
+            let (q, mut r) = n.div_rem_limb(chunk);
+            for _ in 0..chunk_digits {
+                digits.push(
+                    std::char::from_digit((r.0 % Word::from(radix)) as u32, radix.into()).unwrap(),
+                );
+                r.0 /= Word::from(radix);
+            }
+            n = q;
+        }
+        while digits.len() > 1 && digits.last() == Some(&'0') {
+            digits.pop();
+        }
+        if digits.is_empty() {
+            digits.push('0');
+        }
+        if self.negative {
+            digits.push('-');
+        }
+        digits.iter().rev().collect()
+    }
+
+    /// Parses number in given radix, with optional leading `-`. Returns `None` if string is not
+    /// a valid number or if it doesn't fit into fixed-width integer.
+    pub fn from_str_radix(s: &str, radix: u8) -> Option<Self> {
+        if !(2..=36).contains(&radix) {
+            return None;
+        }
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s),
+        };
+        if digits.is_empty() {
+            return None;
+        }
+        let (_, chunk_digits) = radix_chunk(radix);
+        let digits: Vec<Word> = digits
+            .chars()
+            .map(|c| c.to_digit(radix.into()).map(Word::from))
+            .collect::<Option<_>>()?;
+
+        let mut magnitude = Uint::ZERO;
+        // first chunk is shorter, so all the following chunks have exactly `chunk_digits` digits
+        let first = match digits.len() % chunk_digits {
+            0 => chunk_digits,
+            len => len,
+        };
+        let mut start = 0;
+        let mut end = first;
+        while start < digits.len() {
+            let chunk = &digits[start..end];
+            let value = chunk.iter().fold(0, |acc, d| acc * Word::from(radix) + d);
+            let scale = chunk.iter().fold(1, |acc: Word, _| acc * Word::from(radix));
+            let (lo, hi) = magnitude.mul_wide(&Uint::from_word(scale));
+            let (sum, carry) = lo.adc(&Uint::from_word(value), Limb::ZERO);
+            if hi != Uint::ZERO || carry != Limb::ZERO {
+                return None;
+            }
+            magnitude = sum;
+            start = end;
+            end += chunk_digits;
+        }
+        Some(Int::new(negative, magnitude))
+    }
+}
+
+/// Largest power of `radix` that fits into a limb, and its exponent
+fn radix_chunk(radix: u8) -> (Word, usize) {
+    let radix = Word::from(radix);
+    let mut chunk = radix;
+    let mut digits = 1;
+    while let Some(next) = chunk.checked_mul(radix) {
+        chunk = next;
+        digits += 1;
+    }
+    (chunk, digits)
+}
@@ -0,0 +1,162 @@
+//! Schoolbook multiplication and division over significant limbs of fixed-width integers
+//!
+//! crypto-bigint always processes every limb of the integer (its division even every bit), so
+//! operations on small numbers stored in a wide integer are as slow as on full-width ones. These
+//! routines skip leading zero limbs, the same as GMP and num-bigint do.
+//!
+//! They are not constant-time: running time depends on the length of operands, and division
+//! also depends on their values (correction of the quotient digit estimate and the add-back
+//! step of Algorithm D are data-dependent). Only [ConstantTime](crate::arithmetic::ConstantTime)
+//! operations must be used on secret values.
+
+use crypto_bigint::{Limb, WideWord, Word};
+
+use super::Uint;
+
+/// Limbs of `x` up to the most significant non-zero one
+pub fn significant(x: &Uint) -> &[Word] {
+    let len = usize::div_ceil(x.bits_vartime(), Limb::BITS);
+    &x.as_words()[..len]
+}
+
+/// Converts little-endian limbs into an integer. Returns `None` if it doesn't fit.
+pub fn to_uint(words: &[Word]) -> Option<Uint> {
+    let len = words.len() - words.iter().rev().take_while(|w| **w == 0).count();
+    if len > Uint::LIMBS {
+        return None;
+    }
+    let mut x = Uint::ZERO;
+    x.as_words_mut()[..len].copy_from_slice(&words[..len]);
+    Some(x)
+}
+
+pub fn mul(a: &[Word], b: &[Word]) -> Vec<Word> {
+    let mut product = vec![0; a.len() + b.len()];
+    for (i, a_i) in a.iter().enumerate() {
+        let mut carry: Word = 0;
+        for (j, b_j) in b.iter().enumerate() {
+            let t = WideWord::from(*a_i) * WideWord::from(*b_j)
+                + WideWord::from(product[i + j])
+                + WideWord::from(carry);
+            product[i + j] = t as Word;
+            carry = (t >> Limb::BITS) as Word;
+        }
+        product[i + b.len()] = carry;
+    }
+    product
+}
+
+/// Computes quotient and remainder of `u / v`, where `v` has no leading zero limbs
+///
+/// Algorithm D from D. Knuth. The Art of Computer Programming, Vol. 2, 4.3.1.
+///
+/// ## Panics
+/// Panics if `v` is empty (ie. divisor is zero)
+pub fn div_rem(u: &[Word], v: &[Word]) -> (Vec<Word>, Vec<Word>) {
+    assert!(!v.is_empty(), "division by zero");
+    let n = v.len();
+    if u.len() < n {
+        return (vec![], u.to_vec());
+    }
+    if n == 1 {
+        return div_rem_word(u, v[0]);
+    }
+    let m = u.len() - n;
+    let base: WideWord = 1 << Limb::BITS;
+
+    // normalize, so that the most significant limb of divisor has its top bit set
+    let shift = v[n - 1].leading_zeros();
+    let v = shl(v, shift);
+    let mut u = shl(u, shift);
+    u.resize(m + n + 1, 0);
+
+    let (v_top, v_next) = (WideWord::from(v[n - 1]), WideWord::from(v[n - 2]));
+    let mut q = vec![0; m + 1];
+    for j in (0..=m).rev() {
+        // estimate quotient limb from the top two limbs of the remainder, it's at most 2 too large
+        let num = WideWord::from(u[j + n]) << Limb::BITS | WideWord::from(u[j + n - 1]);
+        let mut q_hat = num / v_top;
+        let mut r_hat = num % v_top;
+        while q_hat >= base || q_hat * v_next > (r_hat << Limb::BITS | WideWord::from(u[j + n - 2]))
+        {
+            q_hat -= 1;
+            r_hat += v_top;
+            if r_hat >= base {
+                break;
+            }
+        }
+
+        // u[j..=j+n] -= q_hat * v
+        let (mut borrow, mut carry) = (false, 0 as Word);
+        for i in 0..n {
+            let p = q_hat * WideWord::from(v[i]) + WideWord::from(carry);
+            carry = (p >> Limb::BITS) as Word;
+            let (d, b1) = u[i + j].overflowing_sub(p as Word);
+            let (d, b2) = d.overflowing_sub(borrow as Word);
+            u[i + j] = d;
+            borrow = b1 | b2;
+        }
+        let (d, b1) = u[j + n].overflowing_sub(carry);
+        let (d, b2) = d.overflowing_sub(borrow as Word);
+        u[j + n] = d;
+
+        if b1 | b2 {
+            // estimate was one too large, add divisor back
+            q_hat -= 1;
+            let mut carry = false;
+            for i in 0..n {
+                let (s, c1) = u[i + j].overflowing_add(v[i]);
+                let (s, c2) = s.overflowing_add(carry as Word);
+                u[i + j] = s;
+                carry = c1 | c2;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as Word);
+        }
+        q[j] = q_hat as Word;
+    }
+
+    u.truncate(n);
+    (q, shr(&u, shift))
+}
+
+fn div_rem_word(u: &[Word], v: Word) -> (Vec<Word>, Vec<Word>) {
+    let v = WideWord::from(v);
+    let mut q = vec![0; u.len()];
+    let mut r: WideWord = 0;
+    for (i, u_i) in u.iter().enumerate().rev() {
+        let num = r << Limb::BITS | WideWord::from(*u_i);
+        q[i] = (num / v) as Word;
+        r = num % v;
+    }
+    (q, vec![r as Word])
+}
+
+/// Shifts left by `shift < Limb::BITS` bits, appending a limb if needed
+fn shl(x: &[Word], shift: u32) -> Vec<Word> {
+    if shift == 0 {
+        return x.to_vec();
+    }
+    let mut result = Vec::with_capacity(x.len() + 1);
+    let mut carry = 0;
+    for w in x {
+        result.push(w << shift | carry);
+        carry = w >> (Limb::BITS as u32 - shift);
+    }
+    if carry != 0 {
+        result.push(carry);
+    }
+    result
+}
+
+/// Shifts right by `shift < Limb::BITS` bits
+fn shr(x: &[Word], shift: u32) -> Vec<Word> {
+    if shift == 0 {
+        return x.to_vec();
+    }
+    (0..x.len())
+        .map(|i| {
+            let high = x.get(i + 1).map_or(0, |w| w << (Limb::BITS as u32 - shift));
+            x[i] >> shift | high
+        })
+        .collect()
+}
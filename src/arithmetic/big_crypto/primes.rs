@@ -0,0 +1,75 @@
+//! Primality testing for crypto-bigint backend: trial division by small primes followed by
+//! Miller-Rabin test with random bases, as described in FIPS 186-4 Appendix C.3.1
+
+use super::super::traits::*;
+use super::BigInt;
+
+const SMALL_PRIMES: [u64; 53] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193,
+    197, 199, 211, 223, 227, 229, 233, 239, 241,
+];
+
+/// Reports whether `n` is probably prime, doing `reps` rounds of Miller-Rabin test. Error
+/// probability for composite `n` is at most `4^-reps`.
+pub fn probably_prime(n: &BigInt, reps: usize) -> bool {
+    if n <= &BigInt::one() {
+        return false;
+    }
+    for p in SMALL_PRIMES.iter() {
+        if n == &BigInt::from(*p) {
+            return true;
+        }
+        if (n % *p).is_zero() {
+            return false;
+        }
+    }
+    if n < &BigInt::from(SMALL_PRIMES[SMALL_PRIMES.len() - 1].pow(2)) {
+        return true;
+    }
+    miller_rabin(n, reps)
+}
+
+fn miller_rabin(n: &BigInt, reps: usize) -> bool {
+    let n_minus_one = n - 1u64;
+    let s = (0..n_minus_one.bit_length())
+        .find(|i| n_minus_one.test_bit(*i))
+        .unwrap_or(0);
+    let d = &n_minus_one >> s;
+    // bases are sampled from [2; n-2]
+    let bases_bound = n - 3u64;
+
+    'rounds: for _ in 0..reps {
+        let a = BigInt::sample_below(&bases_bound) + 2u64;
+        let mut x = BigInt::mod_pow(&a, &d, n);
+        if x == BigInt::one() || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..s {
+            x = BigInt::mod_mul(&x, &x, n);
+            if x == n_minus_one {
+                continue 'rounds;
+            }
+            if x == BigInt::one() {
+                return false;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// Returns the smallest prime greater than `n`
+pub fn next_prime(n: &BigInt) -> BigInt {
+    if n < &BigInt::from(2) {
+        return BigInt::from(2);
+    }
+    let mut candidate = n + 1u64;
+    if candidate.is_even() && candidate != BigInt::from(2) {
+        candidate += 1u64;
+    }
+    while !probably_prime(&candidate, 20) {
+        candidate += 2u64;
+    }
+    candidate
+}
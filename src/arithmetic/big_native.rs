@@ -2,7 +2,6 @@ use std::convert::{TryFrom, TryInto};
 use std::{fmt, ops};
 
 use num_traits::Signed;
-use serde::{Deserialize, Serialize};
 
 use super::errors::*;
 use super::traits::*;
@@ -18,8 +17,8 @@ mod primes;
 /// very limited API that allows easily switching between implementations.
 ///
 /// Set of traits implemented on BigInt remains the same regardless of underlying implementation.
-#[derive(PartialOrd, PartialEq, Ord, Eq, Clone, Serialize, Deserialize)]
-#[serde(transparent)]
+/// Serialization format doesn't depend on it either, see [serde_support](super::serde_support).
+#[derive(PartialOrd, PartialEq, Ord, Eq, Clone)]
 pub struct BigInt {
     num: BN,
 }
@@ -87,6 +86,14 @@ impl Converter for BigInt {
                 radix: radix.into(),
             })
     }
+
+    fn to_signed_bytes_be(&self) -> Vec<u8> {
+        super::signed_bytes::to_signed_bytes_be(self)
+    }
+
+    fn from_signed_bytes_be(bytes: &[u8]) -> Self {
+        super::signed_bytes::from_signed_bytes_be(bytes)
+    }
 }
 
 impl num_traits::Num for BigInt {
@@ -137,6 +144,26 @@ impl Primes for BigInt {
             primes::probably_prime(self.num.magnitude(), n as usize)
         }
     }
+
+    fn sample_prime(bit_size: usize) -> Self {
+        super::primes::sample_prime(bit_size)
+    }
+
+    fn sample_safe_prime(bit_size: usize) -> Self {
+        super::primes::sample_safe_prime(bit_size)
+    }
+
+    fn sample_blum_prime(bit_size: usize) -> Self {
+        super::primes::sample_blum_prime(bit_size)
+    }
+
+    fn sample_rsa_modulus(bit_size: usize) -> (Self, Self, Self) {
+        super::primes::sample_rsa_modulus(bit_size)
+    }
+
+    fn sample_paillier_modulus(bit_size: usize) -> (Self, Self, Self) {
+        super::primes::sample_paillier_modulus(bit_size)
+    }
 }
 
 impl Modulo for BigInt {
@@ -178,6 +205,50 @@ impl Modulo for BigInt {
     }
 }
 
+impl ConstantTime for BigInt {
+    fn mod_pow_sec(base: &Self, exponent: &Self, modulus: &Self) -> Self {
+        super::constant_time::mod_pow_sec(base, exponent, modulus)
+    }
+
+    fn ct_eq(&self, other: &Self) -> Choice {
+        super::constant_time::ct_eq(self, other)
+    }
+
+    fn ct_lt(&self, other: &Self) -> Choice {
+        super::constant_time::ct_lt(self, other)
+    }
+
+    fn ct_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        super::constant_time::ct_select(a, b, choice)
+    }
+}
+
+impl NumberTheory for BigInt {
+    fn jacobi(a: &Self, n: &Self) -> Result<i8, NumberTheoryError> {
+        super::number_theory::jacobi(a, n)
+    }
+
+    fn legendre(a: &Self, p: &Self) -> Result<i8, NumberTheoryError> {
+        super::number_theory::legendre(a, p)
+    }
+
+    fn sqrt_mod_prime(a: &Self, p: &Self) -> Result<Self, NumberTheoryError> {
+        super::number_theory::sqrt_mod_prime(a, p)
+    }
+
+    fn sqrt_mod_pq(a: &Self, p: &Self, q: &Self) -> Result<Vec<Self>, NumberTheoryError> {
+        super::number_theory::sqrt_mod_pq(a, p, q)
+    }
+
+    fn is_perfect_square(&self) -> bool {
+        super::number_theory::is_perfect_square(self)
+    }
+
+    fn try_mod_inv(a: &Self, m: &Self) -> Result<Self, NumberTheoryError> {
+        super::number_theory::try_mod_inv(a, m)
+    }
+}
+
 impl BitManipulation for BigInt {
     fn set_bit(&mut self, bit: usize, bit_val: bool) {
         let mask = BigInt::one() << bit;
@@ -0,0 +1,260 @@
+//! Constant-time comparison, selection and exponentiation. Built on top of byte representation
+//! of BigInt and [subtle], so it behaves identically on every BigInt backend.
+//!
+//! Generic BigInt arithmetic can't guarantee constant time (eg. multiplication time depends on
+//! number of non-zero limbs), so the implementation is a best effort: it avoids any branches and
+//! memory accesses that depend on secret values. crypto-bigint backend doesn't use this module,
+//! as its fixed-width integers provide all of these operations natively.
+
+#[cfg(not(feature = "crypto-bigint"))]
+use subtle::{ConditionallySelectable, ConstantTimeEq};
+
+#[cfg(feature = "num-bigint")]
+use super::mod_context::ModContext;
+#[cfg(not(feature = "crypto-bigint"))]
+use super::traits::*;
+#[cfg(not(feature = "crypto-bigint"))]
+use super::BigInt;
+
+/// Number of exponent bits processed per multiplication in [mod_pow_sec]
+#[cfg(feature = "num-bigint")]
+const WINDOW: usize = 4;
+
+/// Fixed window exponentiation in Montgomery form, used by backends that have no native
+/// constant-time exponentiation (gmp backend uses `mpz_powm_sec` instead)
+///
+/// Exponent is processed as a `max(exponent.bit_length(), m.bit_length())`-bit number: every
+/// window does the same number of squarings followed by a multiplication by a table entry, which
+/// is looked up by scanning the whole table.
+#[cfg(feature = "num-bigint")]
+pub fn mod_pow_sec(base: &BigInt, exponent: &BigInt, modulus: &BigInt) -> BigInt {
+    assert!(exponent >= &BigInt::zero(), "exponent must be non-negative");
+    let ctx = ModContext::new(modulus);
+    let len = modulus.to_bytes().len();
+
+    let base = ctx.to_montgomery(base);
+    let mut table = Vec::with_capacity(1 << WINDOW);
+    let mut power = ctx.montgomery_one().clone();
+    for _ in 0..1 << WINDOW {
+        table.push(padded_bytes(&power, len));
+        power = ctx.mont_mul_sec(&power, &base);
+    }
+
+    let bits = exponent.bit_length().max(modulus.bit_length());
+    let mut acc = ctx.montgomery_one().clone();
+    for i in (0..usize::div_ceil(bits, WINDOW)).rev() {
+        for _ in 0..WINDOW {
+            acc = ctx.mont_mul_sec(&acc, &acc);
+        }
+        let digit = (0..WINDOW).fold(0u8, |digit, k| {
+            digit | (exponent.test_bit(i * WINDOW + k) as u8) << k
+        });
+        acc = ctx.mont_mul_sec(&acc, &BigInt::from_bytes(&lookup(&table, digit)));
+    }
+    ctx.redc_sec(&acc)
+}
+
+#[cfg(not(feature = "crypto-bigint"))]
+pub fn ct_eq(a: &BigInt, b: &BigInt) -> Choice {
+    let (a, b) = padded_pair(a, b);
+    a.ct_eq(&b)
+}
+
+#[cfg(not(feature = "crypto-bigint"))]
+pub fn ct_lt(a: &BigInt, b: &BigInt) -> Choice {
+    let (a, b) = padded_pair(a, b);
+    // borrow of a - b, computed from the least significant byte
+    let borrow = a.iter().zip(&b).rev().fold(0u16, |borrow, (a, b)| {
+        let diff = u16::from(*a)
+            .wrapping_sub(u16::from(*b))
+            .wrapping_sub(borrow);
+        diff >> 15
+    });
+    Choice::from(borrow as u8)
+}
+
+#[cfg(not(feature = "crypto-bigint"))]
+pub fn ct_select(a: &BigInt, b: &BigInt, choice: Choice) -> BigInt {
+    let (a, b) = padded_pair(a, b);
+    let selected: Vec<u8> = a
+        .iter()
+        .zip(&b)
+        .map(|(a, b)| u8::conditional_select(a, b, choice))
+        .collect();
+    BigInt::from_bytes(&selected)
+}
+
+/// Returns `table[index]`, touching every entry of the table
+#[cfg(feature = "num-bigint")]
+fn lookup(table: &[Vec<u8>], index: u8) -> Vec<u8> {
+    let mut result = vec![0u8; table[0].len()];
+    for (i, entry) in table.iter().enumerate() {
+        let choice = (i as u8).ct_eq(&index);
+        for (r, e) in result.iter_mut().zip(entry) {
+            r.conditional_assign(e, choice);
+        }
+    }
+    result
+}
+
+#[cfg(not(feature = "crypto-bigint"))]
+fn padded_pair(a: &BigInt, b: &BigInt) -> (Vec<u8>, Vec<u8>) {
+    assert!(
+        a >= &BigInt::zero() && b >= &BigInt::zero(),
+        "constant-time operations are defined only for non-negative numbers"
+    );
+    let len = a.to_bytes().len().max(b.to_bytes().len());
+    (padded_bytes(a, len), padded_bytes(b, len))
+}
+
+#[cfg(not(feature = "crypto-bigint"))]
+/// Big-endian representation of non-negative `n`, left-padded with zeroes up to `len` bytes
+fn padded_bytes(n: &BigInt, len: usize) -> Vec<u8> {
+    let bytes = n.to_bytes();
+    let mut padded = vec![0u8; len.saturating_sub(bytes.len())];
+    padded.extend_from_slice(&bytes);
+    padded
+}
+
+/// dudect-style timing tests: D. Reparaz, J. Balasch, I. Verbauwhede. Dude, is my code constant
+/// time? DATE 2017.
+///
+/// Inputs are split into two classes (fixed vs random secret), measurements of both classes are
+/// interleaved in random order, and Welch's t-test checks whether timing distributions differ.
+/// `|t| > 10` is considered a definite leak. Timing is noisy on shared machines, so the tests are
+/// ignored by default. Run them locally with:
+///
+/// ```text
+/// cargo test --release dudect -- --ignored --test-threads=1 --nocapture
+/// ```
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use rand::Rng;
+
+    use crate::arithmetic::traits::*;
+    use crate::arithmetic::BigInt;
+
+    const MEASUREMENTS: usize = 20_000;
+    const T_THRESHOLD: f64 = 10.0;
+
+    /// Measures `f` on inputs of both classes and returns Welch's t-statistic
+    fn dudect<I>(mut input: impl FnMut(bool) -> I, mut f: impl FnMut(&I)) -> f64 {
+        let mut rng = rand::thread_rng();
+        let mut samples: Vec<(bool, f64)> = (0..MEASUREMENTS)
+            .map(|_| {
+                let class = rng.gen::<bool>();
+                let input = input(class);
+                let start = Instant::now();
+                f(&input);
+                (class, start.elapsed().as_nanos() as f64)
+            })
+            .collect();
+
+        // crop outliers caused by interrupts and context switches
+        let mut times: Vec<f64> = samples.iter().map(|(_, t)| *t).collect();
+        times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let cutoff = times[times.len() * 9 / 10];
+        samples.retain(|(_, t)| *t <= cutoff);
+
+        let stats = |class: bool| {
+            let xs: Vec<f64> = samples
+                .iter()
+                .filter(|(c, _)| *c == class)
+                .map(|(_, t)| *t)
+                .collect();
+            let n = xs.len() as f64;
+            let mean = xs.iter().sum::<f64>() / n;
+            let var = xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            (mean, var, n)
+        };
+        let (m0, v0, n0) = stats(false);
+        let (m1, v1, n1) = stats(true);
+        let t = (m0 - m1) / (v0 / n0 + v1 / n1).sqrt();
+        println!("t = {:.2}", t);
+        t
+    }
+
+    /// Fixed class exponent has a single bit set, random class exponent has the same length
+    fn exponent(class: bool, bits: usize) -> BigInt {
+        let mut e = if class {
+            BigInt::sample(bits)
+        } else {
+            BigInt::zero()
+        };
+        e.set_bit(bits - 1, true);
+        e
+    }
+
+    #[test]
+    #[ignore]
+    fn dudect_detects_leaky_square_and_multiply() {
+        let m = BigInt::sample_prime(512);
+        let base = BigInt::sample_below(&m);
+        let t = dudect(
+            |class| exponent(class, 512),
+            |e| {
+                // multiplies only when exponent bit is set
+                (0..e.bit_length()).rev().fold(BigInt::one(), |acc, i| {
+                    let acc = BigInt::mod_mul(&acc, &acc, &m);
+                    if e.test_bit(i) {
+                        BigInt::mod_mul(&acc, &base, &m)
+                    } else {
+                        acc
+                    }
+                });
+            },
+        );
+        assert!(t.abs() > T_THRESHOLD, "harness failed to detect a leak");
+    }
+
+    #[test]
+    #[ignore]
+    fn dudect_mod_pow_sec() {
+        let m = BigInt::sample_prime(512);
+        let base = BigInt::sample_below(&m);
+        let t = dudect(
+            |class| exponent(class, 512),
+            |e| {
+                BigInt::mod_pow_sec(&base, e, &m);
+            },
+        );
+        assert!(t.abs() < T_THRESHOLD);
+    }
+
+    #[test]
+    #[ignore]
+    fn dudect_ct_eq_and_ct_lt() {
+        let mut a = BigInt::sample(1024);
+        a.set_bit(1023, true);
+        let t = dudect(
+            // equal numbers vs numbers of the same length differing in the most significant byte
+            |class| {
+                if class {
+                    &a ^ &(BigInt::one() << 1020)
+                } else {
+                    a.clone()
+                }
+            },
+            |b| {
+                a.ct_eq(b);
+                a.ct_lt(b);
+            },
+        );
+        assert!(t.abs() < T_THRESHOLD);
+    }
+
+    #[test]
+    #[ignore]
+    fn dudect_ct_select() {
+        let (a, b) = (BigInt::sample(1024), BigInt::sample(1024));
+        let t = dudect(
+            |class| Choice::from(class as u8),
+            |choice| {
+                BigInt::ct_select(&a, &b, *choice);
+            },
+        );
+        assert!(t.abs() < T_THRESHOLD);
+    }
+}
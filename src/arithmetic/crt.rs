@@ -0,0 +1,163 @@
+//! Chinese Remainder Theorem over pairwise coprime moduli. Built on top of generic BigInt
+//! operations, so it behaves identically on every BigInt backend.
+
+use super::traits::*;
+use super::BigInt;
+
+/// Precomputed coefficients for moving between `Z_N` and `Z_{m_1} × ... × Z_{m_k}`, where
+/// `N = m_1 * ... * m_k` and moduli `m_i` are pairwise coprime
+///
+/// Recombination uses Garner's algorithm, which only needs the precomputed inverses
+/// `(m_1 * ... * m_{i-1})^-1 mod m_i` and arithmetic modulo the (small) moduli.
+///
+/// ## Example
+/// Paillier decryption works modulo `N^2 = p^2 q^2`. Knowing factorization, exponentiation can be
+/// done modulo `p^2` and `q^2` separately, with exponents reduced modulo `φ(p^2)` and `φ(q^2)`:
+/// ```
+/// # use curv::arithmetic::*;
+/// let (p, q) = (BigInt::from(1_000_003), BigInt::from(999_983));
+/// let crt = Crt::from_factorization(&[(p, 2), (q, 2)]).unwrap();
+/// let (c, lambda) = (BigInt::from(123_456_789), BigInt::from(987_654_321));
+/// assert_eq!(crt.mod_pow(&c, &lambda), BigInt::mod_pow(&c, &lambda, crt.modulus()));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Crt {
+    moduli: Vec<BigInt>,
+    /// `φ(m_i)`, if factorization of the moduli is known
+    totients: Option<Vec<BigInt>>,
+    /// `(m_1 * ... * m_{i-1})^-1 mod m_i`, where first coefficient is unused
+    coefficients: Vec<BigInt>,
+    modulus: BigInt,
+}
+
+impl Crt {
+    /// Precomputes CRT coefficients for given moduli
+    ///
+    /// Returns `None` if list of moduli is empty, any modulus is less than 2, or moduli are not
+    /// pairwise coprime.
+    pub fn new(moduli: &[BigInt]) -> Option<Self> {
+        Self::with_totients(moduli.to_vec(), None)
+    }
+
+    /// Precomputes CRT coefficients for moduli `p_i^k_i` given as pairs `(p_i, k_i)`
+    ///
+    /// Every `p_i` must be prime (it's not checked). Knowing factorization allows to reduce
+    /// exponents in [mod_pow](Self::mod_pow) modulo `φ(p_i^k_i) = p_i^(k_i-1) (p_i - 1)`.
+    ///
+    /// Returns `None` if list is empty, any `k_i` is zero or primes are not distinct.
+    pub fn from_factorization(factors: &[(BigInt, u32)]) -> Option<Self> {
+        if factors.iter().any(|(_, k)| *k == 0) {
+            return None;
+        }
+        let moduli = factors.iter().map(|(p, k)| p.pow(*k)).collect();
+        let totients = factors
+            .iter()
+            .map(|(p, k)| p.pow(k - 1) * (p - 1u64))
+            .collect();
+        Self::with_totients(moduli, Some(totients))
+    }
+
+    fn with_totients(moduli: Vec<BigInt>, totients: Option<Vec<BigInt>>) -> Option<Self> {
+        if moduli.is_empty() || moduli.iter().any(|m| m <= &BigInt::one()) {
+            return None;
+        }
+        let mut coefficients = vec![BigInt::one()];
+        let mut modulus = moduli[0].clone();
+        for m in &moduli[1..] {
+            // fails if m isn't coprime with any of previous moduli
+            coefficients.push(BigInt::mod_inv(&modulus, m)?);
+            modulus *= m;
+        }
+        Some(Crt {
+            moduli,
+            totients,
+            coefficients,
+            modulus,
+        })
+    }
+
+    pub fn moduli(&self) -> &[BigInt] {
+        &self.moduli
+    }
+
+    /// Product of all moduli
+    pub fn modulus(&self) -> &BigInt {
+        &self.modulus
+    }
+
+    /// Maps `x` to its residues `x mod m_i`
+    pub fn split(&self, x: &BigInt) -> Vec<BigInt> {
+        self.moduli.iter().map(|m| x.modulus(m)).collect()
+    }
+
+    /// Finds the only `x` in `[0; N)` such that `x = residues[i] (mod m_i)`
+    ///
+    /// ## Panics
+    /// Panics if number of residues doesn't match number of moduli
+    pub fn recombine(&self, residues: &[BigInt]) -> BigInt {
+        assert_eq!(
+            residues.len(),
+            self.moduli.len(),
+            "expected one residue per modulus"
+        );
+        let mut x = residues[0].modulus(&self.moduli[0]);
+        let mut product = self.moduli[0].clone();
+        for ((r, m), c) in residues
+            .iter()
+            .zip(&self.moduli)
+            .zip(&self.coefficients)
+            .skip(1)
+        {
+            // x + t * product = r (mod m)
+            let t = BigInt::mod_mul(&BigInt::mod_sub(r, &x, m), c, m);
+            x += t * &product;
+            product *= m;
+        }
+        x
+    }
+
+    /// Calculates `base^exponent (mod N)` by exponentiating modulo every `m_i` separately
+    ///
+    /// If the instance was constructed [from factorization](Self::from_factorization), exponent
+    /// is additionally reduced modulo `φ(m_i)` for every base coprime with `m_i`.
+    ///
+    /// ## Panics
+    /// Panics if exponent is negative
+    pub fn mod_pow(&self, base: &BigInt, exponent: &BigInt) -> BigInt {
+        self.mod_pow_with(base, exponent, BigInt::mod_pow)
+    }
+
+    /// Same as [mod_pow](Self::mod_pow), but uses [ConstantTime::mod_pow_sec] for every
+    /// exponentiation, so running time doesn't depend on the exponent value
+    ///
+    /// ## Panics
+    /// Panics if exponent is negative or any of moduli is even
+    pub fn mod_pow_sec(&self, base: &BigInt, exponent: &BigInt) -> BigInt {
+        self.mod_pow_with(base, exponent, BigInt::mod_pow_sec)
+    }
+
+    fn mod_pow_with(
+        &self,
+        base: &BigInt,
+        exponent: &BigInt,
+        pow: impl Fn(&BigInt, &BigInt, &BigInt) -> BigInt,
+    ) -> BigInt {
+        assert!(exponent >= &BigInt::zero(), "exponent must be non-negative");
+        let residues: Vec<BigInt> = self
+            .moduli
+            .iter()
+            .enumerate()
+            .map(|(i, m)| {
+                let base = base.modulus(m);
+                match &self.totients {
+                    // Euler's theorem only holds for bases coprime with the modulus
+                    Some(totients) if base.gcd(m) == BigInt::one() => {
+                        pow(&base, &exponent.modulus(&totients[i]), m)
+                    }
+                    _ => pow(&base, exponent, m),
+                }
+            })
+            .collect();
+        self.recombine(&residues)
+    }
+}
@@ -13,6 +13,8 @@ pub enum ParseErrorReason {
     Gmp(gmp::mpz::ParseMpzError),
     #[cfg(feature = "num-bigint")]
     NumBigint,
+    #[cfg(feature = "crypto-bigint")]
+    CryptoBigint,
 }
 
 impl fmt::Display for ParseBigIntError {
@@ -24,6 +26,13 @@ impl fmt::Display for ParseBigIntError {
             ParseErrorReason::NumBigint => {
                 write!(f, "invalid {}-based number representation", self.radix)
             }
+            #[cfg(feature = "crypto-bigint")]
+            ParseErrorReason::CryptoBigint => write!(
+                f,
+                "invalid {}-based number representation or number doesn't fit into {} bits",
+                self.radix,
+                super::BigInt::BITS
+            ),
         }
     }
 }
@@ -35,6 +44,8 @@ impl error::Error for ParseBigIntError {
             ParseErrorReason::Gmp(reason) => Some(reason),
             #[cfg(feature = "num-bigint")]
             ParseErrorReason::NumBigint => None,
+            #[cfg(feature = "crypto-bigint")]
+            ParseErrorReason::CryptoBigint => None,
         }
     }
 }
@@ -52,3 +63,28 @@ impl fmt::Display for TryFromBigIntError {
 }
 
 impl error::Error for TryFromBigIntError {}
+
+/// Error type returned by [NumberTheory](super::traits::NumberTheory) methods
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberTheoryError {
+    /// Modulus doesn't satisfy requirements of the method (eg. it's not odd, or not prime)
+    InvalidModulus,
+    /// Number is not coprime with the modulus
+    NotInvertible,
+    /// Number has no square root modulo given modulus
+    NotQuadraticResidue,
+}
+
+impl fmt::Display for NumberTheoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberTheoryError::InvalidModulus => write!(f, "invalid modulus"),
+            NumberTheoryError::NotInvertible => write!(f, "number is not invertible"),
+            NumberTheoryError::NotQuadraticResidue => {
+                write!(f, "number is not a quadratic residue")
+            }
+        }
+    }
+}
+
+impl error::Error for NumberTheoryError {}
@@ -14,14 +14,29 @@
     @license GPL-3.0+ <https://github.com/KZen-networks/cryptography-utils/blob/master/LICENSE>
 */
 
+mod constant_time;
+mod crt;
 mod errors;
 mod macros;
+mod mod_context;
+mod number_theory;
+mod primes;
 mod samplable;
+pub mod serde_support;
+mod signed_bytes;
 pub mod traits;
 
-#[cfg(not(any(feature = "rust-gmp-kzen", feature = "num-bigint")))]
+#[cfg(not(any(
+    feature = "rust-gmp-kzen",
+    feature = "num-bigint",
+    feature = "crypto-bigint"
+)))]
 compile_error!("You need to choose which bigint implementation to use. See crate features.");
-#[cfg(all(feature = "rust-gmp-kzen", feature = "num-bigint"))]
+#[cfg(any(
+    all(feature = "rust-gmp-kzen", feature = "num-bigint"),
+    all(feature = "rust-gmp-kzen", feature = "crypto-bigint"),
+    all(feature = "num-bigint", feature = "crypto-bigint"),
+))]
 compile_error!("You can choose only one bigint implementation. See crate features.");
 
 #[cfg(feature = "rust-gmp-kzen")]
@@ -34,7 +49,14 @@ mod big_native;
 #[cfg(feature = "num-bigint")]
 pub use big_native::BigInt;
 
-pub use errors::{ParseBigIntError, TryFromBigIntError};
+#[cfg(feature = "crypto-bigint")]
+mod big_crypto;
+#[cfg(feature = "crypto-bigint")]
+pub use big_crypto::BigInt;
+
+pub use crt::Crt;
+pub use errors::{NumberTheoryError, ParseBigIntError, TryFromBigIntError};
+pub use mod_context::{FixedBaseTable, ModContext};
 pub use traits::*;
 
 #[cfg(test)]
@@ -73,6 +95,83 @@ mod test {
         assert_eq!(n, BigInt::from(1_000_000_u32))
     }
 
+    #[test]
+    fn try_from_bytes_handles_oversized_numbers() {
+        let n = BigInt::try_from_bytes(b"\x0f\x42\x40");
+        assert_eq!(n, Some(BigInt::from(1_000_000_u32)));
+
+        // leading zeroes don't count, while 8200 bits are more than fixed-width backend holds
+        let mut bytes = vec![0u8; 2048];
+        bytes[1023] = 1;
+        let n = BigInt::try_from_bytes(&bytes);
+        if cfg!(feature = "crypto-bigint") {
+            assert_eq!(n, None);
+        } else {
+            assert_eq!(n, Some(BigInt::one() << 8192));
+        }
+        assert_eq!(BigInt::try_from_bytes(&bytes[1024..]), Some(BigInt::zero()));
+    }
+
+    #[test]
+    fn padded_and_little_endian_bytes() {
+        let n = BigInt::from(1_000_000_u32);
+        assert_eq!(n.to_bytes_be_padded(3), b"\x0f\x42\x40");
+        assert_eq!(n.to_bytes_be_padded(5), b"\x00\x00\x0f\x42\x40");
+        assert_eq!((-&n).to_bytes_be_padded(4), b"\x00\x0f\x42\x40");
+        assert_eq!(BigInt::zero().to_bytes_be_padded(0), b"");
+        assert_eq!(BigInt::zero().to_bytes_be_padded(2), b"\x00\x00");
+        assert_eq!(n.to_bytes_le(), b"\x40\x42\x0f");
+        assert_eq!(BigInt::from_bytes_le(b"\x40\x42\x0f\x00"), n);
+    }
+
+    #[test]
+    #[should_panic]
+    fn to_bytes_be_padded_panics_if_number_doesnt_fit() {
+        BigInt::from(1_000_000_u32).to_bytes_be_padded(2);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn fuzz_signed_bytes_agree_with_i32(n: i32) {
+            test_signed_bytes(n)
+        }
+    }
+    fn test_signed_bytes(n: i32) {
+        let expected = n.to_be_bytes();
+        // Minimal two's complement: strip sign-extension bytes
+        let redundant = expected
+            .windows(2)
+            .take_while(|w| (w[0] == 0 && w[1] < 0x80) || (w[0] == 0xff && w[1] >= 0x80))
+            .count();
+        let expected = &expected[redundant..];
+
+        let bn = BigInt::from(n);
+        assert_eq!(bn.to_signed_bytes_be(), expected);
+        assert_eq!(BigInt::from_signed_bytes_be(expected), bn);
+        assert_eq!(BigInt::from_signed_bytes_be(&n.to_be_bytes()), bn);
+
+        let mut expected_le = expected.to_vec();
+        expected_le.reverse();
+        assert_eq!(bn.to_signed_bytes_le(), expected_le);
+        assert_eq!(BigInt::from_signed_bytes_le(&n.to_le_bytes()), bn);
+    }
+
+    #[test]
+    fn signed_bytes_edge_cases() {
+        assert_eq!(BigInt::from_signed_bytes_be(&[]), BigInt::zero());
+        assert_eq!(BigInt::zero().to_signed_bytes_be(), [0]);
+
+        // -2^200 = 0xff00...00, 2^200 = 0x0100...00
+        let big = BigInt::one() << 200;
+        let mut expected = vec![0xff];
+        expected.extend(std::iter::repeat_n(0u8, 25));
+        assert_eq!((-&big).to_signed_bytes_be(), expected);
+        assert_eq!(BigInt::from_signed_bytes_be(&expected), -&big);
+        expected[0] = 0x01;
+        assert_eq!(big.to_signed_bytes_be(), expected);
+        assert_eq!(BigInt::from_signed_bytes_be(&expected), big);
+    }
+
     #[test]
     fn count_bits() {
         let mut n = BigInt::one();
@@ -331,6 +430,531 @@ mod test {
         assert!(prime.is_probable_prime(20));
     }
 
+    #[test]
+    fn sampled_primes_have_requested_bit_length() {
+        for &bits in &[2, 3, 7, 12, 13, 64, 256] {
+            let p = BigInt::sample_prime(bits);
+            assert_eq!(p.bit_length(), bits);
+            assert!(p.is_probable_prime(20));
+
+            let p = BigInt::sample_blum_prime(bits);
+            assert_eq!(p.bit_length(), bits);
+            assert!(p.is_probable_prime(20));
+            assert_eq!(p.modulus(&BigInt::from(4)), BigInt::from(3));
+        }
+    }
+
+    #[test]
+    fn sampled_safe_primes_have_requested_bit_length() {
+        for &bits in &[3, 8, 12, 13, 64, 256] {
+            let p = BigInt::sample_safe_prime(bits);
+            assert_eq!(p.bit_length(), bits);
+            assert!(p.is_probable_prime(20));
+            let q = (&p - 1u64) >> 1;
+            assert!(q.is_probable_prime(20));
+        }
+    }
+
+    #[test]
+    fn sampled_moduli_have_requested_bit_length() {
+        for &bits in &[16, 17, 33, 512, 513] {
+            let (n, p, q) = BigInt::sample_rsa_modulus(bits);
+            assert_eq!(n.bit_length(), bits);
+            assert_eq!(n, &p * &q);
+            assert_ne!(p, q);
+            assert!(p.is_probable_prime(20) && q.is_probable_prime(20));
+
+            let (n, p, q) = BigInt::sample_paillier_modulus(bits);
+            assert_eq!(n.bit_length(), bits);
+            let phi = (&p - 1u64) * (&q - 1u64);
+            assert_eq!(n.gcd(&phi), BigInt::one());
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn fuzz_mod_context_agrees_with_modulo(
+            a in proptest::collection::vec(proptest::num::u64::ANY, 1..6),
+            b in proptest::collection::vec(proptest::num::u64::ANY, 1..6),
+            e in proptest::collection::vec(proptest::num::u64::ANY, 0..5),
+            n in proptest::collection::vec(proptest::num::u64::ANY, 1..5),
+        ) {
+            test_mod_context(&a, &b, &e, &n)
+        }
+    }
+
+    /// Limbs with values that trigger corner cases of multi-precision division
+    fn limb() -> impl proptest::strategy::Strategy<Value = u64> {
+        proptest::prop_oneof![
+            proptest::strategy::Just(0),
+            proptest::strategy::Just(1),
+            proptest::strategy::Just(1 << 63),
+            proptest::strategy::Just(u64::MAX),
+            proptest::num::u64::ANY,
+        ]
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn fuzz_div_rem(
+            a in proptest::collection::vec(limb(), 0..8),
+            b in proptest::collection::vec(limb(), 1..6),
+            negate_a: bool,
+            negate_b: bool,
+        ) {
+            test_div_rem(&a, &b, negate_a, negate_b)
+        }
+    }
+
+    fn test_div_rem(a: &[u64], b: &[u64], negate_a: bool, negate_b: bool) {
+        let (mut a, mut b) = (from_limbs(a), from_limbs(b));
+        if b.is_zero() {
+            b = BigInt::one();
+        }
+        if negate_a {
+            a = -a;
+        }
+        if negate_b {
+            b = -b;
+        }
+        let (q, r) = (&a / &b, &a % &b);
+        assert_eq!(&q * &b + &r, a);
+        assert!(r.abs() < b.abs());
+        assert!(r.is_zero() || (r < BigInt::zero()) == (a < BigInt::zero()));
+    }
+
+    fn from_limbs(limbs: &[u64]) -> BigInt {
+        limbs
+            .iter()
+            .fold(BigInt::zero(), |acc, limb| (acc << 64) + *limb)
+    }
+
+    fn test_mod_context(a: &[u64], b: &[u64], e: &[u64], n: &[u64]) {
+        let mut n = from_limbs(n);
+        n.set_bit(0, true);
+        if n == BigInt::one() {
+            n = BigInt::from(3);
+        }
+        let (a, b, e) = (from_limbs(a), from_limbs(b), from_limbs(e));
+        let ctx = ModContext::new(&n);
+
+        assert_eq!(ctx.mul(&a, &b), BigInt::mod_mul(&a, &b, &n));
+        assert_eq!(ctx.pow(&a, &e), BigInt::mod_pow(&a, &e, &n));
+        assert_eq!(ctx.inv(&a), BigInt::mod_inv(&a, &n));
+        assert_eq!(
+            ctx.multi_pow(&[(&a, &e), (&b, &a)]),
+            BigInt::mod_mul(
+                &BigInt::mod_pow(&a, &e, &n),
+                &BigInt::mod_pow(&b, &a, &n),
+                &n
+            )
+        );
+        let table = ctx.fixed_base_table(&a, 256);
+        assert_eq!(table.pow(&e), BigInt::mod_pow(&a, &e, &n));
+        assert_eq!(table.pow(&b), BigInt::mod_pow(&a, &b, &n));
+    }
+
+    #[test]
+    fn mod_context_handles_edge_cases() {
+        let n = BigInt::from(3);
+        let ctx = ModContext::new(&n);
+        assert_eq!(ctx.pow(&BigInt::from(5), &BigInt::zero()), BigInt::one());
+        assert_eq!(ctx.pow(&BigInt::zero(), &BigInt::from(5)), BigInt::zero());
+        assert_eq!(ctx.multi_pow(&[]), BigInt::one());
+        assert_eq!(ctx.inv(&BigInt::from(6)), None);
+        let table = ctx.fixed_base_table(&BigInt::from(2), 0);
+        assert_eq!(table.pow(&BigInt::zero()), BigInt::one());
+        assert_eq!(table.pow(&BigInt::from(3)), BigInt::from(2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn mod_context_panics_on_even_modulus() {
+        ModContext::new(&BigInt::from(10));
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn fuzz_mod_pow_sec_agrees_with_mod_pow(
+            b in proptest::collection::vec(proptest::num::u64::ANY, 0..6),
+            e in proptest::collection::vec(proptest::num::u64::ANY, 0..6),
+            m in proptest::collection::vec(proptest::num::u64::ANY, 1..5),
+        ) {
+            test_mod_pow_sec(&b, &e, &m)
+        }
+    }
+
+    fn test_mod_pow_sec(b: &[u64], e: &[u64], m: &[u64]) {
+        let mut m = from_limbs(m);
+        m.set_bit(0, true);
+        if m == BigInt::one() {
+            m = BigInt::from(3);
+        }
+        let (b, e) = (from_limbs(b), from_limbs(e));
+        assert_eq!(BigInt::mod_pow_sec(&b, &e, &m), BigInt::mod_pow(&b, &e, &m));
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn fuzz_constant_time_comparison_and_selection(
+            a in proptest::collection::vec(proptest::num::u64::ANY, 0..4),
+            b in proptest::collection::vec(proptest::num::u64::ANY, 0..4),
+        ) {
+            let (a, b) = (from_limbs(&a), from_limbs(&b));
+            assert_eq!(bool::from(a.ct_eq(&b)), a == b);
+            assert!(bool::from(a.ct_eq(&a)));
+            assert_eq!(bool::from(a.ct_lt(&b)), a < b);
+            assert_eq!(bool::from(b.ct_lt(&a)), b < a);
+            assert_eq!(BigInt::ct_select(&a, &b, Choice::from(0)), a);
+            assert_eq!(BigInt::ct_select(&a, &b, Choice::from(1)), b);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn mod_pow_sec_panics_on_even_modulus() {
+        BigInt::mod_pow_sec(&BigInt::from(3), &BigInt::from(5), &BigInt::from(10));
+    }
+
+    /// Distinct primes, each being the next prime after a seed
+    fn distinct_primes(seeds: &[u32]) -> Vec<BigInt> {
+        let mut primes: Vec<BigInt> = seeds
+            .iter()
+            .map(|s| BigInt::from(*s).next_prime())
+            .collect();
+        primes.sort();
+        primes.dedup();
+        primes
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn fuzz_crt_split_and_recombine(
+            seeds in proptest::collection::vec(proptest::num::u32::ANY, 1..5),
+            powers in proptest::collection::vec(1u32..4, 4),
+            x in proptest::collection::vec(proptest::num::u64::ANY, 0..8),
+        ) {
+            test_crt_split_and_recombine(&seeds, &powers, &x)
+        }
+
+        #[test]
+        fn fuzz_crt_mod_pow(
+            seeds in proptest::collection::vec(3u32.., 1..5),
+            powers in proptest::collection::vec(1u32..4, 4),
+            base in proptest::collection::vec(proptest::num::u64::ANY, 0..8),
+            e in proptest::collection::vec(proptest::num::u64::ANY, 0..8),
+            divisible_by_first_prime: bool,
+        ) {
+            test_crt_mod_pow(&seeds, &powers, &base, &e, divisible_by_first_prime)
+        }
+    }
+
+    fn test_crt_split_and_recombine(seeds: &[u32], powers: &[u32], x: &[u64]) {
+        let moduli: Vec<BigInt> = distinct_primes(seeds)
+            .iter()
+            .zip(powers)
+            .map(|(p, k)| p.pow(*k))
+            .collect();
+        let crt = Crt::new(&moduli).unwrap();
+        let x = from_limbs(x).modulus(crt.modulus());
+
+        let residues = crt.split(&x);
+        for (r, m) in residues.iter().zip(&moduli) {
+            assert_eq!(r, &x.modulus(m));
+        }
+        assert_eq!(crt.recombine(&residues), x);
+    }
+
+    fn test_crt_mod_pow(seeds: &[u32], powers: &[u32], base: &[u64], e: &[u64], divisible: bool) {
+        let factors: Vec<(BigInt, u32)> = distinct_primes(seeds)
+            .into_iter()
+            .zip(powers.iter().cloned())
+            .collect();
+        let crt = Crt::from_factorization(&factors).unwrap();
+        let n = crt.modulus();
+        let mut base = from_limbs(base);
+        if divisible {
+            base *= &factors[0].0;
+        }
+        let e = from_limbs(e);
+
+        let expected = BigInt::mod_pow(&base, &e, n);
+        assert_eq!(crt.mod_pow(&base, &e), expected);
+        assert_eq!(crt.mod_pow_sec(&base, &e), expected);
+        let no_totients = Crt::new(crt.moduli()).unwrap();
+        assert_eq!(no_totients.mod_pow(&base, &e), expected);
+    }
+
+    #[test]
+    fn crt_rejects_invalid_moduli() {
+        let (two, three, six) = (BigInt::from(2), BigInt::from(3), BigInt::from(6));
+        assert!(Crt::new(&[]).is_none());
+        assert!(Crt::new(&[three.clone(), BigInt::one()]).is_none());
+        assert!(Crt::new(&[three.clone(), six.clone()]).is_none());
+        assert!(Crt::from_factorization(&[(three.clone(), 0)]).is_none());
+        assert!(Crt::from_factorization(&[(three.clone(), 1), (three.clone(), 2)]).is_none());
+        assert_eq!(Crt::new(&[two, three]).unwrap().modulus(), &six);
+    }
+
+    /// Primes covering all branches of `sqrt_mod_prime`: `p = 3 (mod 4)`, Tonelli-Shanks with
+    /// small and large 2-adicity, and Cipolla
+    const SQRT_PRIMES: &[&str] = &[
+        "1000000007",
+        "998244353",
+        // 2^64 - 2^32 + 1
+        "18446744069414584321",
+        // Ed25519: 2^255 - 19
+        "57896044618658097711785492504343953926634992332820282019728792003956564819949",
+        // BLS12-381 scalar field
+        "52435875175126190479447740508185965837690552500527637822603658699938581184513",
+    ];
+
+    proptest::proptest! {
+        #[test]
+        fn fuzz_jacobi_agrees_with_euler_criterion(
+            a in proptest::collection::vec(proptest::num::u64::ANY, 0..5),
+            p in 0..SQRT_PRIMES.len(),
+        ) {
+            let p = BigInt::from_str_radix(SQRT_PRIMES[p], 10).unwrap();
+            let a = from_limbs(&a);
+            let euler = BigInt::mod_pow(&a, &((&p - 1u64) >> 1), &p);
+            let expected = if euler.is_zero() { 0 } else if euler.is_one() { 1 } else { -1 };
+            assert_eq!(BigInt::legendre(&a, &p).unwrap(), expected);
+            assert_eq!(BigInt::jacobi(&(-a), &p).unwrap(), BigInt::legendre(&(&p - 1u64), &p).unwrap() * expected);
+        }
+
+        #[test]
+        fn fuzz_jacobi_is_multiplicative(
+            a in proptest::num::i32::ANY,
+            n1 in proptest::num::u32::ANY,
+            n2 in proptest::num::u32::ANY,
+        ) {
+            let a = BigInt::from(a);
+            let (n1, n2) = (BigInt::from(n1 | 1), BigInt::from(n2 | 1));
+            assert_eq!(
+                BigInt::jacobi(&a, &(&n1 * &n2)).unwrap(),
+                BigInt::jacobi(&a, &n1).unwrap() * BigInt::jacobi(&a, &n2).unwrap()
+            );
+        }
+
+        #[test]
+        fn fuzz_sqrt_mod_prime(
+            x in proptest::collection::vec(proptest::num::u64::ANY, 0..5),
+            p in 0..SQRT_PRIMES.len(),
+        ) {
+            let p = BigInt::from_str_radix(SQRT_PRIMES[p], 10).unwrap();
+            let x = from_limbs(&x).modulus(&p);
+            let square = BigInt::mod_mul(&x, &x, &p);
+            let root = BigInt::sqrt_mod_prime(&square, &p).unwrap();
+            assert!(root == x || root == (&p - &x).modulus(&p));
+
+            let non_residue = (1u64..).map(BigInt::from).find(|z| BigInt::legendre(z, &p).unwrap() == -1).unwrap();
+            if !x.is_zero() {
+                let err = BigInt::sqrt_mod_prime(&(square * non_residue), &p).unwrap_err();
+                assert_eq!(err, NumberTheoryError::NotQuadraticResidue);
+            }
+        }
+
+        #[test]
+        fn fuzz_sqrt_mod_pq(x in proptest::num::u64::ANY, p in 0..3usize, q in 0..3usize) {
+            let p = BigInt::from_str_radix(SQRT_PRIMES[p], 10).unwrap();
+            let q = BigInt::from_str_radix(SQRT_PRIMES[q], 10).unwrap();
+            let n = &p * &q;
+            let square = BigInt::mod_mul(&BigInt::from(x), &BigInt::from(x), &n);
+            match BigInt::sqrt_mod_pq(&square, &p, &q) {
+                Err(err) => assert!(p == q && err == NumberTheoryError::InvalidModulus),
+                Ok(roots) => {
+                    assert!(roots.contains(&BigInt::from(x).modulus(&n)));
+                    if BigInt::from(x).gcd(&n).is_one() {
+                        assert_eq!(roots.len(), 4);
+                    }
+                    for r in roots {
+                        assert_eq!(BigInt::mod_mul(&r, &r, &n), square);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn fuzz_is_perfect_square(n in proptest::collection::vec(proptest::num::u64::ANY, 1..5)) {
+            let n = from_limbs(&n);
+            let square = &n * &n;
+            assert!(square.is_perfect_square());
+            assert!(!(n.is_zero() || (&square + 1u64).is_perfect_square()));
+            assert!(!(-square - 1u64).is_perfect_square());
+        }
+    }
+
+    #[test]
+    fn number_theory_rejects_invalid_input() {
+        let (a, even) = (BigInt::from(5), BigInt::from(10));
+        assert_eq!(
+            BigInt::jacobi(&a, &even),
+            Err(NumberTheoryError::InvalidModulus)
+        );
+        assert_eq!(
+            BigInt::jacobi(&a, &BigInt::from(-3)),
+            Err(NumberTheoryError::InvalidModulus)
+        );
+        assert_eq!(
+            BigInt::legendre(&a, &BigInt::one()),
+            Err(NumberTheoryError::InvalidModulus)
+        );
+        assert_eq!(BigInt::jacobi(&BigInt::from(6), &BigInt::from(9)), Ok(0));
+        assert_eq!(
+            BigInt::try_mod_inv(&BigInt::from(6), &even),
+            Err(NumberTheoryError::NotInvertible)
+        );
+        assert_eq!(
+            BigInt::try_mod_inv(&a, &BigInt::zero()),
+            Err(NumberTheoryError::InvalidModulus)
+        );
+        assert_eq!(
+            BigInt::try_mod_inv(&BigInt::from(3), &even),
+            Ok(BigInt::from(7))
+        );
+        // 15 is not prime, and 4 = 2^2 (mod 15) still has a root, but 2 doesn't
+        assert!(BigInt::sqrt_mod_prime(&BigInt::from(2), &BigInt::from(15)).is_err());
+    }
+
+    #[test]
+    fn sqrt_mod_prime_rejects_perfect_squares() {
+        // there are no non-residues modulo a perfect square, so the search used to never end:
+        // 1_000_000_007^2 takes Tonelli-Shanks path, (2^127 - 1)^2 takes Cipolla path
+        for q in [BigInt::from(1_000_000_007), (BigInt::one() << 127) - 1u64].iter() {
+            let p = q * q;
+            assert_eq!(
+                BigInt::sqrt_mod_prime(&BigInt::from(7), &p),
+                Err(NumberTheoryError::InvalidModulus)
+            );
+        }
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct SerdeFormats {
+        default: BigInt,
+        #[serde(with = "serde_support::Hex")]
+        hex: BigInt,
+        #[serde(with = "serde_support::Decimal")]
+        decimal: BigInt,
+        #[serde(with = "serde_support::Base64")]
+        base64: BigInt,
+        #[serde(with = "serde_support::BytesBe::<4>")]
+        bytes: BigInt,
+    }
+
+    impl SerdeFormats {
+        fn new(n: &BigInt) -> Self {
+            SerdeFormats {
+                default: n.clone(),
+                hex: n.clone(),
+                decimal: n.clone(),
+                base64: n.abs(),
+                bytes: n.abs().modulus(&BigInt::from(1u64 << 32)),
+            }
+        }
+    }
+
+    /// Expected encodings are hardcoded, so the test checks that every backend produces exactly
+    /// the same output
+    #[test]
+    fn serde_formats_dont_depend_on_backend() {
+        let n = SerdeFormats::new(&BigInt::from(-1_000_000));
+        let json = r#"{"default":"-f4240","hex":"-f4240","decimal":"-1000000","base64":"D0JA","bytes":[0,15,66,64]}"#;
+        assert_eq!(serde_json::to_string(&n).unwrap(), json);
+        assert_eq!(serde_json::from_str::<SerdeFormats>(json).unwrap(), n);
+
+        let bincode = [
+            &[6, 0, 0, 0, 0, 0, 0, 0][..],
+            b"-f4240",
+            &[6, 0, 0, 0, 0, 0, 0, 0],
+            b"-f4240",
+            &[8, 0, 0, 0, 0, 0, 0, 0],
+            b"-1000000",
+            &[4, 0, 0, 0, 0, 0, 0, 0],
+            b"D0JA",
+            &[0, 15, 66, 64],
+        ]
+        .concat();
+        assert_eq!(bincode::serialize(&n).unwrap(), bincode);
+        assert_eq!(bincode::deserialize::<SerdeFormats>(&bincode).unwrap(), n);
+
+        let zero = SerdeFormats::new(&BigInt::zero());
+        let json = r#"{"default":"0","hex":"0","decimal":"0","base64":"AA==","bytes":[0,0,0,0]}"#;
+        assert_eq!(serde_json::to_string(&zero).unwrap(), json);
+        assert_eq!(serde_json::from_str::<SerdeFormats>(json).unwrap(), zero);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn fuzz_serde_formats_round_trip(n in proptest::collection::vec(limb(), 0..8), negative: bool) {
+            test_serde_formats_round_trip(&n, negative)
+        }
+    }
+
+    fn test_serde_formats_round_trip(n: &[u64], negative: bool) {
+        let mut n = from_limbs(n);
+        if negative {
+            n = -n;
+        }
+        let formats = SerdeFormats::new(&n);
+        let json = serde_json::to_string(&formats).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SerdeFormats>(&json).unwrap(),
+            formats
+        );
+        let bytes = bincode::serialize(&formats).unwrap();
+        assert_eq!(
+            bincode::deserialize::<SerdeFormats>(&bytes).unwrap(),
+            formats
+        );
+    }
+
+    #[test]
+    fn serde_formats_reject_unrepresentable_numbers() {
+        #[derive(serde::Serialize)]
+        struct Bytes(#[serde(with = "serde_support::BytesBe::<4>")] BigInt);
+        #[derive(serde::Serialize)]
+        struct Base64(#[serde(with = "serde_support::Base64")] BigInt);
+
+        assert!(serde_json::to_string(&Bytes(BigInt::from(-1))).is_err());
+        assert!(serde_json::to_string(&Bytes(BigInt::one() << 32)).is_err());
+        assert!(serde_json::to_string(&Bytes((BigInt::one() << 32) - 1u64)).is_ok());
+        assert!(serde_json::to_string(&Base64(BigInt::from(-1))).is_err());
+
+        assert!(serde_json::from_str::<BigInt>(r#""xyz""#).is_err());
+        assert!(serde_json::from_str::<BigInt>("1").is_err());
+    }
+
+    #[test]
+    fn serde_formats_handle_oversized_numbers() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Bytes(#[serde(with = "serde_support::BytesBe::<1025>")] BigInt);
+        #[derive(Debug, serde::Deserialize)]
+        struct Base64(#[serde(with = "serde_support::Base64")] BigInt);
+
+        // 8200 bits are more than the widest fixed-width backend holds
+        let mut bytes = vec![0u8; 1025];
+        bytes[0] = 1;
+        let base64 = serde_json::from_str::<Base64>(&format!("\"{}\"", base64::encode(&bytes)));
+        let bytes = bincode::deserialize::<Bytes>(&bytes);
+        if cfg!(feature = "crypto-bigint") {
+            assert!(base64.is_err());
+            assert!(bytes.is_err());
+        } else {
+            assert_eq!(base64.unwrap().0, BigInt::one() << 8192);
+            assert_eq!(bytes.unwrap().0, BigInt::one() << 8192);
+        }
+
+        // leading zeros don't count
+        let mut bytes = vec![0u8; 1025];
+        bytes[1024] = 7;
+        assert_eq!(
+            bincode::deserialize::<Bytes>(&bytes).unwrap().0,
+            BigInt::from(7)
+        );
+    }
+
     /// This test will fail to compile if BigInt doesn't implement certain traits.
     #[test]
     fn big_int_implements_all_required_trait() {
@@ -345,7 +969,7 @@ mod test {
         T: fmt::Display + fmt::Debug,
         // Basic traits from self::traits module
         T: Converter + BasicOps + Modulo + Samplable + NumberTests + EGCD + BitManipulation,
-        T: Primes,
+        T: Primes + ConstantTime + NumberTheory,
         // Deprecated but not deleted yet traits from self::traits module
         T: ZeroizeBN,
         u64: ConvertFrom<BigInt>,
@@ -0,0 +1,284 @@
+//! Modular arithmetic under a fixed odd modulus using Montgomery representation. Built on top
+//! of generic BigInt operations, so it behaves identically on every BigInt backend.
+
+use std::borrow::Cow;
+
+use super::traits::*;
+use super::BigInt;
+
+/// Precomputed parameters for repeated arithmetic modulo a fixed odd number `N`
+///
+/// Numbers are internally kept in Montgomery form `aR mod N`, where `R = 2^k > N`, which
+/// replaces division by `N` with shifts and masks. Converting into and out of Montgomery form
+/// is done once per public method call, and inputs are only reduced modulo `N` when they're out
+/// of range. [multi_pow](Self::multi_pow) additionally shares squarings between the bases and
+/// [FixedBaseTable] doesn't square at all.
+///
+/// Montgomery reduction built from generic BigInt operations costs three big multiplications,
+/// so a single [mul](Self::mul) or [pow](Self::pow) is about 1.5-2.5 times slower than the
+/// backend's native [Modulo::mod_mul]/[Modulo::mod_pow] with both GMP and num-bigint (see
+/// `benches/mod_context.rs`). Prefer the latter unless the same modulus is used in
+/// [multi_pow](Self::multi_pow) or [FixedBaseTable].
+///
+/// ## Example
+/// ```
+/// # use curv::arithmetic::*;
+/// let n = BigInt::from(1_000_003);
+/// let ctx = ModContext::new(&n);
+/// let (a, e) = (BigInt::from(12345), BigInt::from(6789));
+/// assert_eq!(ctx.pow(&a, &e), BigInt::mod_pow(&a, &e, &n));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModContext {
+    modulus: BigInt,
+    /// `R = 2^r_bits`
+    r_bits: usize,
+    /// `R - 1`
+    r_mask: BigInt,
+    /// `-N^-1 mod R`
+    n_prime: BigInt,
+    /// `R^2 mod N`
+    r2: BigInt,
+    /// `R mod N`, ie. 1 in Montgomery form
+    one: BigInt,
+}
+
+/// Precomputed powers of a fixed base for exponentiation without squarings
+///
+/// Table holds `base^(j * 2^(w*i))` for every window `i` and digit `j < 2^w`, so `base^e` is
+/// a product of one entry per `w`-bit window of `e`.
+#[derive(Clone, Debug)]
+pub struct FixedBaseTable {
+    context: ModContext,
+    base: BigInt,
+    window: usize,
+    max_exponent_bits: usize,
+    table: Vec<Vec<BigInt>>,
+}
+
+impl ModContext {
+    /// Precomputes Montgomery parameters for modulus `N`
+    ///
+    /// ## Panics
+    /// Panics if `N` is not odd or is less than 3
+    pub fn new(modulus: &BigInt) -> Self {
+        assert!(
+            modulus > &BigInt::from(1) && modulus.test_bit(0),
+            "modulus must be odd and greater than 1"
+        );
+        let r_bits = usize::div_ceil(modulus.bit_length(), 64) * 64;
+        let r = BigInt::one() << r_bits;
+        let r_mask = &r - 1u64;
+        let n_inv = BigInt::mod_inv(modulus, &r).expect("odd modulus is invertible modulo 2^k");
+        let n_prime = &r - n_inv;
+        let one = r.modulus(modulus);
+        let r2 = BigInt::mod_mul(&one, &one, modulus);
+        ModContext {
+            modulus: modulus.clone(),
+            r_bits,
+            r_mask,
+            n_prime,
+            r2,
+            one,
+        }
+    }
+
+    pub fn modulus(&self) -> &BigInt {
+        &self.modulus
+    }
+
+    /// Calculates `a * b (mod N)`
+    pub fn mul(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        // (abR^-1) * R^2 * R^-1 = ab
+        let ab = self.mont_mul(&self.reduce(a), &self.reduce(b));
+        self.mont_mul(&ab, &self.r2)
+    }
+
+    /// Calculates `a^-1 (mod N)`. Returns `None` if `a` and `N` are not coprime.
+    pub fn inv(&self, a: &BigInt) -> Option<BigInt> {
+        BigInt::mod_inv(a, &self.modulus)
+    }
+
+    /// Calculates `base^exponent (mod N)`
+    ///
+    /// ## Panics
+    /// Panics if exponent is negative
+    pub fn pow(&self, base: &BigInt, exponent: &BigInt) -> BigInt {
+        self.multi_pow(&[(base, exponent)])
+    }
+
+    /// Calculates `Π base_i^exponent_i (mod N)`
+    ///
+    /// Uses simultaneous (Straus) exponentiation: squarings are shared by all bases, so
+    /// computing product of `k` powers costs little more than a single exponentiation.
+    ///
+    /// ## Panics
+    /// Panics if any exponent is negative
+    pub fn multi_pow(&self, pairs: &[(&BigInt, &BigInt)]) -> BigInt {
+        assert!(
+            pairs.iter().all(|(_, e)| *e >= &BigInt::zero()),
+            "exponent must be non-negative"
+        );
+        let max_bits = pairs.iter().map(|(_, e)| e.bit_length()).max().unwrap_or(0);
+        if max_bits == 0 {
+            return BigInt::one().modulus(&self.modulus);
+        }
+        let window = window_size(max_bits);
+        let tables: Vec<Vec<BigInt>> = pairs
+            .iter()
+            .map(|(b, _)| self.powers_table(&self.to_montgomery(b), window))
+            .collect();
+
+        let windows = usize::div_ceil(max_bits, window);
+        let mut acc = self.one.clone();
+        for i in (0..windows).rev() {
+            for _ in 0..window {
+                acc = self.mont_mul(&acc, &acc);
+            }
+            for ((_, e), table) in pairs.iter().zip(&tables) {
+                let digit = window_digit(e, i * window, window);
+                if digit != 0 {
+                    acc = self.mont_mul(&acc, &table[digit]);
+                }
+            }
+        }
+        self.redc(&acc)
+    }
+
+    /// Precomputes table for computing powers of `base` with exponents of up to
+    /// `max_exponent_bits` bits
+    pub fn fixed_base_table(&self, base: &BigInt, max_exponent_bits: usize) -> FixedBaseTable {
+        FixedBaseTable::new(self, base, max_exponent_bits)
+    }
+
+    pub(super) fn to_montgomery(&self, a: &BigInt) -> BigInt {
+        self.mont_mul(&self.reduce(a), &self.r2)
+    }
+
+    /// Returns `a mod N`, dividing only if `a` is out of `[0, N)`
+    fn reduce<'a>(&self, a: &'a BigInt) -> Cow<'a, BigInt> {
+        if a >= &BigInt::zero() && a < &self.modulus {
+            Cow::Borrowed(a)
+        } else {
+            Cow::Owned(a.modulus(&self.modulus))
+        }
+    }
+
+    fn mont_mul(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        self.redc(&(a * b))
+    }
+
+    /// Montgomery reduction: computes `t * R^-1 (mod N)` for `0 <= t < N*R`
+    fn redc(&self, t: &BigInt) -> BigInt {
+        let m = ((t & &self.r_mask) * &self.n_prime) & &self.r_mask;
+        let u = (t + m * &self.modulus) >> self.r_bits;
+        if u >= self.modulus {
+            u - &self.modulus
+        } else {
+            u
+        }
+    }
+
+    #[cfg(feature = "num-bigint")]
+    /// 1 in Montgomery form
+    pub(super) fn montgomery_one(&self) -> &BigInt {
+        &self.one
+    }
+
+    #[cfg(feature = "num-bigint")]
+    /// Same as `mont_mul`, but with final subtraction done via [ConstantTime::ct_select]
+    pub(super) fn mont_mul_sec(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        self.redc_sec(&(a * b))
+    }
+
+    #[cfg(feature = "num-bigint")]
+    pub(super) fn redc_sec(&self, t: &BigInt) -> BigInt {
+        let m = ((t & &self.r_mask) * &self.n_prime) & &self.r_mask;
+        let u = (t + m * &self.modulus) >> self.r_bits;
+        // u < 2N, so d = u + R - N has bit `r_bits` set iff u >= N, and then d mod R = u - N
+        let d = &u + &self.r_mask + 1u64 - &self.modulus;
+        let u_ge_n = Choice::from(d.test_bit(self.r_bits) as u8);
+        BigInt::ct_select(&u, &(d & &self.r_mask), u_ge_n)
+    }
+
+    /// Returns `[1, b, b^2, ..., b^(2^window - 1)]` in Montgomery form
+    fn powers_table(&self, base: &BigInt, window: usize) -> Vec<BigInt> {
+        let mut table = Vec::with_capacity(1 << window);
+        table.push(self.one.clone());
+        for j in 1..1 << window {
+            let next = self.mont_mul(&table[j - 1], base);
+            table.push(next);
+        }
+        table
+    }
+}
+
+impl FixedBaseTable {
+    fn new(context: &ModContext, base: &BigInt, max_exponent_bits: usize) -> Self {
+        let window = window_size(max_exponent_bits).min(8);
+        let windows = usize::div_ceil(max_exponent_bits.max(1), window);
+        let mut table = Vec::with_capacity(windows);
+        // base^(2^(w*i)) in Montgomery form
+        let mut window_base = context.to_montgomery(base);
+        for _ in 0..windows {
+            let powers = context.powers_table(&window_base, window);
+            window_base = context.mont_mul(&powers[(1 << window) - 1], &window_base);
+            table.push(powers);
+        }
+        FixedBaseTable {
+            context: context.clone(),
+            base: base.clone(),
+            window,
+            max_exponent_bits,
+            table,
+        }
+    }
+
+    pub fn base(&self) -> &BigInt {
+        &self.base
+    }
+
+    /// Calculates `base^exponent (mod N)`
+    ///
+    /// Exponents longer than `max_exponent_bits` are computed with [ModContext::pow].
+    ///
+    /// ## Panics
+    /// Panics if exponent is negative
+    pub fn pow(&self, exponent: &BigInt) -> BigInt {
+        assert!(exponent >= &BigInt::zero(), "exponent must be non-negative");
+        if exponent.bit_length() > self.max_exponent_bits {
+            return self.context.pow(&self.base, exponent);
+        }
+        let ctx = &self.context;
+        let acc = self
+            .table
+            .iter()
+            .enumerate()
+            .fold(ctx.one.clone(), |acc, (i, powers)| {
+                let digit = window_digit(exponent, i * self.window, self.window);
+                if digit == 0 {
+                    acc
+                } else {
+                    ctx.mont_mul(&acc, &powers[digit])
+                }
+            });
+        ctx.redc(&acc)
+    }
+}
+
+fn window_size(exponent_bits: usize) -> usize {
+    match exponent_bits {
+        0..=32 => 1,
+        33..=128 => 3,
+        129..=512 => 4,
+        513..=2048 => 5,
+        _ => 6,
+    }
+}
+
+/// Returns bits `[start, start + window)` of `e` as a number
+fn window_digit(e: &BigInt, start: usize, window: usize) -> usize {
+    (0..window)
+        .filter(|k| e.test_bit(start + k))
+        .fold(0, |acc, k| acc | 1 << k)
+}
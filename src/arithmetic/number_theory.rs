@@ -0,0 +1,203 @@
+//! Jacobi symbol, modular square roots and related functions. Built on top of generic BigInt
+//! operations, so it behaves identically on every BigInt backend.
+
+use super::errors::NumberTheoryError;
+use super::traits::*;
+use super::BigInt;
+use super::Crt;
+
+pub fn jacobi(a: &BigInt, n: &BigInt) -> Result<i8, NumberTheoryError> {
+    if n <= &BigInt::zero() || !n.test_bit(0) {
+        return Err(NumberTheoryError::InvalidModulus);
+    }
+    let mut a = a.modulus(n);
+    let mut n = n.clone();
+    let mut result = 1;
+    while !a.is_zero() {
+        // (2/n) = -1 iff n = 3, 5 (mod 8)
+        let twos = trailing_zeros(&a);
+        a >>= twos;
+        let n_mod_8 = low_bits(&n, 3);
+        if twos % 2 == 1 && (n_mod_8 == 3 || n_mod_8 == 5) {
+            result = -result;
+        }
+        // quadratic reciprocity: (a/n) = -(n/a) iff a = n = 3 (mod 4)
+        if low_bits(&a, 2) == 3 && low_bits(&n, 2) == 3 {
+            result = -result;
+        }
+        std::mem::swap(&mut a, &mut n);
+        a = a.modulus(&n);
+    }
+    if n == BigInt::one() {
+        Ok(result)
+    } else {
+        Ok(0)
+    }
+}
+
+pub fn legendre(a: &BigInt, p: &BigInt) -> Result<i8, NumberTheoryError> {
+    if p < &BigInt::from(3) {
+        return Err(NumberTheoryError::InvalidModulus);
+    }
+    jacobi(a, p)
+}
+
+/// Tonelli-Shanks takes `O(s^2)` multiplications for `p - 1 = 2^s * q`, Cipolla takes `O(m)`
+/// for `m`-bit `p` regardless of `s`. Cipolla is used when `s(s-1) > 8m + 20`, as suggested in
+/// G. Nishihara, R. Harasawa, Y. Sueyoshi, A. Kudo. A remark on the computation of cube roots in
+/// finite fields.
+pub fn sqrt_mod_prime(a: &BigInt, p: &BigInt) -> Result<BigInt, NumberTheoryError> {
+    if legendre(a, p)? == -1 {
+        return Err(NumberTheoryError::NotQuadraticResidue);
+    }
+    let a = a.modulus(p);
+    if a.is_zero() {
+        return Ok(a);
+    }
+
+    let p_minus_one = p - 1u64;
+    let s = trailing_zeros(&p_minus_one);
+    let root = if s == 1 {
+        // p = 3 (mod 4)
+        BigInt::mod_pow(&a, &((p + 1u64) >> 2), p)
+    } else if s * (s - 1) > 8 * p.bit_length() + 20 {
+        cipolla(&a, p)?
+    } else {
+        tonelli_shanks(&a, p, s)?
+    };
+
+    // p is not checked to be prime, so the root might be wrong
+    if BigInt::mod_mul(&root, &root, p) != a {
+        return Err(NumberTheoryError::NotQuadraticResidue);
+    }
+    Ok(root)
+}
+
+fn tonelli_shanks(a: &BigInt, p: &BigInt, s: usize) -> Result<BigInt, NumberTheoryError> {
+    let q = (p - 1u64) >> s;
+    let z = non_residue(p, |z| z.clone())?;
+
+    let mut m = s;
+    let mut c = BigInt::mod_pow(&z, &q, p);
+    let mut t = BigInt::mod_pow(a, &q, p);
+    let mut r = BigInt::mod_pow(a, &((&q + 1u64) >> 1), p);
+    // invariant: r^2 = a * t, and t has order dividing 2^(m-1)
+    while t != BigInt::one() {
+        let mut i = 0;
+        let mut t_pow = t.clone();
+        while t_pow != BigInt::one() {
+            t_pow = BigInt::mod_mul(&t_pow, &t_pow, p);
+            i += 1;
+            if i == m {
+                return Err(NumberTheoryError::NotQuadraticResidue);
+            }
+        }
+        let b = BigInt::mod_pow(&c, &(BigInt::one() << (m - i - 1)), p);
+        m = i;
+        c = BigInt::mod_mul(&b, &b, p);
+        t = BigInt::mod_mul(&t, &c, p);
+        r = BigInt::mod_mul(&r, &b, p);
+    }
+    Ok(r)
+}
+
+fn cipolla(a: &BigInt, p: &BigInt) -> Result<BigInt, NumberTheoryError> {
+    // finds t such that w = t^2 - a is a non-residue, then computes (t + √w)^((p+1)/2) in F_p(√w)
+    let mut t = BigInt::zero();
+    let w = non_residue(p, |candidate| {
+        t = candidate.clone();
+        BigInt::mod_sub(&BigInt::mod_mul(candidate, candidate, p), a, p)
+    })?;
+
+    let mul = |(x1, y1): &(BigInt, BigInt), (x2, y2): &(BigInt, BigInt)| {
+        let x = BigInt::mod_add(
+            &BigInt::mod_mul(x1, x2, p),
+            &BigInt::mod_mul(&BigInt::mod_mul(y1, y2, p), &w, p),
+            p,
+        );
+        let y = BigInt::mod_add(&BigInt::mod_mul(x1, y2, p), &BigInt::mod_mul(y1, x2, p), p);
+        (x, y)
+    };
+    let exponent = (p + 1u64) >> 1;
+    let base = (t, BigInt::one());
+    let mut acc = (BigInt::one(), BigInt::zero());
+    for i in (0..exponent.bit_length()).rev() {
+        acc = mul(&acc, &acc);
+        if exponent.test_bit(i) {
+            acc = mul(&acc, &base);
+        }
+    }
+    Ok(acc.0)
+}
+
+/// Returns `f(z)` for the first `z = 2, 3, ...` such that `f(z)` is a quadratic non-residue
+/// modulo `p`
+///
+/// Jacobi symbol modulo a perfect square is never `-1`, so such `p` is rejected right away. For
+/// other `p` the search gives up after `2m^2 + 64` candidates for `m`-bit `p`, which means that
+/// `p` isn't prime: under GRH the least non-residue modulo prime `p` is below `2 ln^2 p`, and
+/// every candidate of Cipolla's algorithm is a non-residue with probability 1/2.
+fn non_residue(
+    p: &BigInt,
+    mut f: impl FnMut(&BigInt) -> BigInt,
+) -> Result<BigInt, NumberTheoryError> {
+    if is_perfect_square(p) {
+        return Err(NumberTheoryError::InvalidModulus);
+    }
+    let limit = (2 * p.bit_length() * p.bit_length() + 64) as u64;
+    for z in (2..limit + 2).map(BigInt::from) {
+        if &z >= p {
+            break;
+        }
+        let candidate = f(&z);
+        if jacobi(&candidate, p)? == -1 {
+            return Ok(candidate);
+        }
+    }
+    Err(NumberTheoryError::InvalidModulus)
+}
+
+/// Returns all distinct square roots of `a` modulo `pq`, in ascending order
+pub fn sqrt_mod_pq(a: &BigInt, p: &BigInt, q: &BigInt) -> Result<Vec<BigInt>, NumberTheoryError> {
+    let crt = Crt::new(&[p.clone(), q.clone()]).ok_or(NumberTheoryError::InvalidModulus)?;
+    let root_p = sqrt_mod_prime(a, p)?;
+    let root_q = sqrt_mod_prime(a, q)?;
+    let (neg_root_p, neg_root_q) = ((p - &root_p).modulus(p), (q - &root_q).modulus(q));
+
+    let mut roots: Vec<BigInt> = [
+        [&root_p, &root_q],
+        [&root_p, &neg_root_q],
+        [&neg_root_p, &root_q],
+        [&neg_root_p, &neg_root_q],
+    ]
+    .iter()
+    .map(|[r_p, r_q]| crt.recombine(&[(*r_p).clone(), (*r_q).clone()]))
+    .collect();
+    roots.sort();
+    roots.dedup();
+    Ok(roots)
+}
+
+pub fn is_perfect_square(n: &BigInt) -> bool {
+    if n < &BigInt::zero() {
+        return false;
+    }
+    let root = n.sqrt();
+    &(&root * &root) == n
+}
+
+pub fn try_mod_inv(a: &BigInt, m: &BigInt) -> Result<BigInt, NumberTheoryError> {
+    if m <= &BigInt::zero() {
+        return Err(NumberTheoryError::InvalidModulus);
+    }
+    BigInt::mod_inv(a, m).ok_or(NumberTheoryError::NotInvertible)
+}
+
+fn trailing_zeros(n: &BigInt) -> usize {
+    (0..n.bit_length()).find(|i| n.test_bit(*i)).unwrap_or(0)
+}
+
+/// Returns `n mod 2^bits` for small `bits`
+fn low_bits(n: &BigInt, bits: usize) -> u8 {
+    (0..bits).fold(0, |acc, i| acc | (n.test_bit(i) as u8) << i)
+}
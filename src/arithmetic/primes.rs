@@ -0,0 +1,171 @@
+//! Generation of primes with special structure. Built on top of [Samplable] and
+//! [Primes::is_probable_prime], so it behaves identically on every BigInt backend.
+
+use std::convert::TryFrom;
+
+use super::traits::*;
+use super::BigInt;
+
+/// Number of Miller-Rabin rounds used to accept a candidate
+const MILLER_RABIN_ROUNDS: u32 = 30;
+/// Candidates are sieved against odd primes below this bound
+const SIEVE_BOUND: u64 = 2048;
+/// Candidates of up to this many bits are tested directly, as they might be small primes themselves
+const SMALL_BIT_SIZE: usize = 12;
+/// How far an incremental search goes before sampling a new starting point
+const MAX_DELTA: u64 = 1 << 16;
+
+lazy_static::lazy_static! {
+    static ref SMALL_PRIMES: Vec<u64> = {
+        let mut is_composite = vec![false; SIEVE_BOUND as usize];
+        let mut primes = vec![];
+        for i in (3..SIEVE_BOUND as usize).step_by(2) {
+            if !is_composite[i] {
+                primes.push(i as u64);
+                for j in (i * i..SIEVE_BOUND as usize).step_by(2 * i) {
+                    is_composite[j] = true;
+                }
+            }
+        }
+        primes
+    };
+}
+
+pub fn sample_prime(bit_size: usize) -> BigInt {
+    assert!(bit_size >= 2, "there are no primes of {} bits", bit_size);
+    if bit_size == 2 {
+        return BigInt::from(2 + BigInt::sample(1).test_bit(0) as u32);
+    }
+    sample_sieved(bit_size, 1, 0b1, 2)
+}
+
+pub fn sample_blum_prime(bit_size: usize) -> BigInt {
+    assert!(
+        bit_size >= 2,
+        "there are no Blum primes of {} bits",
+        bit_size
+    );
+    sample_sieved(bit_size, 1, 0b11, 4)
+}
+
+/// Samples `q` of `bit_size - 1` bits until both `q` and `p = 2q + 1` are prime
+///
+/// Sieving rejects `q` if either `q` or `p` has a small factor. Then `p` is checked with a single
+/// Fermat test (base 2) before running Miller-Rabin on `q`: by Pocklington's criterion, if `q` is
+/// prime and `2^(p-1) = 1 (mod p)`, then `p` is prime as well.
+pub fn sample_safe_prime(bit_size: usize) -> BigInt {
+    assert!(
+        bit_size >= 3,
+        "there are no safe primes of {} bits",
+        bit_size
+    );
+    let two = BigInt::from(2);
+    loop {
+        let mut start = BigInt::sample(bit_size - 1);
+        start.set_bit(bit_size - 2, true);
+        start.set_bit(0, true);
+        let residues = small_residues(&start, bit_size);
+
+        let mut delta = 0;
+        while delta < MAX_DELTA {
+            let sieved = residues.iter().zip(SMALL_PRIMES.iter()).all(|(r, p)| {
+                let q_mod_p = (r + delta) % p;
+                q_mod_p != 0 && q_mod_p != (p - 1) / 2
+            });
+            if sieved {
+                let q = &start + delta;
+                let p = &q * 2u64 + 1u64;
+                if p.bit_length() != bit_size {
+                    break;
+                }
+                let p_minus_one = &p - 1u64;
+                if q.is_probable_prime(1)
+                    && BigInt::mod_pow(&two, &p_minus_one, &p) == BigInt::one()
+                    && q.is_probable_prime(MILLER_RABIN_ROUNDS)
+                {
+                    return p;
+                }
+            }
+            delta += 2;
+        }
+    }
+}
+
+/// Samples `(n, p, q)` such that `n = pq` is exactly `bit_size` bits long and `p != q` are primes
+/// of roughly `bit_size / 2` bits
+pub fn sample_rsa_modulus(bit_size: usize) -> (BigInt, BigInt, BigInt) {
+    assert!(bit_size >= 16, "RSA modulus must be at least 16 bits long");
+    let p_bits = bit_size - bit_size / 2;
+    let q_bits = bit_size / 2;
+    loop {
+        // two top bits set in both factors guarantee that the product has `bit_size` bits
+        let p = sample_sieved(p_bits, 2, 0b1, 2);
+        let q = sample_sieved(q_bits, 2, 0b1, 2);
+        if p == q {
+            continue;
+        }
+        let n = &p * &q;
+        debug_assert_eq!(n.bit_length(), bit_size);
+        return (n, p, q);
+    }
+}
+
+/// Same as [sample_rsa_modulus], additionally ensuring that `gcd(n, φ(n)) = 1`
+pub fn sample_paillier_modulus(bit_size: usize) -> (BigInt, BigInt, BigInt) {
+    loop {
+        let (n, p, q) = sample_rsa_modulus(bit_size);
+        let phi = (&p - 1u64) * (&q - 1u64);
+        if n.gcd(&phi) == BigInt::one() {
+            return (n, p, q);
+        }
+    }
+}
+
+/// Incremental search for a prime of exactly `bit_size` bits
+///
+/// Starting point is random with `top_bits` most significant bits set and with the two least
+/// significant bits set as in `low_bits`. It's incremented by `step` (which preserves the low
+/// bits), skipping candidates with small factors.
+fn sample_sieved(bit_size: usize, top_bits: usize, low_bits: u64, step: u64) -> BigInt {
+    loop {
+        let mut start = BigInt::sample(bit_size);
+        for bit in bit_size - top_bits..bit_size {
+            start.set_bit(bit, true);
+        }
+        for bit in 0..2 {
+            if low_bits >> bit & 1 == 1 {
+                start.set_bit(bit, true);
+            }
+        }
+        let residues = small_residues(&start, bit_size);
+
+        let mut delta = 0;
+        while delta < MAX_DELTA {
+            let sieved = residues
+                .iter()
+                .zip(SMALL_PRIMES.iter())
+                .all(|(r, p)| (r + delta) % p != 0);
+            if sieved {
+                let candidate = &start + delta;
+                if candidate.bit_length() != bit_size {
+                    break;
+                }
+                if candidate.is_probable_prime(MILLER_RABIN_ROUNDS) {
+                    return candidate;
+                }
+            }
+            delta += step;
+        }
+    }
+}
+
+// small numbers are not sieved as they might be equal to one of the small primes
+fn small_residues(n: &BigInt, bit_size: usize) -> Vec<u64> {
+    if bit_size <= SMALL_BIT_SIZE {
+        return vec![];
+    }
+    SMALL_PRIMES
+        .iter()
+        .map(|p| u64::try_from(&(n % *p)).expect("residue is smaller than p"))
+        .collect()
+}
@@ -0,0 +1,187 @@
+//! Serialization of [BigInt]
+//!
+//! BigInt is serialized the same way regardless of underlying implementation: as a string with
+//! lowercase hex digits, prefixed with `-` for negative numbers (eg. `"-1f"`). It's the format
+//! GMP-based BigInt always used, so data serialized by default build remains readable.
+//!
+//! Other formats can be chosen per field by adapters that follow `#[serde(with = "...")]`
+//! convention:
+//! * [Hex] — the same as default format
+//! * [Decimal] — string with decimal digits, prefixed with `-` for negative numbers
+//! * [Base64] — base64-encoded (standard alphabet, padded) big-endian bytes
+//! * [BytesBe] — big-endian bytes, left-padded with zeroes up to fixed length
+//!
+//! [Base64] and [BytesBe] don't encode a sign, so serializing a negative number fails.
+//!
+//! ## Example
+//! ```
+//! # use curv::BigInt;
+//! use curv::arithmetic::serde_support::{BytesBe, Decimal};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Message {
+//!     nonce: BigInt,
+//!     #[serde(with = "Decimal")]
+//!     amount: BigInt,
+//!     #[serde(with = "BytesBe::<4>")]
+//!     tag: BigInt,
+//! }
+//!
+//! let msg = Message {
+//!     nonce: BigInt::from(255),
+//!     amount: BigInt::from(1000),
+//!     tag: BigInt::from(258),
+//! };
+//! assert_eq!(
+//!     serde_json::to_string(&msg).unwrap(),
+//!     r#"{"nonce":"ff","amount":"1000","tag":[0,0,1,2]}"#
+//! );
+//! ```
+
+use std::fmt;
+
+use serde::de::{self, SeqAccess, Visitor};
+use serde::ser::{self, SerializeTuple};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::traits::*;
+use super::BigInt;
+
+impl Serialize for BigInt {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Hex::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BigInt {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Hex::deserialize(deserializer)
+    }
+}
+
+/// Serializes BigInt as a hex string, the same as default format
+pub struct Hex;
+
+impl Hex {
+    pub fn serialize<S: Serializer>(n: &BigInt, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&n.to_hex())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BigInt, D::Error> {
+        deserializer.deserialize_str(StrRadixVisitor(16))
+    }
+}
+
+/// Serializes BigInt as a decimal string
+pub struct Decimal;
+
+impl Decimal {
+    pub fn serialize<S: Serializer>(n: &BigInt, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&n.to_str_radix(10))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BigInt, D::Error> {
+        deserializer.deserialize_str(StrRadixVisitor(10))
+    }
+}
+
+/// Serializes non-negative BigInt as a base64 string of its big-endian bytes
+pub struct Base64;
+
+impl Base64 {
+    pub fn serialize<S: Serializer>(n: &BigInt, serializer: S) -> Result<S::Ok, S::Error> {
+        if n < &BigInt::zero() {
+            return Err(ser::Error::custom(
+                "base64 format can't encode negative number",
+            ));
+        }
+        serializer.serialize_str(&base64::encode(n.to_bytes()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BigInt, D::Error> {
+        deserializer.deserialize_str(Base64Visitor)
+    }
+}
+
+/// Serializes non-negative BigInt as exactly `N` big-endian bytes
+///
+/// Bytes are serialized as a tuple, so the format doesn't carry their length (eg. bincode
+/// writes exactly `N` bytes).
+pub struct BytesBe<const N: usize>;
+
+impl<const N: usize> BytesBe<N> {
+    pub fn serialize<S: Serializer>(n: &BigInt, serializer: S) -> Result<S::Ok, S::Error> {
+        if n < &BigInt::zero() {
+            return Err(ser::Error::custom(
+                "bytes format can't encode negative number",
+            ));
+        }
+        let bytes = n.to_bytes();
+        let bytes = bytes.strip_prefix(&[0u8][..]).unwrap_or(&bytes);
+        if bytes.len() > N {
+            return Err(ser::Error::custom(format_args!(
+                "number doesn't fit into {} bytes",
+                N
+            )));
+        }
+        let mut tuple = serializer.serialize_tuple(N)?;
+        for byte in std::iter::repeat_n(&0u8, N - bytes.len()).chain(bytes) {
+            tuple.serialize_element(byte)?;
+        }
+        tuple.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BigInt, D::Error> {
+        deserializer.deserialize_tuple(N, BytesBeVisitor::<N>)
+    }
+}
+
+struct StrRadixVisitor(u8);
+
+impl<'de> Visitor<'de> for StrRadixVisitor {
+    type Value = BigInt;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-based number", self.0)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<BigInt, E> {
+        BigInt::from_str_radix(v, self.0).map_err(E::custom)
+    }
+}
+
+struct Base64Visitor;
+
+impl<'de> Visitor<'de> for Base64Visitor {
+    type Value = BigInt;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("base64-encoded number")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<BigInt, E> {
+        let bytes = base64::decode(v).map_err(E::custom)?;
+        BigInt::try_from_bytes(&bytes).ok_or_else(|| E::invalid_length(bytes.len(), &self))
+    }
+}
+
+struct BytesBeVisitor<const N: usize>;
+
+impl<'de, const N: usize> Visitor<'de> for BytesBeVisitor<N> {
+    type Value = BigInt;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} big-endian bytes", N)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<BigInt, A::Error> {
+        let mut bytes = [0u8; N];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        BigInt::try_from_bytes(&bytes).ok_or_else(|| de::Error::invalid_length(N, &self))
+    }
+}
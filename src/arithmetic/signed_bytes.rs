@@ -0,0 +1,34 @@
+//! Two's complement bytes representation of BigInt. Built on top of generic BigInt operations,
+//! so it behaves identically on every BigInt backend.
+
+use super::traits::*;
+use super::BigInt;
+
+pub fn to_signed_bytes_be(n: &BigInt) -> Vec<u8> {
+    // Magnitude has no leading zeroes (besides zero itself, which is `[0]`), so its two's
+    // complement needs at most one extra byte for the sign
+    let mut bytes = n.to_bytes();
+    if n < &BigInt::zero() {
+        // -x = !x + 1 (mod 2^(8·len))
+        let mut carry = true;
+        for byte in bytes.iter_mut().rev() {
+            let (inverted, overflow) = (!*byte).overflowing_add(u8::from(carry));
+            *byte = inverted;
+            carry = carry && overflow;
+        }
+        if bytes[0] & 0x80 == 0 {
+            bytes.insert(0, 0xff);
+        }
+    } else if bytes[0] & 0x80 != 0 {
+        bytes.insert(0, 0);
+    }
+    bytes
+}
+
+pub fn from_signed_bytes_be(bytes: &[u8]) -> BigInt {
+    let n = BigInt::from_bytes(bytes);
+    match bytes.first() {
+        Some(byte) if byte & 0x80 != 0 => n - (BigInt::one() << (8 * bytes.len())),
+        _ => n,
+    }
+}
@@ -14,12 +14,14 @@
     @license GPL-3.0+ <https://github.com/KZen-networks/cryptography-utils/blob/master/LICENSE>
 */
 
-use super::errors::ParseBigIntError;
+use super::errors::{NumberTheoryError, ParseBigIntError};
 
 /// Reuse common traits from [num_integer] crate
 pub use num_integer::{Integer, Roots};
 /// Reuse common traits from [num_traits] crate
 pub use num_traits::{One, Zero};
+/// Reuse [Choice] from [subtle] crate as result of constant-time comparisons
+pub use subtle::Choice;
 
 #[deprecated(
     since = "0.6.0",
@@ -42,11 +44,25 @@ pub trait Converter: Sized {
     fn to_bytes(&self) -> Vec<u8>;
     /// Constructs BigInt from its byte representation
     ///
+    /// ## Panics
+    /// Panics if the number doesn't fit into BigInt, which is only possible with fixed-width
+    /// `crypto-bigint` backend. Use [try_from_bytes](Self::try_from_bytes) for untrusted input.
+    ///
     /// ```
     /// # use curv::arithmetic::{BigInt, Converter};
     /// assert_eq!(BigInt::from_bytes(&[15, 66, 64]), BigInt::from(1_000_000))
     /// ```
     fn from_bytes(bytes: &[u8]) -> Self;
+    /// Constructs BigInt from its byte representation. Returns `None` if the number doesn't fit
+    /// into BigInt (see [from_bytes](Self::from_bytes)).
+    ///
+    /// ```
+    /// # use curv::arithmetic::{BigInt, Converter};
+    /// assert_eq!(BigInt::try_from_bytes(&[15, 66, 64]), Some(BigInt::from(1_000_000)))
+    /// ```
+    fn try_from_bytes(bytes: &[u8]) -> Option<Self> {
+        Some(Self::from_bytes(bytes))
+    }
 
     /// Converts BigInt to hex representation.
     ///
@@ -101,6 +117,108 @@ pub trait Converter: Sized {
     /// assert_eq!(BigInt::from_str_radix("f4240", 16).unwrap(), BigInt::from(1_000_000));
     /// ```
     fn from_str_radix(s: &str, radix: u8) -> Result<Self, ParseBigIntError>;
+
+    /// Returns big-endian bytes representation of the number, left-padded with zeroes up to
+    /// `len` bytes
+    ///
+    /// Like [to_bytes](Self::to_bytes), ignores the sign of the number.
+    ///
+    /// ## Panics
+    /// Panics if the number doesn't fit into `len` bytes
+    ///
+    /// ## Examples
+    /// ```
+    /// # use curv::arithmetic::{BigInt, Converter};
+    /// assert_eq!(BigInt::from(1_000_000).to_bytes_be_padded(4), &[0, 15, 66, 64]);
+    /// assert_eq!(BigInt::from(0).to_bytes_be_padded(2), &[0, 0]);
+    /// ```
+    fn to_bytes_be_padded(&self, len: usize) -> Vec<u8> {
+        let bytes = self.to_bytes();
+        let leading_zeros = bytes.iter().take_while(|b| **b == 0).count();
+        let bytes = &bytes[leading_zeros..];
+        assert!(bytes.len() <= len, "number doesn't fit into {} bytes", len);
+        let mut padded = vec![0u8; len - bytes.len()];
+        padded.extend_from_slice(bytes);
+        padded
+    }
+
+    /// Returns little-endian bytes representation of the number. Ignores the sign of the number.
+    ///
+    /// ## Examples
+    /// ```
+    /// # use curv::arithmetic::{BigInt, Converter};
+    /// assert_eq!(BigInt::from(1_000_000).to_bytes_le(), &[64, 66, 15]);
+    /// ```
+    fn to_bytes_le(&self) -> Vec<u8> {
+        let mut bytes = self.to_bytes();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Constructs non-negative BigInt from its little-endian bytes representation
+    ///
+    /// ## Examples
+    /// ```
+    /// # use curv::arithmetic::{BigInt, Converter};
+    /// assert_eq!(BigInt::from_bytes_le(&[64, 66, 15]), BigInt::from(1_000_000));
+    /// ```
+    fn from_bytes_le(bytes: &[u8]) -> Self {
+        let mut bytes = bytes.to_vec();
+        bytes.reverse();
+        Self::from_bytes(&bytes)
+    }
+
+    /// Returns big-endian two's complement representation of the number, using the minimal
+    /// number of bytes
+    ///
+    /// ## Examples
+    /// ```
+    /// # use curv::arithmetic::{BigInt, Converter};
+    /// assert_eq!(BigInt::from(127).to_signed_bytes_be(), &[0x7f]);
+    /// assert_eq!(BigInt::from(128).to_signed_bytes_be(), &[0x00, 0x80]);
+    /// assert_eq!(BigInt::from(-128).to_signed_bytes_be(), &[0x80]);
+    /// assert_eq!(BigInt::from(-129).to_signed_bytes_be(), &[0xff, 0x7f]);
+    /// ```
+    fn to_signed_bytes_be(&self) -> Vec<u8>;
+
+    /// Constructs BigInt from its big-endian two's complement representation
+    ///
+    /// Empty slice is interpreted as zero.
+    ///
+    /// ## Examples
+    /// ```
+    /// # use curv::arithmetic::{BigInt, Converter};
+    /// assert_eq!(BigInt::from_signed_bytes_be(&[0x00, 0x80]), BigInt::from(128));
+    /// assert_eq!(BigInt::from_signed_bytes_be(&[0xff, 0x7f]), BigInt::from(-129));
+    /// ```
+    fn from_signed_bytes_be(bytes: &[u8]) -> Self;
+
+    /// Returns little-endian two's complement representation of the number, using the minimal
+    /// number of bytes
+    ///
+    /// ## Examples
+    /// ```
+    /// # use curv::arithmetic::{BigInt, Converter};
+    /// assert_eq!(BigInt::from(-129).to_signed_bytes_le(), &[0x7f, 0xff]);
+    /// ```
+    fn to_signed_bytes_le(&self) -> Vec<u8> {
+        let mut bytes = self.to_signed_bytes_be();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Constructs BigInt from its little-endian two's complement representation
+    ///
+    /// ## Examples
+    /// ```
+    /// # use curv::arithmetic::{BigInt, Converter};
+    /// assert_eq!(BigInt::from_signed_bytes_le(&[0x7f, 0xff]), BigInt::from(-129));
+    /// ```
+    fn from_signed_bytes_le(bytes: &[u8]) -> Self {
+        let mut bytes = bytes.to_vec();
+        bytes.reverse();
+        Self::from_signed_bytes_be(&bytes)
+    }
 }
 
 /// Provides basic arithmetic operators for BigInt
@@ -240,4 +358,102 @@ pub trait Primes {
     /// `is_probable_prime` probably return false. The probability of returning true for a randomly
     /// chosen non-prime is at most 4^(-reps).
     fn is_probable_prime(&self, n: u32) -> bool;
+    /// Samples random prime of exactly `bit_size` bits
+    ///
+    /// ## Example
+    /// ```
+    /// # use curv::arithmetic::*;
+    /// let p = BigInt::sample_prime(256);
+    /// assert_eq!(p.bit_length(), 256);
+    /// assert!(p.is_probable_prime(20));
+    /// ```
+    fn sample_prime(bit_size: usize) -> Self;
+    /// Samples random safe prime `p = 2q + 1` (ie. `q` is prime as well) of exactly `bit_size` bits
+    fn sample_safe_prime(bit_size: usize) -> Self;
+    /// Samples random Blum prime `p = 3 (mod 4)` of exactly `bit_size` bits
+    fn sample_blum_prime(bit_size: usize) -> Self;
+    /// Samples RSA modulus `n = pq` of exactly `bit_size` bits, returns `(n, p, q)`
+    ///
+    /// Primes `p != q` are `bit_size - bit_size/2` and `bit_size/2` bits long.
+    ///
+    /// ## Panics
+    /// Panics if `bit_size < 16`
+    fn sample_rsa_modulus(bit_size: usize) -> (Self, Self, Self)
+    where
+        Self: Sized;
+    /// Samples Paillier modulus `n = pq` of exactly `bit_size` bits such that `gcd(n, φ(n)) = 1`,
+    /// returns `(n, p, q)`
+    ///
+    /// ## Panics
+    /// Panics if `bit_size < 16`
+    fn sample_paillier_modulus(bit_size: usize) -> (Self, Self, Self)
+    where
+        Self: Sized;
+}
+
+/// Operations on secret values, running in time that doesn't depend on the values themselves
+///
+/// Running time may depend on the lengths of operands (and modulus), which are considered
+/// public. All operands must be non-negative.
+pub trait ConstantTime: Sized {
+    /// Calculates base^(exponent) (mod m) in time independent of the exponent value
+    ///
+    /// Use it instead of [Modulo::mod_pow] whenever exponent is secret, eg. a Paillier secret key
+    /// or a CRT component of an RSA private exponent.
+    ///
+    /// ## Panics
+    /// Panics if exponent is negative, or if modulus is not odd or is less than 3
+    ///
+    /// ## Example
+    /// ```
+    /// # use curv::arithmetic::*;
+    /// let (b, e, m) = (BigInt::from(3), BigInt::from(1000), BigInt::from(1_000_003));
+    /// assert_eq!(BigInt::mod_pow_sec(&b, &e, &m), BigInt::mod_pow(&b, &e, &m));
+    /// ```
+    fn mod_pow_sec(base: &Self, exponent: &Self, m: &Self) -> Self;
+    /// Returns `Choice(1)` if `self == other`, and `Choice(0)` otherwise
+    fn ct_eq(&self, other: &Self) -> Choice;
+    /// Returns `Choice(1)` if `self < other`, and `Choice(0)` otherwise
+    fn ct_lt(&self, other: &Self) -> Choice;
+    /// Returns `a` if `choice` is `Choice(0)`, and `b` if it's `Choice(1)`
+    fn ct_select(a: &Self, b: &Self, choice: Choice) -> Self;
+}
+
+/// Number-theoretic functions: quadratic residuosity, modular square roots and inverses
+pub trait NumberTheory: Sized {
+    /// Jacobi symbol `(a/n)`, which is either `1`, `-1` or `0`
+    ///
+    /// Returns [InvalidModulus](NumberTheoryError::InvalidModulus) if `n` is not odd and positive.
+    fn jacobi(a: &Self, n: &Self) -> Result<i8, NumberTheoryError>;
+    /// Legendre symbol `(a/p)` for odd prime `p`: `1` if `a` is a non-zero quadratic residue
+    /// modulo `p`, `-1` if it's a non-residue, and `0` if `p` divides `a`
+    ///
+    /// Primality of `p` is not checked: for composite `p` the result is the Jacobi symbol.
+    fn legendre(a: &Self, p: &Self) -> Result<i8, NumberTheoryError>;
+    /// Finds `r` such that `r^2 = a (mod p)` for odd prime `p`, using Tonelli-Shanks or Cipolla
+    /// algorithm, whichever is faster for the given `p`
+    ///
+    /// Other root is `p - r`. Returns [NotQuadraticResidue](NumberTheoryError::NotQuadraticResidue)
+    /// if there's no such `r`. Returned root is always verified, so a composite `p` can only
+    /// result in an error.
+    ///
+    /// ## Example
+    /// ```
+    /// # use curv::arithmetic::*;
+    /// let p = BigInt::from(1_000_000_007);
+    /// let r = BigInt::sqrt_mod_prime(&BigInt::from(7), &p).unwrap();
+    /// assert_eq!(BigInt::mod_mul(&r, &r, &p), BigInt::from(7));
+    /// assert!(BigInt::sqrt_mod_prime(&BigInt::from(5), &p).is_err());
+    /// ```
+    fn sqrt_mod_prime(a: &Self, p: &Self) -> Result<Self, NumberTheoryError>;
+    /// Finds all square roots of `a` modulo `pq` for distinct odd primes `p`, `q`, returned in
+    /// ascending order
+    ///
+    /// There are 4 roots if `a` is coprime with `pq`, and fewer otherwise.
+    fn sqrt_mod_pq(a: &Self, p: &Self, q: &Self) -> Result<Vec<Self>, NumberTheoryError>;
+    /// Checks whether number is a square of an integer
+    fn is_perfect_square(&self) -> bool;
+    /// Calculates `a^-1 (mod m)`, same as [Modulo::mod_inv], but returns an error instead of
+    /// `None`
+    fn try_mod_inv(a: &Self, m: &Self) -> Result<Self, NumberTheoryError>;
 }
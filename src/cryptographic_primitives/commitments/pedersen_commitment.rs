@@ -32,9 +32,8 @@ where
         let h = P::base_point2();
         let message_scalar: P::Scalar = ECScalar::from(message);
         let blinding_scalar: P::Scalar = ECScalar::from(blinding_factor);
-        let mg = g * message_scalar;
-        let rh = h * blinding_scalar;
-        mg + rh
+        // zero message is allowed (multi_scalar_mul skips zero terms)
+        P::multi_scalar_mul(&[message_scalar, blinding_scalar], &[g, h])
     }
 
     fn create_commitment(message: &BigInt) -> (P, BigInt) {
@@ -0,0 +1,247 @@
+#![allow(non_snake_case)]
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+//! ElGamal encryption over elliptic curves: T. ElGamal. A public key cryptosystem and a signature
+//! scheme based on discrete logarithms. IEEE Transactions on Information Theory, 31(4), 1985.
+//!
+//! Ciphertext is a pair `{D, E}` with `D = M + rY`, `E = rG`, where `Y` is the public key and `M`
+//! is the message point, the same form as in [HomoELGamalProof]. Scalars are encrypted "in the
+//! exponent", ie. `M = mG`, which makes the scheme additively homomorphic, but requires solving
+//! a discrete log on decryption, so only small scalars (like vote tallies) can be decrypted.
+//!
+//! [HomoELGamalProof]: crate::cryptographic_primitives::proofs::sigma_correct_homomorphic_elgamal_enc::HomoELGamalProof
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use crate::arithmetic::traits::*;
+use crate::elliptic::curves::traits::*;
+use crate::BigInt;
+
+pub mod threshold;
+
+/// Key pair of the decrypting party. Secret key is left out of `Debug` output and zeroized on
+/// drop.
+#[derive(Serialize, Deserialize, Derivative)]
+#[derivative(Clone(bound = "P: Clone, P::Scalar: Clone"))]
+#[derivative(Debug(bound = "P: Debug"))]
+#[serde(bound(serialize = "P: Serialize, P::Scalar: Serialize"))]
+#[serde(bound(deserialize = "P: Deserialize<'de>, P::Scalar: Deserialize<'de>"))]
+pub struct ElGamalKeyPair<P: ECPoint>
+where
+    P::Scalar: Zeroize,
+{
+    pub pk: P,
+    #[derivative(Debug = "ignore")]
+    sk: P::Scalar,
+}
+
+impl<P: ECPoint> Drop for ElGamalKeyPair<P>
+where
+    P::Scalar: Zeroize,
+{
+    fn drop(&mut self) {
+        self.sk.zeroize();
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ElGamalCiphertext<P> {
+    pub D: P,
+    pub E: P,
+}
+
+impl<P> ElGamalKeyPair<P>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Zeroize + Clone,
+{
+    pub fn generate() -> Self {
+        Self::from_secret_key(ECScalar::new_random())
+    }
+
+    pub fn from_secret_key(sk: P::Scalar) -> Self {
+        ElGamalKeyPair {
+            pk: P::mul_generator(&sk),
+            sk,
+        }
+    }
+
+    pub fn decrypt_point(&self, ciphertext: &ElGamalCiphertext<P>) -> P {
+        ciphertext.unmask_point(&(ciphertext.E.clone() * self.sk.clone()))
+    }
+
+    // finds m < bound such that the ciphertext encrypts m in the exponent
+    pub fn decrypt_scalar(&self, ciphertext: &ElGamalCiphertext<P>, bound: u64) -> Option<u64> {
+        ciphertext.unmask_scalar(&(ciphertext.E.clone() * self.sk.clone()), bound)
+    }
+}
+
+impl<P> ElGamalCiphertext<P>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Clone,
+{
+    pub fn encrypt_point(pk: &P, m: &P) -> Self {
+        Self::encrypt_point_with_randomness(pk, m, &ECScalar::new_random())
+    }
+
+    pub fn encrypt_point_with_randomness(pk: &P, m: &P, r: &P::Scalar) -> Self {
+        ElGamalCiphertext {
+            D: m.clone() + pk.clone() * r.clone(),
+            E: P::mul_generator(r),
+        }
+    }
+
+    pub fn encrypt_scalar(pk: &P, m: &P::Scalar) -> Self {
+        Self::encrypt_scalar_with_randomness(pk, m, &ECScalar::new_random())
+    }
+
+    pub fn encrypt_scalar_with_randomness(pk: &P, m: &P::Scalar, r: &P::Scalar) -> Self {
+        let rY = pk.clone() * r.clone();
+        // mG is the point at infinity for m = 0, which some curves can't represent
+        let D = if m.to_big_int().is_zero() {
+            rY
+        } else {
+            P::mul_generator(m) + rY
+        };
+        ElGamalCiphertext {
+            D,
+            E: P::mul_generator(r),
+        }
+    }
+
+    // encryption of the sum of plaintexts
+    pub fn add(&self, other: &Self) -> Self {
+        ElGamalCiphertext {
+            D: self.D.clone() + other.D.clone(),
+            E: self.E.clone() + other.E.clone(),
+        }
+    }
+
+    // fresh encryption of the same plaintext, unlinkable to the original ciphertext
+    pub fn rerandomize(&self, pk: &P) -> Self {
+        self.rerandomize_with_randomness(pk, &ECScalar::new_random())
+    }
+
+    pub fn rerandomize_with_randomness(&self, pk: &P, r: &P::Scalar) -> Self {
+        ElGamalCiphertext {
+            D: self.D.clone() + pk.clone() * r.clone(),
+            E: self.E.clone() + P::mul_generator(r),
+        }
+    }
+
+    // mask is sE = rY
+    pub(crate) fn unmask_point(&self, mask: &P) -> P {
+        self.D.sub_point(&mask.get_element())
+    }
+
+    pub(crate) fn unmask_scalar(&self, mask: &P, bound: u64) -> Option<u64> {
+        if bound == 0 {
+            return None;
+        }
+        if self.D == *mask {
+            return Some(0);
+        }
+        discrete_log(&self.unmask_point(mask), bound)
+    }
+}
+
+/// Baby-step giant-step search for `m` in `[1, bound)` such that `M = mG`
+fn discrete_log<P>(M: &P, bound: u64) -> Option<u64>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Clone,
+{
+    let G: P = ECPoint::generator();
+    let step = (bound as f64).sqrt().ceil() as u64;
+
+    // baby steps: jG for j in 1..=step
+    let mut table = HashMap::new();
+    let mut jG = G.clone();
+    for j in 1..=step {
+        table.insert(jG.pk_to_key_slice(), j);
+        jG = jG + G.clone();
+    }
+
+    // giant steps: M - i*step*G, which never reaches the point at infinity as jG covers j = step
+    let giant_step: P = G * <P::Scalar as ECScalar>::from(&BigInt::from(step));
+    let mut current = M.clone();
+    for i in 0..step {
+        if let Some(j) = table.get(&current.pk_to_key_slice()) {
+            let m = i * step + j;
+            return if m < bound { Some(m) } else { None };
+        }
+        current = current.sub_point(&giant_step.get_element());
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_for_all_curves;
+
+    test_for_all_curves!(encrypts_and_decrypts_points);
+    fn encrypts_and_decrypts_points<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Zeroize + Clone,
+    {
+        let keys = ElGamalKeyPair::<P>::generate();
+        let m = P::generator() * P::Scalar::new_random();
+        let ciphertext = ElGamalCiphertext::encrypt_point(&keys.pk, &m);
+        assert!(keys.decrypt_point(&ciphertext) == m);
+
+        let rerandomized = ciphertext.rerandomize(&keys.pk);
+        assert!(rerandomized != ciphertext);
+        assert!(keys.decrypt_point(&rerandomized) == m);
+    }
+
+    test_for_all_curves!(scalar_encryption_is_additively_homomorphic);
+    fn scalar_encryption_is_additively_homomorphic<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Zeroize + Clone,
+    {
+        let keys = ElGamalKeyPair::<P>::generate();
+        let encrypt = |m: u32| {
+            let m = <P::Scalar as ECScalar>::from(&BigInt::from(m));
+            ElGamalCiphertext::encrypt_scalar(&keys.pk, &m)
+        };
+        let votes = [1, 0, 1, 1, 0, 1];
+        let tally = votes
+            .iter()
+            .map(|v| encrypt(*v))
+            .fold(encrypt(0), |acc, c| acc.add(&c));
+        assert_eq!(keys.decrypt_scalar(&tally, 10), Some(4));
+        assert_eq!(
+            keys.decrypt_scalar(&tally.rerandomize(&keys.pk), 10),
+            Some(4)
+        );
+        assert_eq!(keys.decrypt_scalar(&tally, 4), None);
+        assert_eq!(keys.decrypt_scalar(&encrypt(0), 10), Some(0));
+        assert_eq!(keys.decrypt_scalar(&encrypt(1000), 1001), Some(1000));
+    }
+
+    #[test]
+    fn debug_output_does_not_contain_secret_key() {
+        use crate::elliptic::curves::secp256_k1::{FE, GE};
+
+        let sk: FE = ECScalar::from(&BigInt::from(0xdead_beef_u32));
+        let keys = ElGamalKeyPair::<GE>::from_secret_key(sk);
+        let debug = format!("{:?}", keys);
+        assert!(debug.contains("pk"));
+        assert!(!debug.contains("sk"));
+        assert!(!debug.to_lowercase().contains("deadbeef"));
+    }
+}
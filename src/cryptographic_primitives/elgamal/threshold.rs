@@ -0,0 +1,201 @@
+#![allow(non_snake_case)]
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+//! Threshold ElGamal decryption: Y. Desmedt, Y. Frankel. Threshold cryptosystems.
+//! In Advances in Cryptology, CRYPTO 1989.
+//!
+//! Secret key `x` is shared with [VerifiableSS], so public key is the first Feldman commitment
+//! `Y = xG`, and party `i` (indexed from 0) holds share `x_i` with public verification key
+//! `X_i = x_i*G` obtained from the commitments. To decrypt ciphertext `{D, E}`, each party publishes
+//! decryption share `D_i = x_i*E` along with [ECDDHProof] that `log_G(X_i) = log_E(D_i)`. Any `t+1`
+//! verified shares are combined with Lagrange coefficients into `xE = rY`, which unmasks `D`.
+
+use std::fmt::Debug;
+
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use super::ElGamalCiphertext;
+use crate::cryptographic_primitives::proofs::sigma_ec_ddh::*;
+use crate::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::cryptographic_primitives::secret_sharing::polynomial::Polynomial;
+use crate::elliptic::curves::traits::*;
+use crate::BigInt;
+use crate::ErrorSS::{self, VerifyShareError};
+
+#[derive(Serialize, Deserialize, Derivative)]
+#[derivative(Clone(bound = "P: Clone, P::Scalar: Clone"))]
+#[derivative(Debug(bound = "P: Debug, P::Scalar: Debug"))]
+#[derivative(PartialEq(bound = "P: PartialEq, P::Scalar: PartialEq"))]
+#[serde(bound(serialize = "P: Serialize, P::Scalar: Serialize"))]
+#[serde(bound(deserialize = "P: Deserialize<'de>, P::Scalar: Deserialize<'de>"))]
+pub struct DecryptionShare<P: ECPoint> {
+    pub index: usize,
+    pub D_i: P,
+    pub proof: ECDDHProof<P>,
+}
+
+// trusted dealer key generation: returns public key, commitments to key shares and the shares
+pub fn keygen<P>(t: usize, n: usize) -> (P, VerifiableSS<P>, Vec<P::Scalar>)
+where
+    P: ECPoint + Clone,
+    P::Scalar: Zeroize + Clone,
+{
+    let mut x: P::Scalar = ECScalar::new_random();
+    let (vss, shares) = VerifiableSS::<P>::share(t, n, &x);
+    x.zeroize();
+    (vss.commitments[0].clone(), vss, shares)
+}
+
+impl<P> DecryptionShare<P>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Zeroize + Clone,
+{
+    pub fn new(ciphertext: &ElGamalCiphertext<P>, index: usize, x_i: &P::Scalar) -> Self {
+        let D_i = ciphertext.E.clone() * x_i.clone();
+        let statement = ECDDHStatement {
+            g1: ECPoint::generator(),
+            h1: P::mul_generator(x_i),
+            g2: ciphertext.E.clone(),
+            h2: D_i.clone(),
+        };
+        let witness = ECDDHWitness { x: x_i.clone() };
+        DecryptionShare {
+            index,
+            D_i,
+            proof: ECDDHProof::prove(&witness, &statement),
+        }
+    }
+
+    pub fn verify(
+        &self,
+        ciphertext: &ElGamalCiphertext<P>,
+        vss: &VerifiableSS<P>,
+    ) -> Result<(), ErrorSS> {
+        if self.index >= vss.parameters.share_count {
+            return Err(VerifyShareError);
+        }
+        let statement = ECDDHStatement {
+            g1: ECPoint::generator(),
+            h1: vss.get_point_commitment(self.index + 1),
+            g2: ciphertext.E.clone(),
+            h2: self.D_i.clone(),
+        };
+        self.proof.verify(&statement).map_err(|_| VerifyShareError)
+    }
+
+    // combines t+1 verified shares (invalid ones are skipped) into the mask xE = rY
+    fn combine(
+        ciphertext: &ElGamalCiphertext<P>,
+        vss: &VerifiableSS<P>,
+        shares: &[DecryptionShare<P>],
+    ) -> Result<P, ErrorSS> {
+        let mut valid: Vec<&DecryptionShare<P>> = vec![];
+        for share in shares {
+            if valid.len() == vss.reconstruct_limit() {
+                break;
+            }
+            if valid.iter().all(|s| s.index != share.index) && share.verify(ciphertext, vss).is_ok()
+            {
+                valid.push(share);
+            }
+        }
+        if valid.len() < vss.reconstruct_limit() {
+            return Err(VerifyShareError);
+        }
+
+        let points: Vec<P::Scalar> = valid
+            .iter()
+            .map(|s| ECScalar::from(&BigInt::from(s.index as u32 + 1)))
+            .collect();
+        let mut terms = valid.iter().enumerate().map(|(i, s)| {
+            let lambda = Polynomial::lagrange_basis(&P::Scalar::zero(), i, &points);
+            s.D_i.clone() * lambda
+        });
+        let head = terms.next().unwrap();
+        Ok(terms.fold(head, |acc, term| acc + term))
+    }
+
+    pub fn combine_point(
+        ciphertext: &ElGamalCiphertext<P>,
+        vss: &VerifiableSS<P>,
+        shares: &[DecryptionShare<P>],
+    ) -> Result<P, ErrorSS> {
+        let mask = Self::combine(ciphertext, vss, shares)?;
+        Ok(ciphertext.unmask_point(&mask))
+    }
+
+    // finds m < bound such that the ciphertext encrypts m in the exponent
+    pub fn combine_scalar(
+        ciphertext: &ElGamalCiphertext<P>,
+        vss: &VerifiableSS<P>,
+        shares: &[DecryptionShare<P>],
+        bound: u64,
+    ) -> Result<Option<u64>, ErrorSS> {
+        let mask = Self::combine(ciphertext, vss, shares)?;
+        Ok(ciphertext.unmask_scalar(&mask, bound))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_for_all_curves;
+
+    test_for_all_curves!(threshold_decryption_2_out_of_4);
+    fn threshold_decryption_2_out_of_4<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Zeroize + Clone,
+    {
+        let (pk, vss, x) = keygen::<P>(1, 4);
+        let m = P::generator() * P::Scalar::new_random();
+        let ciphertext = ElGamalCiphertext::encrypt_point(&pk, &m);
+
+        let shares: Vec<_> = [3, 1]
+            .iter()
+            .map(|i| DecryptionShare::new(&ciphertext, *i, &x[*i]))
+            .collect();
+        assert!(shares.iter().all(|s| s.verify(&ciphertext, &vss).is_ok()));
+        let decrypted = DecryptionShare::combine_point(&ciphertext, &vss, &shares).unwrap();
+        assert!(decrypted == m);
+    }
+
+    test_for_all_curves!(threshold_decryption_of_tally_skips_invalid_shares);
+    fn threshold_decryption_of_tally_skips_invalid_shares<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Zeroize + Clone,
+    {
+        let (pk, vss, x) = keygen::<P>(2, 5);
+        let one: P::Scalar = ECScalar::from(&BigInt::from(1));
+        let tally = (0..3)
+            .map(|_| ElGamalCiphertext::encrypt_scalar(&pk, &one))
+            .fold(ElGamalCiphertext::encrypt_scalar(&pk, &one), |acc, c| {
+                acc.add(&c)
+            })
+            .rerandomize(&pk);
+
+        let mut shares: Vec<_> = (0..5)
+            .map(|i| DecryptionShare::new(&tally, i, &x[i]))
+            .collect();
+        // share of party 0 decrypted with a wrong key, and share of party 1 claimed by party 2
+        shares[0] = DecryptionShare::new(&tally, 0, &x[1]);
+        shares[2].index = 1;
+        assert!(shares[0].verify(&tally, &vss).is_err());
+        assert!(shares[2].verify(&tally, &vss).is_err());
+
+        assert_eq!(
+            DecryptionShare::combine_scalar(&tally, &vss, &shares, 10).unwrap(),
+            Some(4)
+        );
+        assert!(DecryptionShare::combine_scalar(&tally, &vss, &shares[..4], 10).is_err());
+    }
+}
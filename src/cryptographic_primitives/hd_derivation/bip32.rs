@@ -0,0 +1,695 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+//! BIP-32 key derivation for secp256k1 and P-256
+//!
+//! Derivation follows [SLIP-10], which is identical to [BIP-32] whenever BIP-32 produces a valid
+//! key, and also defines what to do in the (negligibly rare for secp256k1) cases when it doesn't.
+//! Extended keys of both curves are serialized with the mainnet `xpub`/`xprv` version bytes.
+//!
+//! Public derivation works on any public key and chain code, so it can be applied to keys
+//! shared between several parties: every child is the parent key shifted by a public tweak,
+//! `child = parent + tweak * G`, and parties adjust their secret shares accordingly.
+//!
+//! [BIP-32]: https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+//! [SLIP-10]: https://github.com/satoshilabs/slips/blob/master/slip-0010.md
+
+use derivative::Derivative;
+use zeroize::Zeroize;
+
+use super::{check_seed_length, fingerprint, hmac_sha512, is_hardened, ChainCode, HdError};
+use crate::arithmetic::traits::*;
+use crate::elliptic::curves::traits::{ECPoint, ECScalar};
+use crate::elliptic::curves::{p256, secp256_k1};
+use crate::BigInt;
+use crate::ErrorKey;
+
+/// Version bytes of serialized extended public key (`xpub`)
+pub const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xB2, 0x1E];
+/// Version bytes of serialized extended private key (`xprv`)
+pub const XPRV_VERSION: [u8; 4] = [0x04, 0x88, 0xAD, 0xE4];
+
+const EXTENDED_KEY_SIZE: usize = 78;
+
+/// Curves supporting BIP-32 derivation
+pub trait Bip32Curve: ECPoint + Clone {
+    /// HMAC key used to derive master key from a seed
+    const SEED_KEY: &'static [u8];
+
+    /// Parses SEC1 compressed encoding of a point
+    fn from_compressed(bytes: &[u8; 33]) -> Result<Self, ErrorKey>;
+}
+
+impl Bip32Curve for secp256_k1::GE {
+    const SEED_KEY: &'static [u8] = b"Bitcoin seed";
+
+    fn from_compressed(bytes: &[u8; 33]) -> Result<Self, ErrorKey> {
+        let public_key =
+            secp256k1::PublicKey::from_slice(bytes).map_err(|_| ErrorKey::InvalidPublicKey)?;
+        ECPoint::from_bytes(&public_key.serialize_uncompressed()[1..])
+    }
+}
+
+impl Bip32Curve for p256::GE {
+    const SEED_KEY: &'static [u8] = b"Nist256p1 seed";
+
+    fn from_compressed(bytes: &[u8; 33]) -> Result<Self, ErrorKey> {
+        if bytes[0] != 2 && bytes[0] != 3 {
+            return Err(ErrorKey::InvalidPublicKey);
+        }
+        ECPoint::from_bytes(bytes)
+    }
+}
+
+/// SEC1 compressed encoding of a point
+pub fn compress<P: ECPoint>(point: &P) -> [u8; 33] {
+    let y = point.y_coor().unwrap();
+    let mut bytes = [0u8; 33];
+    bytes[0] = if BigInt::modulus(&y, &BigInt::from(2)) == BigInt::zero() {
+        2
+    } else {
+        3
+    };
+    bytes[1..].copy_from_slice(&point.x_coor().unwrap().to_bytes_be_padded(32));
+    bytes
+}
+
+/// Extended private key. Secret key is left out of `Debug` output, and both secret key and chain
+/// code are zeroized on drop.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "P::Scalar: Clone"))]
+#[derivative(Debug(bound = ""))]
+#[derivative(PartialEq(bound = "P::Scalar: PartialEq"))]
+pub struct ExtendedPrivateKey<P: ECPoint>
+where
+    P::Scalar: Zeroize,
+{
+    pub depth: u8,
+    pub parent_fingerprint: [u8; 4],
+    pub child_number: u32,
+    pub chain_code: ChainCode,
+    #[derivative(Debug = "ignore")]
+    pub secret_key: P::Scalar,
+}
+
+impl<P: ECPoint> Drop for ExtendedPrivateKey<P>
+where
+    P::Scalar: Zeroize,
+{
+    fn drop(&mut self) {
+        self.secret_key.zeroize();
+        self.chain_code.zeroize();
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtendedPublicKey<P: ECPoint> {
+    pub depth: u8,
+    pub parent_fingerprint: [u8; 4],
+    pub child_number: u32,
+    pub chain_code: ChainCode,
+    pub public_key: P,
+}
+
+impl<P> ExtendedPrivateKey<P>
+where
+    P: Bip32Curve,
+    P::Scalar: Zeroize + Clone,
+{
+    /// Derives master key from a seed of 16 to 64 bytes
+    pub fn new_master(seed: &[u8]) -> Result<Self, HdError> {
+        check_seed_length(seed)?;
+        let (mut il, mut ir) = hmac_sha512(P::SEED_KEY, &[seed]);
+        loop {
+            if let Some(secret_key) = scalar_from_bytes::<P>(&il) {
+                if !is_zero::<P>(&secret_key) {
+                    return Ok(ExtendedPrivateKey {
+                        depth: 0,
+                        parent_fingerprint: [0u8; 4],
+                        child_number: 0,
+                        chain_code: ir,
+                        secret_key,
+                    });
+                }
+            }
+            let (l, r) = hmac_sha512(P::SEED_KEY, &[&il, &ir]);
+            il = l;
+            ir = r;
+        }
+    }
+
+    pub fn public_key(&self) -> ExtendedPublicKey<P> {
+        ExtendedPublicKey {
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+            chain_code: self.chain_code,
+            public_key: P::generator() * self.secret_key.clone(),
+        }
+    }
+
+    pub fn fingerprint(&self) -> [u8; 4] {
+        self.public_key().fingerprint()
+    }
+
+    /// Derives child key, indexes starting from [HARDENED_KEY_OFFSET](super::HARDENED_KEY_OFFSET)
+    /// give hardened children
+    pub fn derive_child(&self, index: u32) -> Result<Self, HdError> {
+        let depth = self.depth.checked_add(1).ok_or(HdError::MaxDepthExceeded)?;
+        let public_key = self.public_key();
+        let data = if is_hardened(index) {
+            let mut data = vec![0u8];
+            data.extend(self.secret_key.to_big_int().to_bytes_be_padded(32));
+            data
+        } else {
+            compress(&public_key.public_key).to_vec()
+        };
+        let parent_key = self.secret_key.to_big_int();
+        let (tweak, chain_code) = derive_tweak::<P, _>(&self.chain_code, &data, index, |tweak| {
+            BigInt::mod_add(&tweak.to_big_int(), &parent_key, &P::Scalar::q()) != BigInt::zero()
+        });
+        Ok(ExtendedPrivateKey {
+            depth,
+            parent_fingerprint: public_key.fingerprint(),
+            child_number: index,
+            chain_code,
+            secret_key: tweak + self.secret_key.clone(),
+        })
+    }
+
+    /// Derives a key following the path, see [parse_path](super::parse_path)
+    pub fn derive_path(&self, path: &[u32]) -> Result<Self, HdError> {
+        path.iter()
+            .try_fold(self.clone(), |key, index| key.derive_child(*index))
+    }
+
+    /// Serializes the key as base58check-encoded `xprv` string
+    pub fn to_base58(&self) -> String {
+        let mut key = [0u8; 33];
+        key[1..].copy_from_slice(&self.secret_key.to_big_int().to_bytes_be_padded(32));
+        encode_extended_key(
+            XPRV_VERSION,
+            self.depth,
+            self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &key,
+        )
+    }
+
+    pub fn from_base58(s: &str) -> Result<Self, HdError> {
+        let (depth, parent_fingerprint, child_number, chain_code, key) =
+            decode_extended_key(s, XPRV_VERSION)?;
+        if key[0] != 0 {
+            return Err(HdError::InvalidExtendedKey);
+        }
+        let mut secret_key = [0u8; 32];
+        secret_key.copy_from_slice(&key[1..]);
+        let secret_key = scalar_from_bytes::<P>(&secret_key)
+            .filter(|k| !is_zero::<P>(k))
+            .ok_or(HdError::InvalidExtendedKey)?;
+        Ok(ExtendedPrivateKey {
+            depth,
+            parent_fingerprint,
+            child_number,
+            chain_code,
+            secret_key,
+        })
+    }
+}
+
+impl<P> ExtendedPublicKey<P>
+where
+    P: Bip32Curve,
+    P::Scalar: Clone,
+{
+    /// Root of public derivation for `public_key`, e.g. a key shared by several parties, and
+    /// a chain code they agreed on
+    pub fn new(public_key: P, chain_code: ChainCode) -> Self {
+        ExtendedPublicKey {
+            depth: 0,
+            parent_fingerprint: [0u8; 4],
+            child_number: 0,
+            chain_code,
+            public_key,
+        }
+    }
+
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint(&compress(&self.public_key))
+    }
+
+    /// Derives non-hardened child key `child = parent + tweak * G`, returns it with the tweak
+    ///
+    /// If the parent secret is split into additive shares, one of the parties adds `tweak` to
+    /// its share. If it's shared with a polynomial (Shamir, Feldman VSS), every party adds
+    /// `tweak` to its share and the commitment to the free coefficient is shifted by
+    /// `tweak * G`.
+    pub fn derive_child_with_tweak(&self, index: u32) -> Result<(Self, P::Scalar), HdError> {
+        if is_hardened(index) {
+            return Err(HdError::HardenedPublicDerivation);
+        }
+        let depth = self.depth.checked_add(1).ok_or(HdError::MaxDepthExceeded)?;
+        let (tweak, chain_code) = derive_tweak::<P, _>(
+            &self.chain_code,
+            &compress(&self.public_key),
+            index,
+            |tweak| !is_negation(&(P::generator() * tweak.clone()), &self.public_key),
+        );
+        let child = ExtendedPublicKey {
+            depth,
+            parent_fingerprint: self.fingerprint(),
+            child_number: index,
+            chain_code,
+            public_key: P::generator() * tweak.clone() + self.public_key.clone(),
+        };
+        Ok((child, tweak))
+    }
+
+    pub fn derive_child(&self, index: u32) -> Result<Self, HdError> {
+        self.derive_child_with_tweak(index).map(|(child, _)| child)
+    }
+
+    /// Derives a key following the path, returns it with the sum of tweaks of every step, so
+    /// `child = self + tweak * G`
+    pub fn derive_path_with_tweak(&self, path: &[u32]) -> Result<(Self, P::Scalar), HdError> {
+        let mut key = self.clone();
+        let mut total_tweak: P::Scalar = ECScalar::zero();
+        for index in path {
+            let (child, tweak) = key.derive_child_with_tweak(*index)?;
+            key = child;
+            total_tweak = total_tweak + tweak;
+        }
+        Ok((key, total_tweak))
+    }
+
+    pub fn derive_path(&self, path: &[u32]) -> Result<Self, HdError> {
+        self.derive_path_with_tweak(path).map(|(key, _)| key)
+    }
+
+    /// Serializes the key as base58check-encoded `xpub` string
+    pub fn to_base58(&self) -> String {
+        encode_extended_key(
+            XPUB_VERSION,
+            self.depth,
+            self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &compress(&self.public_key),
+        )
+    }
+
+    pub fn from_base58(s: &str) -> Result<Self, HdError> {
+        let (depth, parent_fingerprint, child_number, chain_code, key) =
+            decode_extended_key(s, XPUB_VERSION)?;
+        let public_key = P::from_compressed(&key).map_err(|_| HdError::InvalidExtendedKey)?;
+        Ok(ExtendedPublicKey {
+            depth,
+            parent_fingerprint,
+            child_number,
+            chain_code,
+            public_key,
+        })
+    }
+}
+
+/// Computes `I = HMAC-SHA512(chain_code, data || ser32(index))` and returns `(I_L, I_R)`
+///
+/// If `I_L` isn't a valid tweak, `I = HMAC-SHA512(chain_code, 0x01 || I_R || ser32(index))`
+/// is computed instead until it is, as SLIP-10 specifies.
+fn derive_tweak<P, F>(
+    chain_code: &ChainCode,
+    data: &[u8],
+    index: u32,
+    is_valid: F,
+) -> (P::Scalar, ChainCode)
+where
+    P: ECPoint,
+    F: Fn(&P::Scalar) -> bool,
+{
+    let index_bytes = index.to_be_bytes();
+    let (mut il, mut ir) = hmac_sha512(chain_code, &[data, &index_bytes]);
+    loop {
+        if let Some(tweak) = scalar_from_bytes::<P>(&il) {
+            if is_valid(&tweak) {
+                return (tweak, ir);
+            }
+        }
+        let (l, r) = hmac_sha512(chain_code, &[&[1u8], &ir, &index_bytes]);
+        il = l;
+        ir = r;
+    }
+}
+
+/// Parses big-endian integer, returns `None` unless it's less than the curve order
+fn scalar_from_bytes<P: ECPoint>(bytes: &[u8; 32]) -> Option<P::Scalar> {
+    let n = BigInt::from_bytes(bytes);
+    if n < P::Scalar::q() {
+        Some(ECScalar::from(&n))
+    } else {
+        None
+    }
+}
+
+fn is_zero<P: ECPoint>(scalar: &P::Scalar) -> bool {
+    scalar.to_big_int() == BigInt::zero()
+}
+
+/// Checks whether `a = -b`, i.e. whether `a + b` is the point at infinity
+fn is_negation<P: ECPoint>(a: &P, b: &P) -> bool {
+    a.x_coor() == b.x_coor() && a.y_coor() != b.y_coor()
+}
+
+fn encode_extended_key(
+    version: [u8; 4],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: &ChainCode,
+    key: &[u8; 33],
+) -> String {
+    let mut bytes = Vec::with_capacity(EXTENDED_KEY_SIZE);
+    bytes.extend_from_slice(&version);
+    bytes.push(depth);
+    bytes.extend_from_slice(&parent_fingerprint);
+    bytes.extend_from_slice(&child_number.to_be_bytes());
+    bytes.extend_from_slice(chain_code);
+    bytes.extend_from_slice(key);
+    bs58::encode(bytes).with_check().into_string()
+}
+
+#[allow(clippy::type_complexity)]
+fn decode_extended_key(
+    s: &str,
+    version: [u8; 4],
+) -> Result<(u8, [u8; 4], u32, ChainCode, [u8; 33]), HdError> {
+    let bytes = bs58::decode(s)
+        .with_check(None)
+        .into_vec()
+        .map_err(|_| HdError::InvalidExtendedKey)?;
+    if bytes.len() != EXTENDED_KEY_SIZE || bytes[..4] != version {
+        return Err(HdError::InvalidExtendedKey);
+    }
+    let depth = bytes[4];
+    let mut parent_fingerprint = [0u8; 4];
+    parent_fingerprint.copy_from_slice(&bytes[5..9]);
+    let mut child_number = [0u8; 4];
+    child_number.copy_from_slice(&bytes[9..13]);
+    let child_number = u32::from_be_bytes(child_number);
+    if depth == 0 && (parent_fingerprint != [0u8; 4] || child_number != 0) {
+        return Err(HdError::InvalidExtendedKey);
+    }
+    let mut chain_code = [0u8; 32];
+    chain_code.copy_from_slice(&bytes[13..45]);
+    let mut key = [0u8; 33];
+    key.copy_from_slice(&bytes[45..]);
+    Ok((depth, parent_fingerprint, child_number, chain_code, key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptographic_primitives::hd_derivation::{parse_path, HARDENED_KEY_OFFSET};
+    use crate::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+
+    const H: u32 = HARDENED_KEY_OFFSET;
+
+    fn hex32(s: &str) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&hex::decode(s).unwrap());
+        bytes
+    }
+
+    #[test]
+    fn bip32_test_vector_1() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        // (path, xpub, xprv)
+        let vectors = [
+            (
+                "m",
+                "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8",
+                "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+            ),
+            (
+                "m/0H",
+                "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw",
+                "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+            ),
+            (
+                "m/0H/1",
+                "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ",
+                "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+            ),
+            (
+                "m/0H/1/2H",
+                "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5",
+                "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM",
+            ),
+            (
+                "m/0H/1/2H/2",
+                "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV",
+                "xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334",
+            ),
+            (
+                "m/0H/1/2H/2/1000000000",
+                "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy",
+                "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76",
+            ),
+        ];
+        let master = ExtendedPrivateKey::<secp256_k1::GE>::new_master(&seed).unwrap();
+        for (path, xpub, xprv) in vectors.iter() {
+            let key = master.derive_path(&parse_path(path).unwrap()).unwrap();
+            assert_eq!(&key.to_base58(), xprv, "{}", path);
+            assert_eq!(&key.public_key().to_base58(), xpub, "{}", path);
+
+            let parsed = ExtendedPrivateKey::<secp256_k1::GE>::from_base58(xprv).unwrap();
+            assert_eq!(parsed, key);
+            let parsed = ExtendedPublicKey::<secp256_k1::GE>::from_base58(xpub).unwrap();
+            assert_eq!(parsed, key.public_key());
+        }
+    }
+
+    #[test]
+    fn public_derivation_matches_private_derivation() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let master = ExtendedPrivateKey::<p256::GE>::new_master(&seed).unwrap();
+        let account = master.derive_path(&[H + 44, H]).unwrap();
+        let path = [0, 7, 1];
+        let (child, tweak) = account.public_key().derive_path_with_tweak(&path).unwrap();
+        let private_child = account.derive_path(&path).unwrap();
+        assert_eq!(child, private_child.public_key());
+        assert_eq!(private_child.secret_key, account.secret_key + tweak);
+
+        assert_eq!(
+            account.public_key().derive_child(H),
+            Err(HdError::HardenedPublicDerivation)
+        );
+    }
+
+    #[test]
+    fn threshold_shares_follow_public_derivation() {
+        // key shared with Feldman VSS, derivation is done by public key and chain code only
+        let secret: secp256_k1::FE = ECScalar::new_random();
+        let (vss, shares) = VerifiableSS::<secp256_k1::GE>::share(1, 3, &secret);
+        let root = ExtendedPublicKey::new(vss.commitments[0], hex32(&"42".repeat(32)));
+        let (child, tweak) = root.derive_path_with_tweak(&[3, 5]).unwrap();
+
+        let child_shares: Vec<_> = shares.iter().map(|share| *share + tweak).collect();
+        let mut child_vss = vss.clone();
+        child_vss.commitments[0] = child.public_key;
+        for (i, share) in child_shares.iter().enumerate() {
+            assert!(child_vss.validate_share(share, i + 1).is_ok());
+        }
+        let child_secret = vss.reconstruct(&[0, 2], &[child_shares[0], child_shares[2]]);
+        assert_eq!(secp256_k1::GE::generator() * child_secret, child.public_key);
+    }
+
+    #[test]
+    fn slip10_nist256p1_test_vector_1() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        // (path, fingerprint, chain code, private key, public key)
+        let vectors = [
+            (
+                "m",
+                "00000000",
+                "beeb672fe4621673f722f38529c07392fecaa61015c80c34f29ce8b41b3cb6ea",
+                "612091aaa12e22dd2abef664f8a01a82cae99ad7441b7ef8110424915c268bc2",
+                "0266874dc6ade47b3ecd096745ca09bcd29638dd52c2c12117b11ed3e458cfa9e8",
+            ),
+            (
+                "m/0H",
+                "be6105b5",
+                "3460cea53e6a6bb5fb391eeef3237ffd8724bf0a40e94943c98b83825342ee11",
+                "6939694369114c67917a182c59ddb8cafc3004e63ca5d3b84403ba8613debc0c",
+                "0384610f5ecffe8fda089363a41f56a5c7ffc1d81b59a612d0d649b2d22355590c",
+            ),
+            (
+                "m/0H/1",
+                "9b02312f",
+                "4187afff1aafa8445010097fb99d23aee9f599450c7bd140b6826ac22ba21d0c",
+                "284e9d38d07d21e4e281b645089a94f4cf5a5a81369acf151a1c3a57f18b2129",
+                "03526c63f8d0b4bbbf9c80df553fe66742df4676b241dabefdef67733e070f6844",
+            ),
+            // derivation retry: I_L of the first attempt is not less than the curve order
+            (
+                "m/28578H",
+                "be6105b5",
+                "e94c8ebe30c2250a14713212f6449b20f3329105ea15b652ca5bdfc68f6c65c2",
+                "06f0db126f023755d0b8d86d4591718a5210dd8d024e3e14b6159d63f53aa669",
+                "02519b5554a4872e8c9c1c847115363051ec43e93400e030ba3c36b52a3e70a5b7",
+            ),
+            (
+                "m/28578H/33941",
+                "3e2b7bc6",
+                "9e87fe95031f14736774cd82f25fd885065cb7c358c1edf813c72af535e83071",
+                "092154eed4af83e078ff9b84322015aefe5769e31270f62c3f66c33888335f3a",
+                "0235bfee614c0d5b2cae260000bb1d0d84b270099ad790022c1ae0b2e782efe120",
+            ),
+        ];
+        let master = ExtendedPrivateKey::<p256::GE>::new_master(&seed).unwrap();
+        for (path, fingerprint, chain_code, private_key, public_key) in vectors.iter() {
+            let path = parse_path(path).unwrap();
+            let key = master.derive_path(&path).unwrap();
+            assert_eq!(hex::encode(key.parent_fingerprint), *fingerprint);
+            assert_eq!(hex::encode(key.chain_code), *chain_code);
+            assert_eq!(
+                hex::encode(key.secret_key.to_big_int().to_bytes_be_padded(32)),
+                *private_key
+            );
+            assert_eq!(
+                hex::encode(&compress(&key.public_key().public_key)[..]),
+                *public_key
+            );
+
+            // non-hardened part of the path can be derived publicly
+            if let Some(&last) = path.last().filter(|index| !is_hardened(**index)) {
+                let parent = master.derive_path(&path[..path.len() - 1]).unwrap();
+                assert_eq!(
+                    parent.public_key().derive_child(last).unwrap(),
+                    key.public_key()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn slip10_nist256p1_seed_retry() {
+        let seed = hex::decode("a7305bc8df8d0951f0cb224c0e95d7707cbdf2c6ce7e8d481fec69c7ff5e9446")
+            .unwrap();
+        let master = ExtendedPrivateKey::<p256::GE>::new_master(&seed).unwrap();
+        assert_eq!(
+            hex::encode(master.chain_code),
+            "7762f9729fed06121fd13f326884c82f59aa95c57ac492ce8c9654e60efd130c"
+        );
+        assert_eq!(
+            hex::encode(master.secret_key.to_big_int().to_bytes_be_padded(32)),
+            "3b8c18469a4634517d6d0b65448f8e6c62091b45540a1743c5846be55d47d88f"
+        );
+        assert_eq!(
+            hex::encode(&compress(&master.public_key().public_key)[..]),
+            "0383619fadcde31063d8c5cb00dbfe1713f3e6fa169d8541a798752a1c1ca0cb20"
+        );
+    }
+
+    #[test]
+    fn extended_keys_roundtrip() {
+        let seed = [7u8; 32];
+        let key = ExtendedPrivateKey::<p256::GE>::new_master(&seed)
+            .unwrap()
+            .derive_path(&[H + 1, 2])
+            .unwrap();
+        let xprv = key.to_base58();
+        assert!(xprv.starts_with("xprv"));
+        assert_eq!(
+            ExtendedPrivateKey::<p256::GE>::from_base58(&xprv).unwrap(),
+            key
+        );
+        let xpub = key.public_key().to_base58();
+        assert!(xpub.starts_with("xpub"));
+        assert_eq!(
+            ExtendedPublicKey::<p256::GE>::from_base58(&xpub).unwrap(),
+            key.public_key()
+        );
+    }
+
+    #[test]
+    fn debug_output_does_not_contain_secret_key() {
+        let key = ExtendedPrivateKey::<p256::GE>::new_master(&[7u8; 32]).unwrap();
+        let secret = key.secret_key.to_big_int().to_hex();
+        let debug = format!("{:?}", key);
+        assert!(debug.contains("chain_code"));
+        assert!(!debug.contains("secret_key"));
+        assert!(!debug.to_lowercase().contains(&secret));
+    }
+
+    #[test]
+    fn rejects_invalid_extended_keys() {
+        let xpub = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+        let xprv = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+        type Xpub = ExtendedPublicKey<secp256_k1::GE>;
+        type Xprv = ExtendedPrivateKey<secp256_k1::GE>;
+
+        // wrong version
+        assert_eq!(Xpub::from_base58(xprv), Err(HdError::InvalidExtendedKey));
+        assert_eq!(Xprv::from_base58(xpub), Err(HdError::InvalidExtendedKey));
+        // broken checksum
+        let broken = xpub.replace("Fdp6W", "Fdp6X");
+        assert_eq!(Xpub::from_base58(&broken), Err(HdError::InvalidExtendedKey));
+
+        let master = Xprv::from_base58(xprv).unwrap();
+        let reencode = |version: [u8; 4], depth: u8, child_number: u32, key: &[u8; 33]| {
+            encode_extended_key(
+                version,
+                depth,
+                [0u8; 4],
+                child_number,
+                &master.chain_code,
+                key,
+            )
+        };
+        let public_key = compress(&master.public_key().public_key);
+        // depth 0 with non-zero child number
+        assert_eq!(
+            Xpub::from_base58(&reencode(XPUB_VERSION, 0, 1, &public_key)),
+            Err(HdError::InvalidExtendedKey)
+        );
+        // invalid public key prefix
+        let mut bad_public_key = public_key;
+        bad_public_key[0] = 4;
+        assert_eq!(
+            Xpub::from_base58(&reencode(XPUB_VERSION, 1, 1, &bad_public_key)),
+            Err(HdError::InvalidExtendedKey)
+        );
+        // private key equal to the curve order
+        let mut order = [0u8; 33];
+        order[1..].copy_from_slice(&secp256_k1::FE::q().to_bytes_be_padded(32));
+        assert_eq!(
+            Xprv::from_base58(&reencode(XPRV_VERSION, 1, 1, &order)),
+            Err(HdError::InvalidExtendedKey)
+        );
+        // private key not prefixed with zero
+        let mut bad_private_key = [0u8; 33];
+        bad_private_key[0] = 1;
+        bad_private_key[32] = 1;
+        assert_eq!(
+            Xprv::from_base58(&reencode(XPRV_VERSION, 1, 1, &bad_private_key)),
+            Err(HdError::InvalidExtendedKey)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_seed() {
+        assert_eq!(
+            ExtendedPrivateKey::<secp256_k1::GE>::new_master(&[0u8; 15]),
+            Err(HdError::InvalidSeedLength)
+        );
+        assert_eq!(
+            ExtendedPrivateKey::<secp256_k1::GE>::new_master(&[0u8; 65]),
+            Err(HdError::InvalidSeedLength)
+        );
+    }
+}
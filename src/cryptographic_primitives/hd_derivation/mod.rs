@@ -0,0 +1,160 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+//! Hierarchical deterministic key derivation
+//!
+//! * [bip32]: [BIP-32] for secp256k1 and P-256 (the latter as specified by [SLIP-10]),
+//!   including xpub/xprv serialization and public derivation of shared keys
+//! * [slip10_ed25519]: [SLIP-10] for Ed25519, which only supports hardened derivation
+//!
+//! [BIP-32]: https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+//! [SLIP-10]: https://github.com/satoshilabs/slips/blob/master/slip-0010.md
+
+use std::error::Error;
+use std::fmt;
+
+use crypto::digest::Digest;
+use crypto::ripemd160::Ripemd160;
+use hmac::{Hmac, Mac};
+use sha2::{Digest as _, Sha256, Sha512};
+
+pub mod bip32;
+pub mod slip10_ed25519;
+
+/// Child indexes starting from `2^31` denote hardened derivation
+pub const HARDENED_KEY_OFFSET: u32 = 0x8000_0000;
+
+pub type ChainCode = [u8; 32];
+
+#[derive(Copy, PartialEq, Eq, Clone, Debug)]
+pub enum HdError {
+    /// Seed must be between 128 and 512 bits long
+    InvalidSeedLength,
+    /// Hardened children can't be derived from a public key
+    HardenedPublicDerivation,
+    /// Non-hardened derivation isn't defined for the curve
+    NonHardenedDerivation,
+    /// Depth of the derived key would exceed 255
+    MaxDepthExceeded,
+    InvalidPath,
+    InvalidExtendedKey,
+}
+
+impl fmt::Display for HdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HdError::{:?}", self)
+    }
+}
+
+impl Error for HdError {}
+
+/// Returns whether `index` denotes a hardened child
+pub fn is_hardened(index: u32) -> bool {
+    index >= HARDENED_KEY_OFFSET
+}
+
+/// Parses derivation path like `m/44'/0'/0'/0/1`, hardened indexes are marked with `'`, `h`
+/// or `H`
+pub fn parse_path(path: &str) -> Result<Vec<u32>, HdError> {
+    let mut parts = path.split('/');
+    if parts.next() != Some("m") {
+        return Err(HdError::InvalidPath);
+    }
+    parts
+        .map(|part| {
+            let (number, offset) = match part.strip_suffix(&['\'', 'h', 'H'][..]) {
+                Some(number) => (number, HARDENED_KEY_OFFSET),
+                None => (part, 0),
+            };
+            if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(HdError::InvalidPath);
+            }
+            match number.parse::<u32>() {
+                Ok(index) if index < HARDENED_KEY_OFFSET => Ok(index + offset),
+                _ => Err(HdError::InvalidPath),
+            }
+        })
+        .collect()
+}
+
+fn check_seed_length(seed: &[u8]) -> Result<(), HdError> {
+    if seed.len() < 16 || seed.len() > 64 {
+        return Err(HdError::InvalidSeedLength);
+    }
+    Ok(())
+}
+
+/// Computes `HMAC-SHA512(key, data)` and splits it into two halves `(I_L, I_R)`
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut hmac = Hmac::<Sha512>::new_varkey(key).expect("HMAC accepts keys of any size");
+    for chunk in data {
+        hmac.input(chunk);
+    }
+    let code = hmac.result().code();
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&code[..32]);
+    right.copy_from_slice(&code[32..]);
+    (left, right)
+}
+
+/// First 4 bytes of `RIPEMD160(SHA256(public_key))`
+fn fingerprint(public_key: &[u8]) -> [u8; 4] {
+    let sha256 = Sha256::digest(public_key);
+    let mut ripemd = Ripemd160::new();
+    ripemd.input(&sha256);
+    let mut hash160 = [0u8; 20];
+    ripemd.result(&mut hash160);
+    let mut fingerprint = [0u8; 4];
+    fingerprint.copy_from_slice(&hash160[..4]);
+    fingerprint
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_paths() {
+        assert_eq!(parse_path("m"), Ok(vec![]));
+        assert_eq!(
+            parse_path("m/0'/1/2h/2/1000000000H"),
+            Ok(vec![
+                HARDENED_KEY_OFFSET,
+                1,
+                HARDENED_KEY_OFFSET + 2,
+                2,
+                HARDENED_KEY_OFFSET + 1_000_000_000
+            ])
+        );
+        assert_eq!(
+            parse_path("m/2147483647'"),
+            Ok(vec![HARDENED_KEY_OFFSET + 2_147_483_647])
+        );
+        for invalid in [
+            "",
+            "0/1",
+            "m/",
+            "m//1",
+            "m/2147483648",
+            "m/-1",
+            "m/+1",
+            "m/1''",
+            "m/x",
+            "M/1",
+        ]
+        .iter()
+        {
+            assert_eq!(
+                parse_path(invalid),
+                Err(HdError::InvalidPath),
+                "{}",
+                invalid
+            );
+        }
+    }
+}
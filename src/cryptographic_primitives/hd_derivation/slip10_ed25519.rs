@@ -0,0 +1,174 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+//! SLIP-10 key derivation for Ed25519
+//!
+//! Private keys are 32 bytes Ed25519 seeds as defined by [RFC 8032], the signing scalar is
+//! derived from the seed by hashing and clamping. As a result only hardened derivation is
+//! possible, there are no extended public keys.
+//!
+//! [RFC 8032]: https://tools.ietf.org/html/rfc8032#section-5.1.5
+
+use sha2::{Digest, Sha512};
+
+use super::{check_seed_length, fingerprint, hmac_sha512, is_hardened, ChainCode, HdError};
+use crate::arithmetic::traits::*;
+use crate::elliptic::curves::ed25519::{FE, GE};
+use crate::elliptic::curves::traits::{ECPoint, ECScalar};
+use crate::BigInt;
+
+const SEED_KEY: &[u8] = b"ed25519 seed";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtendedSecretKey {
+    pub depth: u8,
+    pub parent_fingerprint: [u8; 4],
+    pub child_number: u32,
+    pub chain_code: ChainCode,
+    pub secret_key: [u8; 32],
+}
+
+impl ExtendedSecretKey {
+    /// Derives master key from a seed of 16 to 64 bytes
+    pub fn new_master(seed: &[u8]) -> Result<Self, HdError> {
+        check_seed_length(seed)?;
+        let (secret_key, chain_code) = hmac_sha512(SEED_KEY, &[seed]);
+        Ok(ExtendedSecretKey {
+            depth: 0,
+            parent_fingerprint: [0u8; 4],
+            child_number: 0,
+            chain_code,
+            secret_key,
+        })
+    }
+
+    /// Derives hardened child key, `index` must be at least
+    /// [HARDENED_KEY_OFFSET](super::HARDENED_KEY_OFFSET)
+    pub fn derive_child(&self, index: u32) -> Result<Self, HdError> {
+        if !is_hardened(index) {
+            return Err(HdError::NonHardenedDerivation);
+        }
+        let depth = self.depth.checked_add(1).ok_or(HdError::MaxDepthExceeded)?;
+        let (secret_key, chain_code) = hmac_sha512(
+            &self.chain_code,
+            &[&[0u8], &self.secret_key, &index.to_be_bytes()],
+        );
+        Ok(ExtendedSecretKey {
+            depth,
+            parent_fingerprint: self.fingerprint(),
+            child_number: index,
+            chain_code,
+            secret_key,
+        })
+    }
+
+    /// Derives a key following the path, see [parse_path](super::parse_path)
+    pub fn derive_path(&self, path: &[u32]) -> Result<Self, HdError> {
+        path.iter()
+            .try_fold(self.clone(), |key, index| key.derive_child(*index))
+    }
+
+    /// Signing scalar: first half of `SHA-512(secret_key)` with the bits clamped
+    pub fn secret_scalar(&self) -> FE {
+        let hash = Sha512::digest(&self.secret_key);
+        let mut scalar_bytes = [0u8; 32];
+        scalar_bytes.copy_from_slice(&hash[..32]);
+        scalar_bytes[0] &= 248;
+        scalar_bytes[31] &= 63;
+        scalar_bytes[31] |= 64;
+        ECScalar::from(&BigInt::from_bytes_le(&scalar_bytes))
+    }
+
+    pub fn public_key(&self) -> GE {
+        GE::generator() * self.secret_scalar()
+    }
+
+    /// Key identifier as SLIP-10 defines it: `0x00` followed by the public key is hashed
+    pub fn fingerprint(&self) -> [u8; 4] {
+        let mut public_key = vec![0u8];
+        public_key.extend(self.public_key().pk_to_key_slice());
+        fingerprint(&public_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptographic_primitives::hd_derivation::{parse_path, HARDENED_KEY_OFFSET};
+
+    #[test]
+    fn slip10_ed25519_test_vector_1() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        // (path, fingerprint, chain code, private key, public key)
+        let vectors = [
+            (
+                "m",
+                "00000000",
+                "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+                "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+                "00a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed",
+            ),
+            (
+                "m/0H",
+                "ddebc675",
+                "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+                "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+                "008c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c",
+            ),
+            (
+                "m/0H/1H",
+                "13dab143",
+                "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+                "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+                "001932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c1187",
+            ),
+            (
+                "m/0H/1H/2H",
+                "ebe4cb29",
+                "2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c",
+                "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+                "00ae98736566d30ed0e9d2f4486a64bc95740d89c7db33f52121f8ea8f76ff0fc1",
+            ),
+            (
+                "m/0H/1H/2H/2H",
+                "316ec1c6",
+                "8f6d87f93d750e0efccda017d662a1b31a266e4a6f5993b15f5c1f07f74dd5cc",
+                "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662",
+                "008abae2d66361c879b900d204ad2cc4984fa2aa344dd7ddc46007329ac76c429c",
+            ),
+            (
+                "m/0H/1H/2H/2H/1000000000H",
+                "d6322ccd",
+                "68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230",
+                "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+                "003c24da049451555d51a7014a37337aa4e12d41e485abccfa46b47dfb2af54b7a",
+            ),
+        ];
+        let master = ExtendedSecretKey::new_master(&seed).unwrap();
+        for (path, fingerprint, chain_code, private_key, public_key) in vectors.iter() {
+            let key = master.derive_path(&parse_path(path).unwrap()).unwrap();
+            assert_eq!(hex::encode(key.parent_fingerprint), *fingerprint);
+            assert_eq!(hex::encode(key.chain_code), *chain_code);
+            assert_eq!(hex::encode(key.secret_key), *private_key);
+            assert_eq!(
+                format!("00{}", hex::encode(key.public_key().pk_to_key_slice())),
+                *public_key
+            );
+        }
+    }
+
+    #[test]
+    fn non_hardened_derivation_is_rejected() {
+        let master = ExtendedSecretKey::new_master(&[1u8; 32]).unwrap();
+        assert_eq!(master.derive_child(0), Err(HdError::NonHardenedDerivation));
+        assert!(master.derive_child(HARDENED_KEY_OFFSET).is_ok());
+        assert_eq!(
+            ExtendedSecretKey::new_master(&[1u8; 8]),
+            Err(HdError::InvalidSeedLength)
+        );
+    }
+}
@@ -6,7 +6,10 @@
 */
 
 pub mod commitments;
+pub mod elgamal;
 pub mod hashing;
+pub mod hd_derivation;
+pub mod multiparty;
 pub mod proofs;
 pub mod secret_sharing;
 pub mod twoparty;
@@ -0,0 +1,340 @@
+#![allow(non_snake_case)]
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+use std::error::Error;
+use std::fmt::{self, Debug};
+
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use crate::arithmetic::traits::*;
+use crate::cryptographic_primitives::hashing::hash_sha256::HSha256;
+use crate::cryptographic_primitives::hashing::traits::Hash;
+use crate::cryptographic_primitives::proofs::sigma_valid_pedersen::PedersenProof;
+use crate::cryptographic_primitives::proofs::sigma_valid_pedersen_blind::PedersenBlindingProof;
+use crate::cryptographic_primitives::secret_sharing::pvss::{DecryptedShare, PubliclyVerifiableSS};
+use crate::elliptic::curves::traits::*;
+use crate::BigInt;
+use crate::ErrorSS;
+
+/// n-party commit-reveal coin toss. Parties are indexed `0..n`:
+/// 1: party i commits to random seed m_i with Pedersen commitment c_i = m_i*G + r_i*H and
+///    broadcasts it with PedersenProof
+/// 2: once all commitments are verified, party i reveals m_i with PedersenBlindingProof
+/// 3: the result is the sum of all seeds
+///
+/// The result is uniform as long as one party is honest, and every party that fails to commit or
+/// reveal properly is identified. However the last party to reveal can abort after seeing
+/// the other seeds, which lets it choose between two results. Use [BeaconDealing] if that is
+/// not acceptable.
+#[derive(Derivative, Serialize, Deserialize)]
+#[derivative(Clone(bound = "PedersenProof<P>: Clone"))]
+#[derivative(Debug(bound = "PedersenProof<P>: Debug"))]
+#[derivative(PartialEq(bound = "PedersenProof<P>: PartialEq"))]
+#[serde(bound(serialize = "PedersenProof<P>: Serialize"))]
+#[serde(bound(deserialize = "PedersenProof<P>:  Deserialize<'de>"))]
+pub struct CommitMessage<P: ECPoint> {
+    pub proof: PedersenProof<P>,
+}
+
+#[derive(Derivative, Serialize, Deserialize)]
+#[derivative(Clone(bound = "PedersenBlindingProof<P>: Clone"))]
+#[derivative(Debug(bound = "PedersenBlindingProof<P>: Debug"))]
+#[derivative(PartialEq(bound = "PedersenBlindingProof<P>: PartialEq"))]
+#[serde(bound(serialize = "PedersenBlindingProof<P>: Serialize"))]
+#[serde(bound(deserialize = "PedersenBlindingProof<P>:  Deserialize<'de>"))]
+pub struct RevealMessage<P: ECPoint> {
+    pub proof: PedersenBlindingProof<P>,
+}
+
+/// Parties that deviated from the protocol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbortError {
+    pub parties: Vec<usize>,
+}
+
+impl fmt::Display for AbortError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AbortError: parties {:?} aborted", self.parties)
+    }
+}
+
+impl Error for AbortError {
+    fn description(&self) -> &str {
+        "Parties aborted the protocol"
+    }
+}
+
+impl<P> CommitMessage<P>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Zeroize,
+{
+    // returns the message along with seed and blinding factor, which are kept until reveal
+    pub fn commit() -> (CommitMessage<P>, P::Scalar, P::Scalar) {
+        let seed: P::Scalar = ECScalar::new_random();
+        let blinding: P::Scalar = ECScalar::new_random();
+        let proof = PedersenProof::prove(&seed, &blinding);
+        (CommitMessage { proof }, seed, blinding)
+    }
+}
+
+impl<P> RevealMessage<P>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Zeroize + Clone,
+{
+    pub fn reveal(seed: &P::Scalar, blinding: &P::Scalar) -> RevealMessage<P> {
+        RevealMessage {
+            proof: PedersenBlindingProof::prove(seed, blinding),
+        }
+    }
+}
+
+// checks commitments of all parties, a party should only reveal if this succeeds
+pub fn verify_commitments<P>(commits: &[CommitMessage<P>]) -> Result<(), AbortError>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Zeroize,
+{
+    let parties: Vec<usize> = commits
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| PedersenProof::verify(&c.proof).is_err())
+        .map(|(i, _)| i)
+        .collect();
+    if parties.is_empty() {
+        Ok(())
+    } else {
+        Err(AbortError { parties })
+    }
+}
+
+// reveals[i] is None if party i didn't reveal its seed
+pub fn finalize<P>(
+    commits: &[CommitMessage<P>],
+    reveals: &[Option<RevealMessage<P>>],
+) -> Result<P::Scalar, AbortError>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Zeroize + Clone,
+{
+    assert_eq!(commits.len(), reveals.len());
+    verify_commitments(commits)?;
+    let parties: Vec<usize> = commits
+        .iter()
+        .zip(reveals)
+        .enumerate()
+        .filter(|(_, (commit, reveal))| match reveal {
+            Some(reveal) => {
+                PedersenBlindingProof::verify(&reveal.proof).is_err()
+                    || reveal.proof.com != commit.proof.com
+            }
+            None => true,
+        })
+        .map(|(i, _)| i)
+        .collect();
+    if !parties.is_empty() {
+        return Err(AbortError { parties });
+    }
+    Ok(reveals
+        .iter()
+        .flatten()
+        .fold(P::Scalar::zero(), |acc, reveal| {
+            acc + reveal.proof.m.clone()
+        }))
+}
+
+/// Expands coin toss result into a uniformly random number of `bits` bits:
+/// `H(coin, 0) || H(coin, 1) || ...` truncated to `bits`
+pub fn expand_to_bits<S: ECScalar>(coin: &S, bits: usize) -> BigInt {
+    let coin = coin.to_big_int();
+    let blocks = bits.div_ceil(256);
+    let expanded = (0..blocks).fold(BigInt::zero(), |acc, i| {
+        let block = HSha256::create_hash(&[&coin, &BigInt::from(i as u64)]);
+        (acc << 256) + block
+    });
+    expanded >> (blocks * 256 - bits)
+}
+
+/// Bias-resistant coin toss for a randomness beacon, based on SCRAPE (see [PubliclyVerifiableSS]):
+/// 1: party i chooses random s_i and shares it with PVSS among all n parties (with threshold t)
+/// 2: once dealings are verified, party i opens s_i
+/// 3: if party i refuses to open, any t+1 parties decrypt their shares of its dealing
+///    and reconstruct s_i*G
+/// 4: the result is the hash of Σ s_i*G over all parties with valid dealings
+///
+/// As opened secrets can always be recovered, aborting in step 2 gives no advantage, given that
+/// at most t parties are corrupted and t < n/2.
+#[derive(Derivative, Serialize, Deserialize)]
+#[derivative(Clone(bound = "PubliclyVerifiableSS<P>: Clone"))]
+#[derivative(Debug(bound = "PubliclyVerifiableSS<P>: Debug"))]
+#[derivative(PartialEq(bound = "PubliclyVerifiableSS<P>: PartialEq"))]
+#[serde(bound(serialize = "PubliclyVerifiableSS<P>: Serialize"))]
+#[serde(bound(deserialize = "PubliclyVerifiableSS<P>:  Deserialize<'de>"))]
+pub struct BeaconDealing<P: ECPoint> {
+    pub pvss: PubliclyVerifiableSS<P>,
+}
+
+impl<P> BeaconDealing<P>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Zeroize + Clone,
+{
+    // returns the dealing along with the secret, which is kept until opening
+    pub fn deal(t: usize, public_keys: &[P]) -> (BeaconDealing<P>, P::Scalar) {
+        let secret: P::Scalar = ECScalar::new_random();
+        let pvss = PubliclyVerifiableSS::share(t, &secret, public_keys);
+        (BeaconDealing { pvss }, secret)
+    }
+
+    pub fn verify(&self, public_keys: &[P]) -> Result<(), ErrorSS> {
+        self.pvss.verify(public_keys)
+    }
+
+    // checks dealer's opening, returns its contribution s_i*G
+    pub fn verify_opening(&self, secret: &P::Scalar) -> Option<P> {
+        let G: P = ECPoint::generator();
+        let contribution = G * secret.clone();
+        if contribution == self.pvss.vss.commitments[0] {
+            Some(contribution)
+        } else {
+            None
+        }
+    }
+
+    // recovers contribution of a dealer that didn't open, invalid shares are skipped
+    pub fn recover(&self, shares: &[DecryptedShare<P>], public_keys: &[P]) -> Option<P> {
+        let mut valid: Vec<DecryptedShare<P>> = vec![];
+        for share in shares {
+            let is_valid = valid.iter().all(|s| s.index != share.index)
+                && public_keys
+                    .get(share.index)
+                    .map(|pk| self.pvss.verify_decrypted_share(share, pk).is_ok())
+                    .unwrap_or(false);
+            if is_valid {
+                valid.push(share.clone());
+            }
+        }
+        if valid.len() < self.pvss.vss.reconstruct_limit() {
+            return None;
+        }
+        self.pvss.reconstruct(&valid, public_keys).ok()
+    }
+}
+
+// contributions of all parties whose dealings were verified, in any fixed order
+pub fn beacon_output<P>(contributions: &[P]) -> P::Scalar
+where
+    P: ECPoint + Clone,
+{
+    let mut contributions = contributions.iter().cloned();
+    let head = contributions.next().expect("at least one contribution");
+    let sum = contributions.fold(head, |acc, c| acc + c);
+    HSha256::create_hash_from_ge(&[&sum])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_for_all_curves;
+
+    test_for_all_curves!(test_coin_toss_4_parties);
+    fn test_coin_toss_4_parties<P>()
+    where
+        P: ECPoint + Clone + Debug,
+        P::Scalar: PartialEq + Clone + Debug + Zeroize,
+    {
+        let (commits, secrets): (Vec<CommitMessage<P>>, Vec<_>) = (0..4)
+            .map(|_| {
+                let (commit, seed, blinding) = CommitMessage::<P>::commit();
+                (commit, (seed, blinding))
+            })
+            .unzip();
+        assert!(verify_commitments(&commits).is_ok());
+        let reveals: Vec<_> = secrets
+            .iter()
+            .map(|(seed, blinding)| Some(RevealMessage::<P>::reveal(seed, blinding)))
+            .collect();
+        let coin = finalize(&commits, &reveals).unwrap();
+        let expected = secrets
+            .iter()
+            .fold(P::Scalar::zero(), |acc, (seed, _)| acc + seed.clone());
+        assert_eq!(coin, expected);
+
+        let bits = expand_to_bits(&coin, 300);
+        assert!(bits.bit_length() <= 300);
+        assert_eq!(bits, expand_to_bits(&coin, 300));
+        assert_eq!(expand_to_bits(&coin, 512) >> 212, bits);
+    }
+
+    test_for_all_curves!(test_coin_toss_identifies_aborts);
+    fn test_coin_toss_identifies_aborts<P>()
+    where
+        P: ECPoint + Clone + Debug,
+        P::Scalar: PartialEq + Clone + Debug + Zeroize,
+    {
+        let (commits, secrets): (Vec<CommitMessage<P>>, Vec<_>) = (0..4)
+            .map(|_| {
+                let (commit, seed, blinding) = CommitMessage::<P>::commit();
+                (commit, (seed, blinding))
+            })
+            .unzip();
+        let mut reveals: Vec<_> = secrets
+            .iter()
+            .map(|(seed, blinding)| Some(RevealMessage::<P>::reveal(seed, blinding)))
+            .collect();
+        // party 1 doesn't reveal, party 3 reveals a different seed
+        reveals[1] = None;
+        let (_, other_seed, other_blinding) = CommitMessage::<P>::commit();
+        reveals[3] = Some(RevealMessage::reveal(&other_seed, &other_blinding));
+        assert_eq!(
+            finalize(&commits, &reveals),
+            Err(AbortError {
+                parties: vec![1, 3]
+            })
+        );
+    }
+
+    test_for_all_curves!(test_beacon_recovers_withheld_secret);
+    fn test_beacon_recovers_withheld_secret<P>()
+    where
+        P: ECPoint + Clone + Debug,
+        P::Scalar: PartialEq + Clone + Debug + Zeroize,
+    {
+        let n = 4;
+        let t = 1;
+        let G: P = ECPoint::generator();
+        let sks: Vec<P::Scalar> = (0..n).map(|_| ECScalar::new_random()).collect();
+        let pks: Vec<P> = sks.iter().map(|sk| G.clone() * sk.clone()).collect();
+
+        let (dealings, secrets): (Vec<BeaconDealing<P>>, Vec<_>) =
+            (0..n).map(|_| BeaconDealing::deal(t, &pks)).unzip();
+        assert!(dealings.iter().all(|d| d.verify(&pks).is_ok()));
+
+        // party 2 withholds its secret, parties 0 and 3 recover it
+        let contributions: Vec<P> = (0..n)
+            .map(|i| {
+                if i == 2 {
+                    let shares: Vec<_> = [0, 3]
+                        .iter()
+                        .map(|j| dealings[i].pvss.decrypt_share(*j, &sks[*j]).unwrap())
+                        .collect();
+                    dealings[i].recover(&shares, &pks).unwrap()
+                } else {
+                    dealings[i].verify_opening(&secrets[i]).unwrap()
+                }
+            })
+            .collect();
+        assert!(dealings[0].verify_opening(&secrets[1]).is_none());
+
+        let opened: Vec<P> = secrets.iter().map(|s| G.clone() * s.clone()).collect();
+        assert_eq!(beacon_output(&contributions), beacon_output(&opened));
+    }
+}
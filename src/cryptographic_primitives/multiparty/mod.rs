@@ -0,0 +1,12 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+/// This is an implementation of n-party coin tossing. The commit-reveal protocol generalizes
+/// twoparty::coin_flip_optimal_rounds and identifies aborting parties. A party that aborts
+/// after seeing the other reveals can still bias the result, so a bias-resistant variant
+/// built on PVSS is also provided for randomness beacons.
+pub mod coin_flip;
@@ -0,0 +1,411 @@
+#![allow(non_snake_case)]
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+use serde::{Deserialize, Serialize};
+
+use super::ProofError;
+use crate::arithmetic::traits::*;
+use crate::cryptographic_primitives::hashing::hash_sha256::HSha256;
+use crate::cryptographic_primitives::hashing::traits::Hash;
+use crate::elliptic::curves::traits::*;
+use crate::BigInt;
+
+/// Inner product argument from Bünz et al. 2018. Bulletproofs: Short Proofs for Confidential
+/// Transactions and More. In IEEE S&P 2018 (Protocol 2 made non-interactive).
+///
+/// Proves knowledge of vectors `a`, `b` of length `n` (a power of two) such that
+/// `P = <a, g> + <b, h> + <a, b>*u` for public generators `g`, `h`, `u`. The proof consists of
+/// `2*log(n)` points and 2 scalars.
+///
+/// Generators must be independent, ie. nobody may know discrete log relation between them. Use
+/// [Generators] to derive such vectors.
+///
+/// Challenges are derived from a `seed` which must bind everything the prover committed to
+/// before. [prove](Self::prove) and [verify](Self::verify) take care of it for standalone use,
+/// while protocols built on top of the argument (like [RangeProof]) derive the seed from their
+/// own transcript and use [prove_with_seed](Self::prove_with_seed) and
+/// [verification_scalars](Self::verification_scalars).
+///
+/// [RangeProof]: super::range_proof::RangeProof
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct InnerProductArg<P: ECPoint> {
+    pub L: Vec<P>,
+    pub R: Vec<P>,
+    pub a: P::Scalar,
+    pub b: P::Scalar,
+}
+
+/// Scalars that turn verification of [InnerProductArg] into a single multi-scalar
+/// multiplication
+///
+/// The argument is valid iff
+/// `P + Σ x_j^2 * L_j + Σ x_j^-2 * R_j = a * Σ s_i * g_i + b * Σ s_i^-1 * h_i + a*b * u`
+pub struct VerificationScalars<S> {
+    /// `x_j^2` for every round `j`
+    pub challenges_sq: Vec<S>,
+    /// `x_j^-2` for every round `j`
+    pub challenges_inv_sq: Vec<S>,
+    /// `s_i` for every generator `i`
+    pub s: Vec<S>,
+    /// `s_i^-1` for every generator `i`
+    pub s_inv: Vec<S>,
+}
+
+impl<P> InnerProductArg<P>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Clone,
+{
+    /// Proves that `P = <a, g> + <b, h> + <a, b>*u`
+    pub fn prove(g: &[P], h: &[P], u: &P, P: &P, a: &[P::Scalar], b: &[P::Scalar]) -> Self {
+        let seed = hash_points(&BigInt::zero(), &[u, P]);
+        Self::prove_with_seed(&seed, g, h, u, a, b)
+    }
+
+    /// Verifies that prover knows `a`, `b` such that `P = <a, g> + <b, h> + <a, b>*u`
+    pub fn verify(&self, g: &[P], h: &[P], u: &P, P: &P) -> Result<(), ProofError> {
+        let seed = hash_points(&BigInt::zero(), &[u, P]);
+        let scalars = self.verification_scalars(&seed, g.len())?;
+        if h.len() != g.len() {
+            return Err(ProofError);
+        }
+
+        let ab = mul(&self.a, &self.b);
+        let scalars_iter = scalars
+            .s
+            .iter()
+            .map(|s_i| mul(&self.a, s_i))
+            .chain(scalars.s_inv.iter().map(|s_i_inv| mul(&self.b, s_i_inv)))
+            .chain(std::iter::once(ab))
+            .chain(scalars.challenges_sq.iter().map(negate))
+            .chain(scalars.challenges_inv_sq.iter().map(negate));
+        let points_iter = g
+            .iter()
+            .chain(h)
+            .chain(std::iter::once(u))
+            .chain(&self.L)
+            .chain(&self.R)
+            .cloned();
+
+        let expected_P = P::vartime_multi_scalar_mul(
+            &scalars_iter.collect::<Vec<_>>(),
+            &points_iter.collect::<Vec<_>>(),
+        );
+        if expected_P == *P {
+            Ok(())
+        } else {
+            Err(ProofError)
+        }
+    }
+
+    /// Proves that `<a, g> + <b, h> + <a, b>*u` is the point prover committed to, with
+    /// challenges derived from `seed`
+    ///
+    /// ## Panics
+    /// Panics if vectors have different lengths, or their length is not a power of two
+    pub fn prove_with_seed(
+        seed: &BigInt,
+        g: &[P],
+        h: &[P],
+        u: &P,
+        a: &[P::Scalar],
+        b: &[P::Scalar],
+    ) -> Self {
+        let n = g.len();
+        assert!(n.is_power_of_two(), "length must be a power of two");
+        assert!(
+            h.len() == n && a.len() == n && b.len() == n,
+            "vectors must have the same length"
+        );
+
+        let mut g = g.to_vec();
+        let mut h = h.to_vec();
+        let mut a = a.to_vec();
+        let mut b = b.to_vec();
+        let mut L = Vec::with_capacity(n.trailing_zeros() as usize);
+        let mut R = Vec::with_capacity(n.trailing_zeros() as usize);
+        let mut challenge = seed.clone();
+
+        while a.len() > 1 {
+            let half = a.len() / 2;
+            let (a_lo, a_hi) = a.split_at(half);
+            let (b_lo, b_hi) = b.split_at(half);
+            let (g_lo, g_hi) = g.split_at(half);
+            let (h_lo, h_hi) = h.split_at(half);
+
+            let c_L = inner_product(a_lo, b_hi);
+            let c_R = inner_product(a_hi, b_lo);
+            let L_j = P::multi_scalar_mul(
+                &[a_lo, b_hi, &[c_L]].concat(),
+                &[g_hi, h_lo, std::slice::from_ref(u)].concat(),
+            );
+            let R_j = P::multi_scalar_mul(
+                &[a_hi, b_lo, &[c_R]].concat(),
+                &[g_lo, h_hi, std::slice::from_ref(u)].concat(),
+            );
+
+            challenge = hash_points(&challenge, &[&L_j, &R_j]);
+            let x: P::Scalar = ECScalar::from(&challenge);
+            let x_inv = x.invert();
+
+            a = fold_scalars(a_lo, a_hi, &x, &x_inv);
+            b = fold_scalars(b_lo, b_hi, &x_inv, &x);
+            g = fold_points(g_lo, g_hi, &x_inv, &x);
+            h = fold_points(h_lo, h_hi, &x, &x_inv);
+            L.push(L_j);
+            R.push(R_j);
+        }
+
+        InnerProductArg {
+            L,
+            R,
+            a: a.pop().unwrap(),
+            b: b.pop().unwrap(),
+        }
+    }
+
+    /// Computes challenges and scalars `s_i` (see [VerificationScalars]) for generators of
+    /// length `n`
+    ///
+    /// Returns error if proof doesn't match length `n`.
+    pub fn verification_scalars(
+        &self,
+        seed: &BigInt,
+        n: usize,
+    ) -> Result<VerificationScalars<P::Scalar>, ProofError> {
+        let rounds = self.L.len();
+        if n.trailing_zeros() as usize != rounds || !n.is_power_of_two() || self.R.len() != rounds {
+            return Err(ProofError);
+        }
+
+        let mut challenge = seed.clone();
+        let mut challenges = Vec::with_capacity(rounds);
+        let mut challenges_inv = Vec::with_capacity(rounds);
+        for (L_j, R_j) in self.L.iter().zip(&self.R) {
+            challenge = hash_points(&challenge, &[L_j, R_j]);
+            let x: P::Scalar = ECScalar::from(&challenge);
+            if x.to_big_int().is_zero() {
+                return Err(ProofError);
+            }
+            challenges_inv.push(x.invert());
+            challenges.push(x);
+        }
+
+        // s_i = Π x_j^(±1), where the sign is given by bit (rounds-1-j) of i: generator g_i ends
+        // up in the upper half at round j iff the bit is set
+        let s: Vec<P::Scalar> = (0..n)
+            .map(|i| {
+                (0..rounds)
+                    .map(|j| {
+                        if i >> (rounds - 1 - j) & 1 == 1 {
+                            &challenges[j]
+                        } else {
+                            &challenges_inv[j]
+                        }
+                    })
+                    .fold(one(), |acc, x| mul(&acc, x))
+            })
+            .collect();
+        // s_i^-1 is s_(n-1-i) as the bits are flipped
+        let s_inv = s.iter().rev().cloned().collect();
+
+        Ok(VerificationScalars {
+            challenges_sq: challenges.iter().map(|x| mul(x, x)).collect(),
+            challenges_inv_sq: challenges_inv.iter().map(|x| mul(x, x)).collect(),
+            s,
+            s_inv,
+        })
+    }
+}
+
+/// Hashes previous challenge along with points sent by prover, returns the next challenge
+pub(super) fn hash_points<P: ECPoint>(previous: &BigInt, points: &[&P]) -> BigInt {
+    let points: Vec<BigInt> = points
+        .iter()
+        .map(|p| p.bytes_compressed_to_big_int())
+        .collect();
+    let input: Vec<&BigInt> = std::iter::once(previous).chain(&points).collect();
+    HSha256::create_hash(&input)
+}
+
+pub(super) fn inner_product<S: ECScalar + Clone>(a: &[S], b: &[S]) -> S {
+    a.iter()
+        .zip(b)
+        .fold(S::zero(), |acc, (a_i, b_i)| add(&acc, &mul(a_i, b_i)))
+}
+
+fn fold_scalars<S: ECScalar>(lo: &[S], hi: &[S], x_lo: &S, x_hi: &S) -> Vec<S> {
+    lo.iter()
+        .zip(hi)
+        .map(|(lo_i, hi_i)| add(&mul(lo_i, x_lo), &mul(hi_i, x_hi)))
+        .collect()
+}
+
+fn fold_points<P>(lo: &[P], hi: &[P], x_lo: &P::Scalar, x_hi: &P::Scalar) -> Vec<P>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Clone,
+{
+    lo.iter()
+        .zip(hi)
+        .map(|(lo_i, hi_i)| {
+            P::multi_scalar_mul(&[x_lo.clone(), x_hi.clone()], &[lo_i.clone(), hi_i.clone()])
+        })
+        .collect()
+}
+
+pub(super) fn one<S: ECScalar>() -> S {
+    S::from(&BigInt::one())
+}
+
+pub(super) fn add<S: ECScalar>(a: &S, b: &S) -> S {
+    a.add(&b.get_element())
+}
+
+pub(super) fn sub<S: ECScalar>(a: &S, b: &S) -> S {
+    a.sub(&b.get_element())
+}
+
+pub(super) fn mul<S: ECScalar>(a: &S, b: &S) -> S {
+    a.mul(&b.get_element())
+}
+
+pub(super) fn negate<S: ECScalar>(s: &S) -> S {
+    sub(&S::zero(), s)
+}
+
+/// Vectors of independent generators derived by hashing to curve
+///
+/// `g_i = hash_to_curve(label || "g" || i)` and `h_i = hash_to_curve(label || "h" || i)`, so
+/// both prover and verifier derive the same vectors, and nobody knows discrete log relations
+/// between them, [generator](ECPoint::generator) and [base_point2](ECPoint::base_point2).
+#[derive(Clone, Debug)]
+pub struct Generators<P> {
+    pub g_vec: Vec<P>,
+    pub h_vec: Vec<P>,
+}
+
+impl<P: ECPoint> Generators<P> {
+    /// Label used by [new](Self::new)
+    pub const DEFAULT_LABEL: &'static [u8] = b"curv bulletproofs generators";
+
+    /// Derives vectors of `n` generators each with [DEFAULT_LABEL](Self::DEFAULT_LABEL)
+    pub fn new(n: usize) -> Self {
+        Self::with_label(Self::DEFAULT_LABEL, n)
+    }
+
+    /// Derives vectors of `n` generators each, domain separated by `label`
+    pub fn with_label(label: &[u8], n: usize) -> Self {
+        let derive = |vector: u8, i: usize| {
+            let mut message = label.to_vec();
+            message.push(vector);
+            message.extend_from_slice(&(i as u64).to_be_bytes());
+            P::hash_to_curve(&message)
+        };
+        Generators {
+            g_vec: (0..n).map(|i| derive(b'g', i)).collect(),
+            h_vec: (0..n).map(|i| derive(b'h', i)).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_vector<S: ECScalar>(n: usize) -> Vec<S> {
+        (0..n).map(|_| S::new_random()).collect()
+    }
+
+    fn statement<P>(gens: &Generators<P>, u: &P, a: &[P::Scalar], b: &[P::Scalar]) -> P
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone,
+    {
+        let scalars = [a, b, &[inner_product(a, b)]].concat();
+        let points = [&gens.g_vec[..], &gens.h_vec[..], std::slice::from_ref(u)].concat();
+        P::multi_scalar_mul(&scalars, &points)
+    }
+
+    crate::test_for_all_curves!(test_inner_product_arg);
+    fn test_inner_product_arg<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone,
+    {
+        for &n in &[1, 2, 8] {
+            let gens = Generators::<P>::new(n);
+            let u = P::hash_to_curve(b"inner product test u");
+            let a = random_vector(n);
+            let b = random_vector(n);
+            let P = statement(&gens, &u, &a, &b);
+
+            let proof = InnerProductArg::prove(&gens.g_vec, &gens.h_vec, &u, &P, &a, &b);
+            assert_eq!(proof.L.len(), n.trailing_zeros() as usize);
+            proof
+                .verify(&gens.g_vec, &gens.h_vec, &u, &P)
+                .expect("valid proof is rejected");
+        }
+    }
+
+    crate::test_for_all_curves!(test_inner_product_arg_rejects_wrong_statement);
+    fn test_inner_product_arg_rejects_wrong_statement<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone,
+    {
+        let n = 4;
+        let gens = Generators::<P>::new(n);
+        let u = P::hash_to_curve(b"inner product test u");
+        let a: Vec<P::Scalar> = random_vector(n);
+        let b: Vec<P::Scalar> = random_vector(n);
+        let P = statement(&gens, &u, &a, &b);
+        let proof = InnerProductArg::prove(&gens.g_vec, &gens.h_vec, &u, &P, &a, &b);
+
+        let wrong_P = P.clone() + u.clone();
+        assert!(proof
+            .verify(&gens.g_vec, &gens.h_vec, &u, &wrong_P)
+            .is_err());
+
+        let mut wrong_proof = proof.clone();
+        wrong_proof.a = add(&wrong_proof.a, &one());
+        assert!(wrong_proof
+            .verify(&gens.g_vec, &gens.h_vec, &u, &P)
+            .is_err());
+
+        let mut truncated_proof = proof;
+        truncated_proof.L.pop();
+        assert!(truncated_proof
+            .verify(&gens.g_vec, &gens.h_vec, &u, &P)
+            .is_err());
+    }
+
+    crate::test_for_all_curves!(test_generators_are_deterministic_and_distinct);
+    fn test_generators_are_deterministic_and_distinct<P>()
+    where
+        P: ECPoint + Clone + std::fmt::Debug,
+    {
+        let gens = Generators::<P>::new(4);
+        let same_gens = Generators::<P>::new(4);
+        assert!(gens.g_vec == same_gens.g_vec && gens.h_vec == same_gens.h_vec);
+
+        let other_gens = Generators::<P>::with_label(b"other label", 4);
+        let all: Vec<&P> = gens
+            .g_vec
+            .iter()
+            .chain(&gens.h_vec)
+            .chain(&other_gens.g_vec)
+            .collect();
+        let g: P = ECPoint::generator();
+        let h: P = ECPoint::base_point2();
+        for (i, p) in all.iter().enumerate() {
+            assert!(**p != g && **p != h);
+            assert!(all[i + 1..].iter().all(|q| q != p));
+        }
+    }
+}
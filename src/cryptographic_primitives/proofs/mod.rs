@@ -8,10 +8,17 @@
 use std::error::Error;
 use std::fmt;
 
+use crate::arithmetic::traits::*;
+use crate::elliptic::curves::traits::ECScalar;
+
+pub mod inner_product;
+pub mod range_proof;
 pub mod sigma_correct_homomorphic_elgamal_enc;
 pub mod sigma_correct_homomorphic_elgamal_encryption_of_dlog;
 pub mod sigma_dlog;
 pub mod sigma_ec_ddh;
+pub mod sigma_pedersen_dlog_equality;
+pub mod sigma_pedersen_linear_relation;
 pub mod sigma_valid_pedersen;
 pub mod sigma_valid_pedersen_blind;
 
@@ -29,3 +36,23 @@ impl Error for ProofError {
         "Error while verifying"
     }
 }
+
+/// Rejects a proof if any of its responses is zero
+///
+/// Honest responses are zero with negligible probability, while not every curve can represent
+/// the point at infinity that a zero response produces during verification.
+pub(crate) fn check_nonzero_responses<'a, S>(
+    responses: impl IntoIterator<Item = &'a S>,
+) -> Result<(), ProofError>
+where
+    S: ECScalar + 'a,
+{
+    if responses
+        .into_iter()
+        .any(|response| response.to_big_int().is_zero())
+    {
+        Err(ProofError)
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,460 @@
+#![allow(non_snake_case)]
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+use serde::{Deserialize, Serialize};
+
+use super::inner_product::*;
+use super::ProofError;
+use crate::arithmetic::traits::*;
+use crate::cryptographic_primitives::hashing::hash_sha256::HSha256;
+use crate::cryptographic_primitives::hashing::traits::Hash;
+use crate::elliptic::curves::traits::*;
+use crate::BigInt;
+
+/// Aggregated range proof from Bünz et al. 2018. Bulletproofs: Short Proofs for Confidential
+/// Transactions and More. In IEEE S&P 2018 (Section 4.3).
+///
+/// Proves that each of `m` Pedersen commitments `V_j = v_j*G + γ_j*H` (as constructed by
+/// [PedersenCommitment](crate::cryptographic_primitives::commitments::pedersen_commitment::PedersenCommitment),
+/// ie. `G` is [generator](ECPoint::generator) and `H` is [base_point2](ECPoint::base_point2))
+/// commits to a value in range `[0; 2^n)`. The proof consists of `2*log(n*m) + 4` points and
+/// 5 scalars, so aggregating several values is much cheaper than proving them separately.
+///
+/// Both `n` and `m` must be powers of two. Prover and verifier must use the same [Generators]
+/// of length at least `n*m`.
+///
+/// Verification is done by a single multi-scalar multiplication (see
+/// [ECPoint::multi_scalar_mul]).
+///
+/// ## Example
+/// ```
+/// # use curv::BigInt;
+/// use curv::cryptographic_primitives::commitments::pedersen_commitment::PedersenCommitment;
+/// use curv::cryptographic_primitives::commitments::traits::Commitment;
+/// use curv::cryptographic_primitives::proofs::inner_product::Generators;
+/// use curv::cryptographic_primitives::proofs::range_proof::RangeProof;
+/// use curv::elliptic::curves::secp256_k1::{FE, GE};
+/// use curv::elliptic::curves::traits::ECScalar;
+///
+/// let gens = Generators::<GE>::new(64);
+/// let value = BigInt::from(42);
+/// let blinding: FE = ECScalar::new_random();
+/// let commitment: GE = PedersenCommitment::create_commitment_with_user_defined_randomness(
+///     &value,
+///     &blinding.to_big_int(),
+/// );
+///
+/// let proof = RangeProof::prove(&gens, 64, &[ECScalar::from(&value)], &[blinding]);
+/// assert!(proof.verify(&gens, 64, &[commitment]).is_ok());
+/// ```
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct RangeProof<P: ECPoint> {
+    A: P,
+    S: P,
+    T1: P,
+    T2: P,
+    tau_x: P::Scalar,
+    mu: P::Scalar,
+    t_hat: P::Scalar,
+    inner_product_proof: InnerProductArg<P>,
+}
+
+impl<P> RangeProof<P>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Clone,
+{
+    /// Proves that every `values[j]` fits into `bit_length` bits, where `values[j]` and
+    /// `blindings[j]` open commitment `V_j`
+    ///
+    /// ## Panics
+    /// Panics if any value doesn't fit into `bit_length` bits, `values` and `blindings` have
+    /// different lengths, `bit_length` or number of values is not a power of two, or there are
+    /// not enough generators.
+    pub fn prove(
+        gens: &Generators<P>,
+        bit_length: usize,
+        values: &[P::Scalar],
+        blindings: &[P::Scalar],
+    ) -> Self {
+        let n = bit_length;
+        let m = values.len();
+        assert_eq!(m, blindings.len(), "every value must have a blinding");
+        assert!(
+            n.is_power_of_two() && m.is_power_of_two(),
+            "bit length and number of values must be powers of two"
+        );
+        assert!(gens.g_vec.len() >= n * m, "not enough generators");
+        let g_vec = &gens.g_vec[..n * m];
+        let h_vec = &gens.h_vec[..n * m];
+        let G: P = ECPoint::generator();
+        let H: P = ECPoint::base_point2();
+
+        let commitments: Vec<P> = values
+            .iter()
+            .zip(blindings)
+            .map(|(v, gamma)| {
+                P::multi_scalar_mul(&[v.clone(), gamma.clone()], &[G.clone(), H.clone()])
+            })
+            .collect();
+
+        // a_L are the bits of all values, a_R = a_L - 1
+        let a_L: Vec<P::Scalar> = values
+            .iter()
+            .flat_map(|v| {
+                let v = v.to_big_int();
+                assert!(v.bit_length() <= n, "value doesn't fit into {} bits", n);
+                (0..n).map(move |i| ECScalar::from(&BigInt::from(v.test_bit(i) as u32)))
+            })
+            .collect();
+        let a_R: Vec<P::Scalar> = a_L.iter().map(|a| sub(a, &one())).collect();
+
+        let alpha: P::Scalar = ECScalar::new_random();
+        let A = P::multi_scalar_mul(
+            &[&a_L[..], &a_R, std::slice::from_ref(&alpha)].concat(),
+            &[g_vec, h_vec, std::slice::from_ref(&H)].concat(),
+        );
+
+        let s_L: Vec<P::Scalar> = (0..n * m).map(|_| ECScalar::new_random()).collect();
+        let s_R: Vec<P::Scalar> = (0..n * m).map(|_| ECScalar::new_random()).collect();
+        let rho: P::Scalar = ECScalar::new_random();
+        let S = P::multi_scalar_mul(
+            &[&s_L[..], &s_R, std::slice::from_ref(&rho)].concat(),
+            &[g_vec, h_vec, std::slice::from_ref(&H)].concat(),
+        );
+
+        let seed = transcript_seed(n, &commitments);
+        let (y_hash, z_hash) = challenges_y_z(&seed, &A, &S);
+        let y: P::Scalar = ECScalar::from(&y_hash);
+        let z: P::Scalar = ECScalar::from(&z_hash);
+
+        // l(X) = (a_L - z*1) + s_L*X
+        // r(X) = y^nm ∘ (a_R + z*1 + s_R*X) + Σ_j z^(2+j) * (0^(jn) || 2^n || 0^((m-1-j)n))
+        let y_powers = powers(&y, n * m);
+        let z_powers = powers(&z, m + 2);
+        let two_powers = powers(&ECScalar::from(&BigInt::from(2)), n);
+        let l_0: Vec<P::Scalar> = a_L.iter().map(|a| sub(a, &z)).collect();
+        let l_1 = s_L;
+        let r_0: Vec<P::Scalar> = (0..n * m)
+            .map(|i| {
+                let z_2_pow = mul(&z_powers[2 + i / n], &two_powers[i % n]);
+                add(&mul(&y_powers[i], &add(&a_R[i], &z)), &z_2_pow)
+            })
+            .collect();
+        let r_1: Vec<P::Scalar> = y_powers
+            .iter()
+            .zip(&s_R)
+            .map(|(y_i, s)| mul(y_i, s))
+            .collect();
+
+        // t(X) = <l(X), r(X)> = t_0 + t_1*X + t_2*X^2
+        let t_1 = add(&inner_product(&l_0, &r_1), &inner_product(&l_1, &r_0));
+        let t_2 = inner_product(&l_1, &r_1);
+        let tau_1: P::Scalar = ECScalar::new_random();
+        let tau_2: P::Scalar = ECScalar::new_random();
+        let T1 = P::multi_scalar_mul(&[t_1, tau_1.clone()], &[G.clone(), H.clone()]);
+        let T2 = P::multi_scalar_mul(&[t_2, tau_2.clone()], &[G.clone(), H.clone()]);
+
+        let x_hash = hash_points(&z_hash, &[&T1, &T2]);
+        let x: P::Scalar = ECScalar::from(&x_hash);
+
+        let tau_x = blindings.iter().zip(&z_powers[2..]).fold(
+            add(&mul(&tau_2, &mul(&x, &x)), &mul(&tau_1, &x)),
+            |acc, (gamma, z_j)| add(&acc, &mul(z_j, gamma)),
+        );
+        let mu = add(&alpha, &mul(&rho, &x));
+        let l: Vec<P::Scalar> = l_0
+            .iter()
+            .zip(&l_1)
+            .map(|(l0, l1)| add(l0, &mul(l1, &x)))
+            .collect();
+        let r: Vec<P::Scalar> = r_0
+            .iter()
+            .zip(&r_1)
+            .map(|(r0, r1)| add(r0, &mul(r1, &x)))
+            .collect();
+        let t_hat = inner_product(&l, &r);
+
+        // Inner product argument is run for generators g and h' = y^-i * h, with u = w*G
+        let w_hash = HSha256::create_hash(&[
+            &x_hash,
+            &tau_x.to_big_int(),
+            &mu.to_big_int(),
+            &t_hat.to_big_int(),
+        ]);
+        let w: P::Scalar = ECScalar::from(&w_hash);
+        let u = G * w;
+        let y_inv_powers = powers(&y.invert(), n * m);
+        let h_prime: Vec<P> = h_vec
+            .iter()
+            .zip(&y_inv_powers)
+            .map(|(h_i, y_inv_i)| h_i.clone() * y_inv_i.clone())
+            .collect();
+        let inner_product_proof =
+            InnerProductArg::prove_with_seed(&w_hash, g_vec, &h_prime, &u, &l, &r);
+
+        RangeProof {
+            A,
+            S,
+            T1,
+            T2,
+            tau_x,
+            mu,
+            t_hat,
+            inner_product_proof,
+        }
+    }
+
+    /// Verifies that every commitment `V_j` opens to a value that fits into `bit_length` bits
+    pub fn verify(
+        &self,
+        gens: &Generators<P>,
+        bit_length: usize,
+        commitments: &[P],
+    ) -> Result<(), ProofError> {
+        let n = bit_length;
+        let m = commitments.len();
+        if !n.is_power_of_two() || !m.is_power_of_two() || gens.g_vec.len() < n * m {
+            return Err(ProofError);
+        }
+        let g_vec = &gens.g_vec[..n * m];
+        let h_vec = &gens.h_vec[..n * m];
+        let G: P = ECPoint::generator();
+        let H: P = ECPoint::base_point2();
+
+        let seed = transcript_seed(n, commitments);
+        let (y_hash, z_hash) = challenges_y_z(&seed, &self.A, &self.S);
+        let y: P::Scalar = ECScalar::from(&y_hash);
+        let z: P::Scalar = ECScalar::from(&z_hash);
+        let x_hash = hash_points(&z_hash, &[&self.T1, &self.T2]);
+        let x: P::Scalar = ECScalar::from(&x_hash);
+        let w_hash = HSha256::create_hash(&[
+            &x_hash,
+            &self.tau_x.to_big_int(),
+            &self.mu.to_big_int(),
+            &self.t_hat.to_big_int(),
+        ]);
+        let w: P::Scalar = ECScalar::from(&w_hash);
+        if y.to_big_int().is_zero() {
+            return Err(ProofError);
+        }
+
+        let ipa = &self.inner_product_proof;
+        let ipa_scalars = ipa.verification_scalars(&w_hash, n * m)?;
+
+        let y_powers = powers(&y, n * m);
+        let y_inv_powers = powers(&y.invert(), n * m);
+        let z_powers = powers(&z, m + 3);
+        let two_powers = powers(&ECScalar::from(&BigInt::from(2)), n);
+
+        // δ(y,z) = (z - z^2) * <1, y^nm> - Σ_j z^(3+j) * <1, 2^n>
+        let sum_y = y_powers
+            .iter()
+            .fold(P::Scalar::zero(), |acc, y_i| add(&acc, y_i));
+        let sum_2 = two_powers
+            .iter()
+            .fold(P::Scalar::zero(), |acc, p| add(&acc, p));
+        let delta = z_powers[3..]
+            .iter()
+            .fold(mul(&sub(&z, &z_powers[2]), &sum_y), |acc, z_j| {
+                sub(&acc, &mul(z_j, &sum_2))
+            });
+
+        // Two checks are combined with random weight c into a single multi-scalar multiplication:
+        //   t_hat*G + tau_x*H = Σ_j z^(2+j)*V_j + δ*G + x*T1 + x^2*T2
+        //   A + x*S - z*<1, g> + <z*y^nm + z^2*2^nm, h'> - mu*H + t_hat*u + Σ x_j^2*L_j + Σ x_j^-2*R_j
+        //     = a*<s, g> + b*<s^-1, h'> + a*b*u
+        // where h' = y^-i * h and u = w*G. Everything but A goes to the left side, -A to the right.
+        let c: P::Scalar = ECScalar::new_random();
+        let a = &ipa.a;
+        let b = &ipa.b;
+        let ab = mul(a, b);
+        let x_sq = mul(&x, &x);
+
+        let g_scalars = ipa_scalars
+            .s
+            .iter()
+            .map(|s_i| negate(&add(&z, &mul(a, s_i))));
+        let h_scalars = (0..n * m).map(|i| {
+            let z_2_pow = mul(&z_powers[2 + i / n], &two_powers[i % n]);
+            let b_s_inv = mul(b, &ipa_scalars.s_inv[i]);
+            add(&z, &mul(&y_inv_powers[i], &sub(&z_2_pow, &b_s_inv)))
+        });
+        let G_scalar = add(
+            &mul(&w, &sub(&self.t_hat, &ab)),
+            &mul(&c, &sub(&self.t_hat, &delta)),
+        );
+        let H_scalar = sub(&mul(&c, &self.tau_x), &self.mu);
+        let V_scalars = z_powers[2..2 + m].iter().map(|z_j| negate(&mul(&c, z_j)));
+
+        let scalars: Vec<P::Scalar> = g_scalars
+            .chain(h_scalars)
+            .chain(vec![G_scalar, H_scalar, x.clone()])
+            .chain(vec![negate(&mul(&c, &x)), negate(&mul(&c, &x_sq))])
+            .chain(V_scalars)
+            .chain(ipa_scalars.challenges_sq.iter().cloned())
+            .chain(ipa_scalars.challenges_inv_sq.iter().cloned())
+            .collect();
+        let points: Vec<P> = g_vec
+            .iter()
+            .chain(h_vec)
+            .chain(vec![&G, &H, &self.S, &self.T1, &self.T2])
+            .chain(commitments)
+            .chain(&ipa.L)
+            .chain(&ipa.R)
+            .cloned()
+            .collect();
+
+        let minus_A = self.A.clone() * negate(&one());
+        if P::vartime_multi_scalar_mul(&scalars, &points) == minus_A {
+            Ok(())
+        } else {
+            Err(ProofError)
+        }
+    }
+}
+
+/// Binds the proof to the statement: bit length and commitments
+fn transcript_seed<P: ECPoint>(bit_length: usize, commitments: &[P]) -> BigInt {
+    let bit_length = BigInt::from(bit_length as u64);
+    hash_points(&bit_length, &commitments.iter().collect::<Vec<_>>())
+}
+
+fn challenges_y_z<P: ECPoint>(seed: &BigInt, A: &P, S: &P) -> (BigInt, BigInt) {
+    let y = hash_points(seed, &[A, S]);
+    let z = HSha256::create_hash(&[&y]);
+    (y, z)
+}
+
+/// Returns `[1, x, x^2, ..., x^(n-1)]`
+fn powers<S: ECScalar + Clone>(x: &S, n: usize) -> Vec<S> {
+    std::iter::successors(Some(one::<S>()), |x_i| Some(mul(x_i, x)))
+        .take(n)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptographic_primitives::commitments::pedersen_commitment::PedersenCommitment;
+    use crate::cryptographic_primitives::commitments::traits::Commitment;
+
+    fn commit<P: ECPoint>(values: &[BigInt]) -> (Vec<P::Scalar>, Vec<P::Scalar>, Vec<P>) {
+        let blindings: Vec<P::Scalar> = values.iter().map(|_| ECScalar::new_random()).collect();
+        let commitments = values
+            .iter()
+            .zip(&blindings)
+            .map(|(v, gamma)| {
+                PedersenCommitment::<P>::create_commitment_with_user_defined_randomness(
+                    v,
+                    &gamma.to_big_int(),
+                )
+            })
+            .collect();
+        let values = values.iter().map(ECScalar::from).collect();
+        (values, blindings, commitments)
+    }
+
+    crate::test_for_all_curves!(test_range_proof);
+    fn test_range_proof<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone,
+    {
+        let n = 8;
+        let gens = Generators::<P>::new(n * 4);
+        for values in &[
+            vec![BigInt::from(0)],
+            vec![BigInt::from(255), BigInt::from(1)],
+            vec![
+                BigInt::from(17),
+                BigInt::from(0),
+                BigInt::from(128),
+                BigInt::from(254),
+            ],
+        ] {
+            let (values, blindings, commitments) = commit::<P>(values);
+            let proof = RangeProof::prove(&gens, n, &values, &blindings);
+            proof
+                .verify(&gens, n, &commitments)
+                .expect("valid proof is rejected");
+        }
+    }
+
+    crate::test_for_all_curves!(test_range_proof_64_bits);
+    fn test_range_proof_64_bits<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone,
+    {
+        let gens = Generators::<P>::new(64);
+        let (values, blindings, commitments) = commit::<P>(&[BigInt::from(u64::MAX)]);
+        let proof = RangeProof::prove(&gens, 64, &values, &blindings);
+        proof
+            .verify(&gens, 64, &commitments)
+            .expect("valid proof is rejected");
+    }
+
+    crate::test_for_all_curves!(test_range_proof_rejects_wrong_statement);
+    fn test_range_proof_rejects_wrong_statement<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone,
+    {
+        let n = 8;
+        let gens = Generators::<P>::new(2 * n);
+        let (values, blindings, commitments) = commit::<P>(&[BigInt::from(3), BigInt::from(200)]);
+        let proof = RangeProof::prove(&gens, n, &values, &blindings);
+
+        let mut swapped = commitments.clone();
+        swapped.swap(0, 1);
+        assert!(proof.verify(&gens, n, &swapped).is_err());
+        assert!(proof.verify(&gens, 2 * n, &commitments).is_err());
+        assert!(proof.verify(&gens, n, &commitments[..1]).is_err());
+
+        let (_, _, other_commitments) = commit::<P>(&[BigInt::from(3), BigInt::from(200)]);
+        assert!(proof.verify(&gens, n, &other_commitments).is_err());
+
+        let mut wrong_proof = proof;
+        wrong_proof.t_hat = add(&wrong_proof.t_hat, &one());
+        assert!(wrong_proof.verify(&gens, n, &commitments).is_err());
+    }
+
+    crate::test_for_all_curves!(test_range_proof_rejects_value_out_of_range);
+    fn test_range_proof_rejects_value_out_of_range<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone,
+    {
+        // Prover refuses to prove a value out of range, so take a proof of 8-bit value and check
+        // it against commitment to 256 with the same blinding
+        let n = 8;
+        let gens = Generators::<P>::new(n);
+        let (values, blindings, _) = commit::<P>(&[BigInt::from(255)]);
+        let proof = RangeProof::prove(&gens, n, &values, &blindings);
+        let out_of_range = PedersenCommitment::<P>::create_commitment_with_user_defined_randomness(
+            &BigInt::from(256),
+            &blindings[0].to_big_int(),
+        );
+        assert!(proof.verify(&gens, n, &[out_of_range]).is_err());
+    }
+
+    crate::test_for_all_curves!(
+        #[should_panic]
+        test_prover_panics_on_value_out_of_range
+    );
+    fn test_prover_panics_on_value_out_of_range<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone,
+    {
+        let gens = Generators::<P>::new(8);
+        let (values, blindings, _) = commit::<P>(&[BigInt::from(256)]);
+        RangeProof::prove(&gens, 8, &values, &blindings);
+    }
+}
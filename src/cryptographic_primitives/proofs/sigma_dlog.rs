@@ -10,6 +10,9 @@ use zeroize::Zeroize;
 
 use crate::cryptographic_primitives::hashing::hash_sha256::HSha256;
 use crate::cryptographic_primitives::hashing::traits::Hash;
+use crate::elliptic::curves::dynamic::{
+    for_each_curve, impl_curve_tagged_serde, CurveId, DynPoint, DynScalar,
+};
 use crate::elliptic::curves::traits::*;
 
 use super::ProofError;
@@ -40,9 +43,8 @@ where
         let base_point: P = ECPoint::generator();
         let generator_x = base_point.bytes_compressed_to_big_int();
         let mut sk_t_rand_commitment: P::Scalar = ECScalar::new_random();
-        let pk_t_rand_commitment = base_point.scalar_mul(&sk_t_rand_commitment.get_element());
-        let ec_point: P = ECPoint::generator();
-        let pk = ec_point.scalar_mul(&sk.get_element());
+        let pk_t_rand_commitment = P::mul_generator(&sk_t_rand_commitment);
+        let pk = P::mul_generator(sk);
         let challenge = HSha256::create_hash(&[
             &pk_t_rand_commitment.bytes_compressed_to_big_int(),
             &generator_x,
@@ -71,9 +73,7 @@ where
         let pk = proof.pk.clone();
         let pk_challenge = pk.scalar_mul(&sk_challenge.get_element());
 
-        let base_point: P = ECPoint::generator();
-
-        let mut pk_verifier = base_point.scalar_mul(&proof.challenge_response.get_element());
+        let mut pk_verifier = P::mul_generator(&proof.challenge_response);
 
         pk_verifier = pk_verifier.add_point(&pk_challenge.get_element());
 
@@ -85,6 +85,49 @@ where
     }
 }
 
+macro_rules! impl_dyn_dlog_proof {
+    ($($variant:ident => $name:literal, $point:ty;)*) => {
+        /// [DLogProof] over the curve chosen at runtime, serialized along with the curve name
+        #[derive(Clone, PartialEq, Debug)]
+        pub enum DynDLogProof {
+            $(
+                $variant(DLogProof<$point>),
+            )*
+        }
+
+        impl_curve_tagged_serde!(DynDLogProof, "proof"; $($variant => DLogProof<$point>;)*);
+
+        impl DynDLogProof {
+            /// Proves knowledge of `sk`, the proof is over the curve of `sk`
+            pub fn prove(sk: &DynScalar) -> DynDLogProof {
+                match sk {
+                    $(DynScalar::$variant(sk) => DynDLogProof::$variant(DLogProof::prove(sk)),)*
+                }
+            }
+
+            pub fn verify(&self) -> Result<(), ProofError> {
+                match self {
+                    $(DynDLogProof::$variant(proof) => DLogProof::verify(proof),)*
+                }
+            }
+
+            pub fn curve(&self) -> CurveId {
+                match self {
+                    $(DynDLogProof::$variant(_) => CurveId::$variant,)*
+                }
+            }
+
+            pub fn public_key(&self) -> DynPoint {
+                match self {
+                    $(DynDLogProof::$variant(proof) => DynPoint::$variant(proof.pk.clone()),)*
+                }
+            }
+        }
+    };
+}
+
+for_each_curve!(impl_dyn_dlog_proof);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,4 +142,40 @@ mod tests {
         let dlog_proof = DLogProof::<P>::prove(&witness);
         assert!(DLogProof::verify(&dlog_proof).is_ok());
     }
+
+    #[test]
+    fn test_dyn_dlog_proof() {
+        for curve in CurveId::ALL.iter().copied() {
+            let witness = DynScalar::new_random(curve);
+            let proof = DynDLogProof::prove(&witness);
+            assert_eq!(proof.curve(), curve);
+            assert_eq!(
+                proof.public_key(),
+                DynPoint::generator(curve).try_mul(&witness).unwrap()
+            );
+
+            let json = serde_json::to_string(&proof).unwrap();
+            let proof = serde_json::from_str::<DynDLogProof>(&json).unwrap();
+            let encoded = bincode::serialize(&proof).unwrap();
+            assert_eq!(
+                bincode::deserialize::<DynDLogProof>(&encoded).unwrap(),
+                proof
+            );
+            assert!(proof.verify().is_ok(), "{}", curve);
+        }
+    }
+
+    #[test]
+    fn test_dyn_dlog_proof_of_another_curve_is_rejected() {
+        let proof = DynDLogProof::prove(&DynScalar::new_random(CurveId::P256));
+        let mut relabeled = serde_json::to_value(&proof).unwrap();
+        relabeled["curve"] = "secp256k1".into();
+        // P-256 points are serialized as compressed hex strings, secp256k1 points as {x, y}
+        let err = serde_json::from_value::<DynDLogProof>(relabeled).unwrap_err();
+        assert!(
+            err.to_string().contains("expected Secp256k1Point"),
+            "{}",
+            err
+        );
+    }
 }
@@ -0,0 +1,227 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use super::{check_nonzero_responses, ProofError};
+use crate::cryptographic_primitives::hashing::hash_sha256::HSha256;
+use crate::cryptographic_primitives::hashing::traits::Hash;
+use crate::elliptic::curves::traits::*;
+
+/// Protocol for proving that a Pedersen commitment and a discrete log share the same value, e.g.
+/// that a committed value equals a Feldman share or a secret key.
+/// The statement is (C, G', Y), the witness is (m, r). The relation outputs 1 if :
+/// C = mG + rH, Y = mG'
+/// where G, H are the Pedersen commitment bases and G' is an arbitrary base (G by default).
+/// The protocol:
+/// 1: Prover chooses A1 = s1*G + s2*H, A2 = s1*G' for random s1,s2
+/// prover calculates challenge e = H(G,H,G',C,Y,A1,A2)
+/// prover calculates z1 = s1 + em, z2 = s2 + er
+/// prover sends pi = {A1, A2, z1, z2}
+///
+/// verifier checks that z1*G + z2*H = A1 + eC, z1*G' = A2 + eY
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PedersenDLogEqualityProof<P: ECPoint> {
+    pub a1: P,
+    pub a2: P,
+    pub z1: P::Scalar,
+    pub z2: P::Scalar,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PedersenDLogEqualityStatement<P: ECPoint> {
+    pub commitment: P,
+    pub g: P,
+    pub y: P,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PedersenDLogEqualityWitness<S: ECScalar> {
+    pub m: S,
+    pub r: S,
+}
+
+impl<P: ECPoint> PedersenDLogEqualityStatement<P> {
+    /// Statement `C = mG + rH, Y = mG` where `G` is the curve generator
+    pub fn new(commitment: P, y: P) -> Self {
+        PedersenDLogEqualityStatement {
+            commitment,
+            g: P::generator(),
+            y,
+        }
+    }
+}
+
+impl<P> PedersenDLogEqualityProof<P>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Zeroize + Clone,
+{
+    pub fn prove(
+        w: &PedersenDLogEqualityWitness<P::Scalar>,
+        delta: &PedersenDLogEqualityStatement<P>,
+    ) -> PedersenDLogEqualityProof<P> {
+        let g: P = ECPoint::generator();
+        let h: P = ECPoint::base_point2();
+        let mut s1: P::Scalar = ECScalar::new_random();
+        let mut s2: P::Scalar = ECScalar::new_random();
+        let a1 = P::multi_scalar_mul(&[s1.clone(), s2.clone()], &[g.clone(), h.clone()]);
+        let a2 = delta.g.clone() * s1.clone();
+        let e = HSha256::create_hash_from_ge(&[
+            &g,
+            &h,
+            &delta.g,
+            &delta.commitment,
+            &delta.y,
+            &a1,
+            &a2,
+        ]);
+        let z1 = s1.clone() + e.clone() * w.m.clone();
+        let z2 = s2.clone() + e * w.r.clone();
+        s1.zeroize();
+        s2.zeroize();
+        PedersenDLogEqualityProof { a1, a2, z1, z2 }
+    }
+
+    pub fn verify(&self, delta: &PedersenDLogEqualityStatement<P>) -> Result<(), ProofError> {
+        check_nonzero_responses([&self.z1, &self.z2])?;
+        let g: P = ECPoint::generator();
+        let h: P = ECPoint::base_point2();
+        let e = HSha256::create_hash_from_ge(&[
+            &g,
+            &h,
+            &delta.g,
+            &delta.commitment,
+            &delta.y,
+            &self.a1,
+            &self.a2,
+        ]);
+        let z1_g_plus_z2_h =
+            P::vartime_multi_scalar_mul(&[self.z1.clone(), self.z2.clone()], &[g, h]);
+        let z1_g_prime = delta.g.clone() * self.z1.clone();
+        let a1_plus_e_c = self.a1.clone() + delta.commitment.clone() * e.clone();
+        let a2_plus_e_y = self.a2.clone() + delta.y.clone() * e;
+        if z1_g_plus_z2_h == a1_plus_e_c && z1_g_prime == a2_plus_e_y {
+            Ok(())
+        } else {
+            Err(ProofError)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptographic_primitives::commitments::pedersen_commitment::PedersenCommitment;
+    use crate::cryptographic_primitives::commitments::traits::Commitment;
+    use crate::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+    use crate::test_for_all_curves;
+
+    fn commit<P: ECPoint>(m: &P::Scalar, r: &P::Scalar) -> P {
+        PedersenCommitment::create_commitment_with_user_defined_randomness(
+            &m.to_big_int(),
+            &r.to_big_int(),
+        )
+    }
+
+    test_for_all_curves!(test_pedersen_dlog_equality_proof);
+    fn test_pedersen_dlog_equality_proof<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Zeroize + Clone,
+    {
+        let m: P::Scalar = ECScalar::new_random();
+        let r: P::Scalar = ECScalar::new_random();
+        let commitment: P = commit(&m, &r);
+        let y = P::generator() * m.clone();
+        let delta = PedersenDLogEqualityStatement::new(commitment, y);
+        let w = PedersenDLogEqualityWitness { m, r };
+        let proof = PedersenDLogEqualityProof::prove(&w, &delta);
+        assert!(proof.verify(&delta).is_ok());
+    }
+
+    test_for_all_curves!(test_pedersen_dlog_equality_proof_different_base);
+    fn test_pedersen_dlog_equality_proof_different_base<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Zeroize + Clone,
+    {
+        let m: P::Scalar = ECScalar::new_random();
+        let r: P::Scalar = ECScalar::new_random();
+        let g = P::hash_to_curve(b"pedersen dlog equality test base");
+        let delta = PedersenDLogEqualityStatement {
+            commitment: commit(&m, &r),
+            y: g.clone() * m.clone(),
+            g,
+        };
+        let w = PedersenDLogEqualityWitness { m, r };
+        let proof = PedersenDLogEqualityProof::prove(&w, &delta);
+        assert!(proof.verify(&delta).is_ok());
+    }
+
+    test_for_all_curves!(test_pedersen_dlog_equality_proof_for_feldman_share);
+    fn test_pedersen_dlog_equality_proof_for_feldman_share<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Zeroize + Clone,
+    {
+        let secret: P::Scalar = ECScalar::new_random();
+        let (vss, shares) = VerifiableSS::<P>::share(1, 3, &secret);
+        let share = shares[1].clone();
+        let r: P::Scalar = ECScalar::new_random();
+        let delta =
+            PedersenDLogEqualityStatement::new(commit(&share, &r), vss.get_point_commitment(2));
+        let w = PedersenDLogEqualityWitness { m: share, r };
+        let proof = PedersenDLogEqualityProof::prove(&w, &delta);
+        assert!(proof.verify(&delta).is_ok());
+    }
+
+    test_for_all_curves!(test_pedersen_dlog_equality_proof_rejects_zero_responses);
+    fn test_pedersen_dlog_equality_proof_rejects_zero_responses<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Zeroize + Clone,
+    {
+        let m: P::Scalar = ECScalar::new_random();
+        let r: P::Scalar = ECScalar::new_random();
+        let delta = PedersenDLogEqualityStatement::new(commit(&m, &r), P::generator() * m.clone());
+        let w = PedersenDLogEqualityWitness { m, r };
+        let proof = PedersenDLogEqualityProof::prove(&w, &delta);
+
+        let mut zero = proof.clone();
+        zero.z1 = P::Scalar::zero();
+        zero.z2 = P::Scalar::zero();
+        assert!(zero.verify(&delta).is_err());
+        let mut zero_z1 = proof.clone();
+        zero_z1.z1 = P::Scalar::zero();
+        assert!(zero_z1.verify(&delta).is_err());
+        let mut zero_z2 = proof;
+        zero_z2.z2 = P::Scalar::zero();
+        assert!(zero_z2.verify(&delta).is_err());
+    }
+
+    test_for_all_curves!(test_pedersen_dlog_equality_proof_rejects_different_values);
+    fn test_pedersen_dlog_equality_proof_rejects_different_values<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Zeroize + Clone,
+    {
+        let m: P::Scalar = ECScalar::new_random();
+        let m2: P::Scalar = ECScalar::new_random();
+        let r: P::Scalar = ECScalar::new_random();
+        let delta = PedersenDLogEqualityStatement::new(commit(&m, &r), P::generator() * m2);
+        let w = PedersenDLogEqualityWitness { m, r };
+        let proof = PedersenDLogEqualityProof::prove(&w, &delta);
+        assert!(proof.verify(&delta).is_err());
+
+        let mut other = delta.clone();
+        other.g = P::base_point2();
+        let proof = PedersenDLogEqualityProof::prove(&w, &delta);
+        assert!(proof.verify(&other).is_err());
+    }
+}
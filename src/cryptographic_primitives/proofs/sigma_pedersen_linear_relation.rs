@@ -0,0 +1,270 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use super::inner_product::{add, inner_product, mul, negate};
+use super::{check_nonzero_responses, ProofError};
+use crate::arithmetic::traits::*;
+use crate::cryptographic_primitives::hashing::hash_sha256::HSha256;
+use crate::cryptographic_primitives::hashing::traits::Hash;
+use crate::elliptic::curves::traits::*;
+use crate::BigInt;
+
+/// Protocol for proving knowledge of openings of several Pedersen commitments whose committed
+/// values satisfy a public linear relation.
+/// The statement is (C_1..C_n, a_1..a_n, b), the witness is (m_1..m_n, r_1..r_n). The relation
+/// outputs 1 if :
+/// C_i = m_i*G + r_i*H for every i, and a_1*m_1 + ... + a_n*m_n = b
+/// The protocol:
+/// 1: Prover chooses random s_i, t_i such that a_1*s_1 + ... + a_n*s_n = 0,
+///    and computes A_i = s_i*G + t_i*H
+/// prover calculates challenge e = H(G,H,C_1..C_n,a_1..a_n,b,A_1..A_n)
+/// prover calculates z_i = s_i + e*m_i, w_i = t_i + e*r_i
+/// prover sends pi = {A_1..A_n, z_1..z_n, w_1..w_n}
+///
+/// verifier checks that z_i*G + w_i*H = A_i + e*C_i for every i, and a_1*z_1 + ... + a_n*z_n = e*b
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(bound(serialize = "P: Serialize, P::Scalar: Serialize"))]
+#[serde(bound(deserialize = "P: Deserialize<'de>, P::Scalar: Deserialize<'de>"))]
+pub struct PedersenLinearRelationProof<P: ECPoint> {
+    pub a: Vec<P>,
+    pub z: Vec<P::Scalar>,
+    pub w: Vec<P::Scalar>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PedersenLinearRelationStatement<P: ECPoint> {
+    pub commitments: Vec<P>,
+    pub coefficients: Vec<P::Scalar>,
+    pub b: P::Scalar,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PedersenLinearRelationWitness<S: ECScalar> {
+    pub m: Vec<S>,
+    pub r: Vec<S>,
+}
+
+impl<P> PedersenLinearRelationProof<P>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Zeroize + Clone,
+{
+    /// ## Panics
+    /// Panics if the witness and the statement have different lengths
+    pub fn prove(
+        w: &PedersenLinearRelationWitness<P::Scalar>,
+        delta: &PedersenLinearRelationStatement<P>,
+    ) -> PedersenLinearRelationProof<P> {
+        let n = delta.commitments.len();
+        assert_eq!(delta.coefficients.len(), n);
+        assert_eq!(w.m.len(), n);
+        assert_eq!(w.r.len(), n);
+
+        let g: P = ECPoint::generator();
+        let h: P = ECPoint::base_point2();
+        let mut s: Vec<P::Scalar> = (0..n).map(|_| ECScalar::new_random()).collect();
+        let mut t: Vec<P::Scalar> = (0..n).map(|_| ECScalar::new_random()).collect();
+        // fix the last mask with non-zero coefficient so that masks satisfy the homogeneous relation
+        if let Some(k) = delta
+            .coefficients
+            .iter()
+            .rposition(|a_k| !a_k.to_big_int().is_zero())
+        {
+            s[k] = P::Scalar::zero();
+            let rest = inner_product(&delta.coefficients, &s);
+            s[k] = mul(&negate(&rest), &delta.coefficients[k].invert());
+        }
+
+        let a: Vec<P> = s
+            .iter()
+            .zip(&t)
+            .map(|(s_i, t_i)| {
+                P::multi_scalar_mul(&[s_i.clone(), t_i.clone()], &[g.clone(), h.clone()])
+            })
+            .collect();
+        let e = challenge(delta, &a);
+        let z = s
+            .iter()
+            .zip(&w.m)
+            .map(|(s_i, m_i)| add(s_i, &mul(&e, m_i)))
+            .collect();
+        let w = t
+            .iter()
+            .zip(&w.r)
+            .map(|(t_i, r_i)| add(t_i, &mul(&e, r_i)))
+            .collect();
+        s.iter_mut().for_each(|s_i| s_i.zeroize());
+        t.iter_mut().for_each(|t_i| t_i.zeroize());
+        PedersenLinearRelationProof { a, z, w }
+    }
+
+    pub fn verify(&self, delta: &PedersenLinearRelationStatement<P>) -> Result<(), ProofError> {
+        let n = delta.commitments.len();
+        if delta.coefficients.len() != n
+            || self.a.len() != n
+            || self.z.len() != n
+            || self.w.len() != n
+        {
+            return Err(ProofError);
+        }
+        check_nonzero_responses(self.z.iter().chain(&self.w))?;
+
+        let g: P = ECPoint::generator();
+        let h: P = ECPoint::base_point2();
+        let e = challenge(delta, &self.a);
+        for i in 0..n {
+            let z_g_plus_w_h = P::vartime_multi_scalar_mul(
+                &[self.z[i].clone(), self.w[i].clone()],
+                &[g.clone(), h.clone()],
+            );
+            let a_plus_e_c = self.a[i].clone() + delta.commitments[i].clone() * e.clone();
+            if z_g_plus_w_h != a_plus_e_c {
+                return Err(ProofError);
+            }
+        }
+        let a_z = inner_product(&delta.coefficients, &self.z);
+        if a_z.to_big_int() == mul(&e, &delta.b).to_big_int() {
+            Ok(())
+        } else {
+            Err(ProofError)
+        }
+    }
+}
+
+fn challenge<P: ECPoint>(delta: &PedersenLinearRelationStatement<P>, a: &[P]) -> P::Scalar {
+    let g: P = ECPoint::generator();
+    let h: P = ECPoint::base_point2();
+    let input: Vec<BigInt> = [g, h]
+        .iter()
+        .chain(&delta.commitments)
+        .map(|p| p.bytes_compressed_to_big_int())
+        .chain(delta.coefficients.iter().map(|a_i| a_i.to_big_int()))
+        .chain(std::iter::once(delta.b.to_big_int()))
+        .chain(a.iter().map(|a_i| a_i.bytes_compressed_to_big_int()))
+        .collect();
+    let input: Vec<&BigInt> = input.iter().collect();
+    ECScalar::from(&HSha256::create_hash(&input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptographic_primitives::commitments::pedersen_commitment::PedersenCommitment;
+    use crate::cryptographic_primitives::commitments::traits::Commitment;
+    use crate::test_for_all_curves;
+
+    fn setup<P>(
+        coefficients: Vec<P::Scalar>,
+        m: Vec<P::Scalar>,
+    ) -> (
+        PedersenLinearRelationStatement<P>,
+        PedersenLinearRelationWitness<P::Scalar>,
+    )
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone,
+    {
+        let r: Vec<P::Scalar> = m.iter().map(|_| ECScalar::new_random()).collect();
+        let commitments = m
+            .iter()
+            .zip(&r)
+            .map(|(m_i, r_i)| {
+                PedersenCommitment::create_commitment_with_user_defined_randomness(
+                    &m_i.to_big_int(),
+                    &r_i.to_big_int(),
+                )
+            })
+            .collect();
+        let b = inner_product(&coefficients, &m);
+        let delta = PedersenLinearRelationStatement {
+            commitments,
+            coefficients,
+            b,
+        };
+        (delta, PedersenLinearRelationWitness { m, r })
+    }
+
+    fn scalar<S: ECScalar>(n: u32) -> S {
+        S::from(&BigInt::from(n))
+    }
+
+    test_for_all_curves!(test_pedersen_linear_relation_proof);
+    fn test_pedersen_linear_relation_proof<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Zeroize + Clone,
+    {
+        let coefficients = (0..4).map(|_| ECScalar::new_random()).collect();
+        let m = (0..4).map(|_| ECScalar::new_random()).collect();
+        let (delta, w) = setup::<P>(coefficients, m);
+        let proof = PedersenLinearRelationProof::prove(&w, &delta);
+        assert!(proof.verify(&delta).is_ok());
+    }
+
+    test_for_all_curves!(test_pedersen_linear_relation_proof_of_sum);
+    fn test_pedersen_linear_relation_proof_of_sum<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Zeroize + Clone,
+    {
+        // m_1 + m_2 - m_3 = 0, with a zero coefficient for the last commitment
+        let coefficients = vec![scalar(1), scalar(1), negate(&scalar(1)), P::Scalar::zero()];
+        let m = vec![scalar(3), scalar(4), scalar(7), scalar(100)];
+        let (delta, w) = setup::<P>(coefficients, m);
+        assert!(delta.b.to_big_int().is_zero());
+        let proof = PedersenLinearRelationProof::prove(&w, &delta);
+        assert!(proof.verify(&delta).is_ok());
+    }
+
+    test_for_all_curves!(test_pedersen_linear_relation_proof_rejects_wrong_relation);
+    fn test_pedersen_linear_relation_proof_rejects_wrong_relation<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Zeroize + Clone,
+    {
+        let coefficients = vec![scalar(2), scalar(3)];
+        let m = vec![scalar(5), scalar(6)];
+        let (mut delta, w) = setup::<P>(coefficients, m);
+        delta.b = scalar(29);
+        let proof = PedersenLinearRelationProof::prove(&w, &delta);
+        assert!(proof.verify(&delta).is_err());
+
+        delta.b = scalar(28);
+        let proof = PedersenLinearRelationProof::prove(&w, &delta);
+        assert!(proof.verify(&delta).is_ok());
+        let mut other = delta.clone();
+        other.commitments.swap(0, 1);
+        assert!(proof.verify(&other).is_err());
+        let mut truncated = proof.clone();
+        truncated.z.pop();
+        assert!(truncated.verify(&delta).is_err());
+    }
+
+    test_for_all_curves!(test_pedersen_linear_relation_proof_rejects_zero_responses);
+    fn test_pedersen_linear_relation_proof_rejects_zero_responses<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Zeroize + Clone,
+    {
+        let coefficients = vec![scalar(2), scalar(3)];
+        let m = vec![scalar(5), scalar(6)];
+        let (delta, w) = setup::<P>(coefficients, m);
+        let proof = PedersenLinearRelationProof::prove(&w, &delta);
+
+        let mut zero = proof.clone();
+        zero.z = vec![P::Scalar::zero(), P::Scalar::zero()];
+        zero.w = vec![P::Scalar::zero(), P::Scalar::zero()];
+        assert!(zero.verify(&delta).is_err());
+        let mut zero_pair = proof;
+        zero_pair.z[1] = P::Scalar::zero();
+        zero_pair.w[1] = P::Scalar::zero();
+        assert!(zero_pair.verify(&delta).is_err());
+    }
+}
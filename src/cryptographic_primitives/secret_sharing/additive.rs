@@ -0,0 +1,357 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+use serde::{Deserialize, Serialize};
+
+use super::feldman_vss::{ShamirSecretSharing, VerifiableSS};
+use super::polynomial::Polynomial;
+use crate::elliptic::curves::traits::*;
+use crate::BigInt;
+use crate::ErrorSS::{self, DuplicatePartyError, InvalidPartyError, TooFewPartiesError};
+
+/// n-out-of-n additive secret sharing: the secret is the sum of all `n` shares
+///
+/// Parties are indexed `0..n`, the same way as [VerifiableSS::reconstruct] indexes them.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct AdditiveSecretSharing {
+    pub share_count: usize, //n
+}
+
+/// Additive share held by party `index`
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct AdditiveShare<S> {
+    pub index: usize,
+    pub value: S,
+}
+
+impl AdditiveSecretSharing {
+    // first n-1 shares are random, the last one is fixed by the secret
+    pub fn share<S>(&self, secret: &S) -> Vec<AdditiveShare<S>>
+    where
+        S: ECScalar + Clone,
+    {
+        assert!(self.share_count > 0);
+        let mut values: Vec<S> = (1..self.share_count).map(|_| S::new_random()).collect();
+        let last = values
+            .iter()
+            .fold(secret.clone(), |acc, v| acc.sub(&v.get_element()));
+        values.push(last);
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| AdditiveShare { index, value })
+            .collect()
+    }
+
+    pub fn reconstruct<S>(&self, shares: &[AdditiveShare<S>]) -> S
+    where
+        S: ECScalar + Clone,
+    {
+        assert_eq!(shares.len(), self.share_count);
+        let mut indices: Vec<usize> = shares.iter().map(|s| s.index).collect();
+        indices.sort_unstable();
+        assert!(
+            indices.iter().enumerate().all(|(i, index)| i == *index),
+            "every party must contribute exactly one share"
+        );
+        shares
+            .iter()
+            .fold(S::zero(), |acc, share| acc + share.value.clone())
+    }
+
+    // Combines resharings of every additive share (see AdditiveShare::reshare) into a single
+    // Shamir sharing of the same secret. `dealings[j]` and `received[j]` are the commitments
+    // and the share that the receiving party got from party j.
+    pub fn combine_reshared<P>(
+        dealings: &[VerifiableSS<P>],
+        received: &[P::Scalar],
+    ) -> (VerifiableSS<P>, P::Scalar)
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone,
+    {
+        assert_eq!(dealings.len(), received.len());
+        assert!(!dealings.is_empty());
+        let parameters = dealings[0].parameters.clone();
+        assert!(dealings.iter().all(|d| d.parameters == parameters));
+
+        let commitments = (0..=parameters.threshold)
+            .map(|k| {
+                let mut terms = dealings.iter().map(|d| d.commitments[k].clone());
+                let head = terms.next().unwrap();
+                terms.fold(head, |acc, c| acc + c)
+            })
+            .collect();
+        let share = received
+            .iter()
+            .fold(P::Scalar::zero(), |acc, s| acc + s.clone());
+        (
+            VerifiableSS {
+                parameters,
+                commitments,
+            },
+            share,
+        )
+    }
+}
+
+impl<S> AdditiveShare<S>
+where
+    S: ECScalar + Clone,
+{
+    pub fn add(&self, other: &Self) -> Self {
+        assert_eq!(self.index, other.index);
+        AdditiveShare {
+            index: self.index,
+            value: self.value.clone() + other.value.clone(),
+        }
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        assert_eq!(self.index, other.index);
+        AdditiveShare {
+            index: self.index,
+            value: self.value.sub(&other.value.get_element()),
+        }
+    }
+
+    pub fn mul_by_constant(&self, c: &S) -> Self {
+        AdditiveShare {
+            index: self.index,
+            value: self.value.clone() * c.clone(),
+        }
+    }
+
+    // the constant is added only once, by party 0
+    pub fn add_constant(&self, c: &S) -> Self {
+        let value = if self.index == 0 {
+            self.value.clone() + c.clone()
+        } else {
+            self.value.clone()
+        };
+        AdditiveShare {
+            index: self.index,
+            value,
+        }
+    }
+
+    /// Locally converts Shamir share of party `index` into an additive share among `parties`
+    ///
+    /// `parties` are the indices of at least `t+1` parties taking part in the conversion; the
+    /// resulting share has index equal to position of `index` in `parties`. Shamir share is
+    /// multiplied by Lagrange coefficient `λ_index(0)` (see [VerifiableSS::map_share_to_new_params]).
+    /// Returns an error if `parties` lists some party more than once, has less than `t+1` parties,
+    /// or doesn't fit into the sharing parameters.
+    pub fn from_shamir(
+        params: &ShamirSecretSharing,
+        index: usize,
+        share: &S,
+        parties: &[usize],
+    ) -> Result<Self, ErrorSS> {
+        let position = check_parties(parties, index, params.share_count)?;
+        if parties.len() <= params.threshold {
+            return Err(TooFewPartiesError);
+        }
+        let lambda = Polynomial::lagrange_basis(&S::zero(), position, &party_points(parties));
+        Ok(AdditiveShare {
+            index: position,
+            value: share.clone() * lambda,
+        })
+    }
+
+    /// Locally converts additive share into a share of `(n-1, n)` Shamir sharing of the same
+    /// secret, where `n` is `share_count`
+    ///
+    /// This is the inverse of [from_shamir](Self::from_shamir) with all `n` parties: share is
+    /// divided by Lagrange coefficient `λ_index(0)`. Use [reshare](Self::reshare) to get a
+    /// sharing with lower threshold.
+    pub fn to_shamir(&self, share_count: usize) -> (ShamirSecretSharing, S) {
+        assert!(self.index < share_count);
+        let parties: Vec<usize> = (0..share_count).collect();
+        let lambda = Polynomial::lagrange_basis(&S::zero(), self.index, &party_points(&parties));
+        (
+            ShamirSecretSharing {
+                threshold: share_count - 1,
+                share_count,
+            },
+            self.value.clone() * lambda.invert(),
+        )
+    }
+
+    /// Deals Feldman VSS of the additive share to `n` parties with threshold `t`
+    ///
+    /// Once every party has done the same, each receiver obtains its share of the sum with
+    /// [AdditiveSecretSharing::combine_reshared].
+    pub fn reshare<P>(&self, t: usize, n: usize) -> (VerifiableSS<P>, Vec<S>)
+    where
+        P: ECPoint<Scalar = S> + Clone,
+    {
+        VerifiableSS::share(t, n, &self.value)
+    }
+}
+
+/// Checks that `parties` are distinct indices below `share_count` and include `index`, returns
+/// position of `index` in `parties`
+pub(crate) fn check_parties(
+    parties: &[usize],
+    index: usize,
+    share_count: usize,
+) -> Result<usize, ErrorSS> {
+    check_distinct(parties)?;
+    if parties.iter().any(|p| *p >= share_count) {
+        return Err(InvalidPartyError);
+    }
+    parties
+        .iter()
+        .position(|p| *p == index)
+        .ok_or(InvalidPartyError)
+}
+
+/// The same party listed twice would get two Lagrange coefficients (or a zero denominator in
+/// them), so its shares wouldn't add up to the secret
+pub(crate) fn check_distinct(parties: &[usize]) -> Result<(), ErrorSS> {
+    let mut sorted = parties.to_vec();
+    sorted.sort_unstable();
+    if sorted.windows(2).any(|w| w[0] == w[1]) {
+        return Err(DuplicatePartyError);
+    }
+    Ok(())
+}
+
+// party `i` holds evaluation of polynomial at point `i+1`
+pub(crate) fn party_points<S: ECScalar>(parties: &[usize]) -> Vec<S> {
+    parties
+        .iter()
+        .map(|i| S::from(&BigInt::from(*i as u32 + 1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_for_all_curves;
+
+    test_for_all_curves!(additive_sharing_reconstructs);
+    fn additive_sharing_reconstructs<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone + PartialEq + std::fmt::Debug,
+    {
+        let secret = P::Scalar::new_random();
+        let scheme = AdditiveSecretSharing { share_count: 4 };
+        let mut shares = scheme.share(&secret);
+        assert_eq!(scheme.reconstruct(&shares), secret);
+        shares.reverse();
+        assert_eq!(scheme.reconstruct(&shares), secret);
+    }
+
+    test_for_all_curves!(additive_share_arithmetic);
+    fn additive_share_arithmetic<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone + PartialEq + std::fmt::Debug,
+    {
+        let scheme = AdditiveSecretSharing { share_count: 3 };
+        let (a, b, c) = (
+            P::Scalar::new_random(),
+            P::Scalar::new_random(),
+            P::Scalar::new_random(),
+        );
+        let a_shares = scheme.share(&a);
+        let b_shares = scheme.share(&b);
+
+        // c * (a - b) + a + c
+        let result: Vec<_> = a_shares
+            .iter()
+            .zip(&b_shares)
+            .map(|(a_i, b_i)| a_i.sub(b_i).mul_by_constant(&c).add(a_i).add_constant(&c))
+            .collect();
+        let expected = a.sub(&b.get_element()) * c.clone() + a + c;
+        assert_eq!(scheme.reconstruct(&result), expected);
+    }
+
+    test_for_all_curves!(shamir_to_additive_and_back);
+    fn shamir_to_additive_and_back<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone + PartialEq + std::fmt::Debug,
+    {
+        let secret = P::Scalar::new_random();
+        let (vss, shamir_shares) = VerifiableSS::<P>::share(2, 5, &secret);
+
+        let parties = [4, 1, 3];
+        let additive: Vec<_> = parties
+            .iter()
+            .map(|i| {
+                AdditiveShare::from_shamir(&vss.parameters, *i, &shamir_shares[*i], &parties)
+                    .unwrap()
+            })
+            .collect();
+        let scheme = AdditiveSecretSharing { share_count: 3 };
+        assert_eq!(scheme.reconstruct(&additive), secret);
+
+        let converted: Vec<_> = additive.iter().map(|s| s.to_shamir(3)).collect();
+        let (params, _) = converted[0].clone();
+        assert_eq!(params.threshold, 2);
+        let vss_back = VerifiableSS::<P> {
+            parameters: params,
+            commitments: vec![],
+        };
+        let shares: Vec<_> = converted.into_iter().map(|(_, s)| s).collect();
+        assert_eq!(vss_back.reconstruct(&[0, 1, 2], &shares), secret);
+    }
+
+    test_for_all_curves!(additive_reshare_to_lower_threshold);
+    fn additive_reshare_to_lower_threshold<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone + PartialEq + std::fmt::Debug,
+    {
+        let secret = P::Scalar::new_random();
+        let additive = AdditiveSecretSharing { share_count: 3 }.share(&secret);
+        let (t, n) = (1, 4);
+        let dealings: Vec<(VerifiableSS<P>, Vec<P::Scalar>)> =
+            additive.iter().map(|s| s.reshare(t, n)).collect();
+        let commitments: Vec<_> = dealings.iter().map(|(vss, _)| vss.clone()).collect();
+
+        let combined: Vec<_> = (0..n)
+            .map(|receiver| {
+                let received: Vec<_> = dealings
+                    .iter()
+                    .map(|(_, shares)| shares[receiver].clone())
+                    .collect();
+                AdditiveSecretSharing::combine_reshared(&commitments, &received)
+            })
+            .collect();
+        for (i, (vss, share)) in combined.iter().enumerate() {
+            assert!(vss.validate_share(share, i + 1).is_ok());
+        }
+        let (vss, _) = &combined[0];
+        assert!(vss.commitments[0] == P::generator() * secret.clone());
+        let shares = [combined[3].1.clone(), combined[1].1.clone()];
+        assert_eq!(vss.reconstruct(&[3, 1], &shares), secret);
+    }
+
+    test_for_all_curves!(shamir_to_additive_rejects_invalid_parties);
+    fn shamir_to_additive_rejects_invalid_parties<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone + PartialEq + std::fmt::Debug,
+    {
+        let secret = P::Scalar::new_random();
+        let (vss, shamir_shares) = VerifiableSS::<P>::share(2, 5, &secret);
+
+        let convert = |parties: &[usize]| {
+            AdditiveShare::from_shamir(&vss.parameters, 1, &shamir_shares[1], parties)
+        };
+        assert_eq!(convert(&[4, 1, 4]), Err(DuplicatePartyError));
+        assert_eq!(convert(&[4, 1]), Err(TooFewPartiesError));
+        assert_eq!(convert(&[4, 1, 5]), Err(InvalidPartyError));
+        assert_eq!(convert(&[4, 0, 3]), Err(InvalidPartyError));
+        assert!(convert(&[4, 0, 1]).is_ok());
+    }
+}
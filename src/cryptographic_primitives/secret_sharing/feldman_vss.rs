@@ -8,7 +8,11 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::polynomial::Polynomial;
 use crate::arithmetic::traits::*;
+use crate::elliptic::curves::dynamic::{
+    for_each_curve, impl_curve_tagged_serde, CurveId, CurveMismatch, DynPoint, DynScalar,
+};
 use crate::elliptic::curves::traits::*;
 use crate::BigInt;
 use crate::ErrorSS::{self, VerifyShareError};
@@ -45,10 +49,7 @@ where
         let index_vec: Vec<usize> = (1..=n).collect();
         let secret_shares = VerifiableSS::<P>::evaluate_polynomial(&poly, &index_vec);
 
-        let G: P = ECPoint::generator();
-        let commitments = (0..poly.len())
-            .map(|i| G.clone() * poly[i].clone())
-            .collect::<Vec<P>>();
+        let commitments = poly.iter().map(P::mul_generator).collect::<Vec<P>>();
         (
             VerifiableSS {
                 parameters: ShamirSecretSharing {
@@ -70,10 +71,9 @@ where
         let secret_shares: Vec<_> = (0..secret_shares_biased.len())
             .map(|i| secret_shares_biased[i].sub(&one.get_element()))
             .collect();
-        let G: P = ECPoint::generator();
         let mut new_commitments = vec![self.commitments[0].clone()];
         for (poly, commitment) in poly.iter().zip(&self.commitments).skip(1) {
-            new_commitments.push((G.clone() * poly.clone()) + commitment.clone())
+            new_commitments.push(P::mul_generator(poly) + commitment.clone())
         }
         (
             VerifiableSS {
@@ -95,10 +95,7 @@ where
         let poly = VerifiableSS::<P>::sample_polynomial(t, secret);
         let secret_shares = VerifiableSS::<P>::evaluate_polynomial(&poly, index_vec);
 
-        let G: P = ECPoint::generator();
-        let commitments = (0..poly.len())
-            .map(|i| G.clone() * poly[i].clone())
-            .collect::<Vec<P>>();
+        let commitments = poly.iter().map(P::mul_generator).collect::<Vec<P>>();
         (
             VerifiableSS {
                 parameters: ShamirSecretSharing {
@@ -113,35 +110,15 @@ where
 
     // returns vector of coefficients
     pub fn sample_polynomial(t: usize, coef0: &P::Scalar) -> Vec<P::Scalar> {
-        let mut coefficients = vec![coef0.clone()];
-        // sample the remaining coefficients randomly using secure randomness
-        let random_coefficients: Vec<P::Scalar> = (0..t).map(|_| ECScalar::new_random()).collect();
-        coefficients.extend(random_coefficients);
-        // return
-        coefficients
+        Polynomial::sample_with_fixed_const_term(t, coef0.clone()).into_coefficients()
     }
 
     pub fn evaluate_polynomial(coefficients: &[P::Scalar], index_vec: &[usize]) -> Vec<P::Scalar> {
-        (0..index_vec.len())
-            .map(|point| {
-                let point_bn = BigInt::from(index_vec[point] as u32);
-
-                VerifiableSS::<P>::mod_evaluate_polynomial(coefficients, ECScalar::from(&point_bn))
-            })
-            .collect()
+        Polynomial::from_coefficients(coefficients.to_vec()).evaluate_at_indices(index_vec)
     }
 
     pub fn mod_evaluate_polynomial(coefficients: &[P::Scalar], point: P::Scalar) -> P::Scalar {
-        // evaluate using Horner's rule
-        //  - to combine with fold we consider the coefficients in reverse order
-        let mut reversed_coefficients = coefficients.iter().rev();
-        // manually split due to fold insisting on an initial value
-        let head = reversed_coefficients.next().unwrap();
-        let tail = reversed_coefficients;
-        tail.fold(head.clone(), |partial, coef| {
-            let partial_times_point = partial.mul(&point.get_element());
-            partial_times_point.add(&coef.get_element())
-        })
+        Polynomial::from_coefficients(coefficients.to_vec()).evaluate(&point)
     }
 
     pub fn reconstruct(&self, indices: &[usize], shares: &[P::Scalar]) -> P::Scalar {
@@ -165,49 +142,15 @@ where
 
     // The result is the value of the polynomial at x=0. It is also its zero-degree coefficient.
 
-    // This is obviously less general than `newton_interpolation_general` as we
+    // This is obviously less general than `Polynomial::interpolate` as we
     // only get a single value, but it is much faster.
 
     pub fn lagrange_interpolation_at_zero(points: &[P::Scalar], values: &[P::Scalar]) -> P::Scalar {
-        let vec_len = values.len();
-
-        assert_eq!(points.len(), vec_len);
-        // Lagrange interpolation for point 0
-        // let mut acc = 0i64;
-        let lag_coef = (0..vec_len)
-            .map(|i| {
-                let xi = &points[i];
-                let yi = &values[i];
-                let num: P::Scalar = ECScalar::from(&BigInt::one());
-                let denum: P::Scalar = ECScalar::from(&BigInt::one());
-                let num = points.iter().zip(0..vec_len).fold(num, |acc, x| {
-                    if i != x.1 {
-                        acc * x.0.clone()
-                    } else {
-                        acc
-                    }
-                });
-                let denum = points.iter().zip(0..vec_len).fold(denum, |acc, x| {
-                    if i != x.1 {
-                        let xj_sub_xi = x.0.sub(&xi.get_element());
-                        acc * xj_sub_xi
-                    } else {
-                        acc
-                    }
-                });
-                let denum = denum.invert();
-                num * denum * yi.clone()
-            })
-            .collect::<Vec<P::Scalar>>();
-        let mut lag_coef_iter = lag_coef.iter();
-        let head = lag_coef_iter.next().unwrap();
-        let tail = lag_coef_iter;
-        tail.fold(head.clone(), |acc, x| acc.add(&x.get_element()))
+        Polynomial::lagrange_interpolation(&P::Scalar::zero(), points, values)
     }
 
     pub fn validate_share(&self, secret_share: &P::Scalar, index: usize) -> Result<(), ErrorSS> {
-        let G: P = ECPoint::generator();
-        let ss_point = G * secret_share.clone();
+        let ss_point = P::mul_generator(secret_share);
         self.validate_share_public(&ss_point, index)
     }
 
@@ -270,6 +213,92 @@ where
     }
 }
 
+macro_rules! impl_dyn_verifiable_ss {
+    ($($variant:ident => $name:literal, $point:ty;)*) => {
+        /// [VerifiableSS] over the curve chosen at runtime, serialized along with the curve name
+        #[derive(Clone, PartialEq, Debug)]
+        pub enum DynVerifiableSS {
+            $(
+                $variant(VerifiableSS<$point>),
+            )*
+        }
+
+        impl_curve_tagged_serde!(DynVerifiableSS, "vss"; $($variant => VerifiableSS<$point>;)*);
+
+        impl DynVerifiableSS {
+            /// Shares `secret` over its curve, see [VerifiableSS::share]
+            pub fn share(
+                t: usize,
+                n: usize,
+                secret: &DynScalar,
+            ) -> (DynVerifiableSS, Vec<DynScalar>) {
+                match secret {
+                    $(DynScalar::$variant(secret) => {
+                        let (vss, shares) = VerifiableSS::share(t, n, secret);
+                        (
+                            DynVerifiableSS::$variant(vss),
+                            shares.into_iter().map(DynScalar::$variant).collect(),
+                        )
+                    })*
+                }
+            }
+
+            pub fn curve(&self) -> CurveId {
+                match self {
+                    $(DynVerifiableSS::$variant(_) => CurveId::$variant,)*
+                }
+            }
+
+            pub fn parameters(&self) -> &ShamirSecretSharing {
+                match self {
+                    $(DynVerifiableSS::$variant(vss) => &vss.parameters,)*
+                }
+            }
+
+            pub fn commitments(&self) -> Vec<DynPoint> {
+                match self {
+                    $(DynVerifiableSS::$variant(vss) => {
+                        vss.commitments.iter().cloned().map(DynPoint::$variant).collect()
+                    })*
+                }
+            }
+
+            /// Share of another curve fails the validation
+            pub fn validate_share(
+                &self,
+                secret_share: &DynScalar,
+                index: usize,
+            ) -> Result<(), ErrorSS> {
+                match (self, secret_share) {
+                    $((DynVerifiableSS::$variant(vss), DynScalar::$variant(share)) => {
+                        vss.validate_share(share, index)
+                    })*
+                    _ => Err(VerifyShareError),
+                }
+            }
+
+            /// Reconstructs the secret, see [VerifiableSS::reconstruct]
+            pub fn reconstruct(
+                &self,
+                indices: &[usize],
+                shares: &[DynScalar],
+            ) -> Result<DynScalar, CurveMismatch> {
+                match self {
+                    $(DynVerifiableSS::$variant(vss) => {
+                        let shares = shares
+                            .iter()
+                            .map(DynScalar::downcast::<$point>)
+                            .collect::<Result<Vec<_>, _>>()?;
+                        Ok(DynScalar::$variant(vss.reconstruct(indices, &shares)))
+                    })*
+                }
+            }
+        }
+    };
+}
+
+for_each_curve!(impl_dyn_verifiable_ss);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -500,4 +529,46 @@ mod tests {
         assert!(valid2.is_ok());
         assert!(valid3.is_ok());
     }
+
+    #[test]
+    fn test_dyn_secret_sharing() {
+        for curve in CurveId::ALL.iter().copied() {
+            let secret = DynScalar::new_random(curve);
+            let (vss, shares) = DynVerifiableSS::share(2, 4, &secret);
+            assert_eq!(vss.curve(), curve);
+            assert_eq!(vss.commitments().len(), 3);
+            assert_eq!(
+                vss.commitments()[0],
+                DynPoint::generator(curve).try_mul(&secret).unwrap()
+            );
+
+            let json = serde_json::to_string(&vss).unwrap();
+            let vss = serde_json::from_str::<DynVerifiableSS>(&json).unwrap();
+            for (i, share) in shares.iter().enumerate() {
+                assert!(vss.validate_share(share, i + 1).is_ok(), "{}", curve);
+            }
+            assert!(vss.validate_share(&shares[0], 2).is_err());
+
+            let reconstructed = vss.reconstruct(
+                &[0, 1, 3],
+                &[shares[0].clone(), shares[1].clone(), shares[3].clone()],
+            );
+            assert_eq!(reconstructed, Ok(secret), "{}", curve);
+        }
+    }
+
+    #[test]
+    fn test_dyn_secret_sharing_rejects_other_curve() {
+        let (vss, shares) =
+            DynVerifiableSS::share(1, 3, &DynScalar::new_random(CurveId::Secp256k1));
+        let foreign = DynScalar::new_random(CurveId::P256);
+        assert_eq!(vss.validate_share(&foreign, 1), Err(VerifyShareError));
+        assert_eq!(
+            vss.reconstruct(&[0, 1], &[shares[0].clone(), foreign]),
+            Err(CurveMismatch {
+                expected: CurveId::Secp256k1,
+                found: CurveId::P256,
+            })
+        );
+    }
 }
@@ -5,4 +5,8 @@
     License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
 */
 
+pub mod additive;
 pub mod feldman_vss;
+pub mod polynomial;
+pub mod pvss;
+pub mod replicated;
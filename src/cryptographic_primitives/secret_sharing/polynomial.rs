@@ -0,0 +1,703 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+use std::ops;
+
+use serde::{Deserialize, Serialize};
+
+use crate::arithmetic::traits::*;
+use crate::elliptic::curves::traits::ECScalar;
+use crate::BigInt;
+
+/// Polynomial of some degree `n` over the scalar field of a curve
+///
+/// Polynomial has a form: `f(x) = a_0 + a_1 * x^1 + ... + a_(n-1) * x^(n-1) + a_n * x^n`
+///
+/// Coefficients `a_i` are stored in ascending order: `coefficients[i] = a_i`. Leading zero
+/// coefficients are allowed and are ignored by [degree](Self::degree) and comparison.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Polynomial<S> {
+    coefficients: Vec<S>,
+}
+
+impl<S> Polynomial<S>
+where
+    S: ECScalar + Clone,
+{
+    /// Constructs polynomial `f(x)` from list of coefficients `a` in ascending order
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use curv::cryptographic_primitives::secret_sharing::polynomial::Polynomial;
+    /// # use curv::elliptic::curves::secp256_k1::FE;
+    /// # use curv::elliptic::curves::traits::ECScalar;
+    /// # use curv::BigInt;
+    /// let coefs: Vec<FE> = vec![ECScalar::new_random(), ECScalar::new_random()];
+    /// let poly = Polynomial::from_coefficients(coefs.clone());
+    /// assert_eq!(poly.coefficients(), coefs.as_slice());
+    /// assert_eq!(poly.degree(), Some(1));
+    /// ```
+    pub fn from_coefficients(coefficients: Vec<S>) -> Self {
+        Self { coefficients }
+    }
+
+    /// Returns polynomial `f(x) = 0`
+    pub fn zero() -> Self {
+        Self {
+            coefficients: vec![],
+        }
+    }
+
+    /// Samples random polynomial of degree `n` with fixed constant term (ie. `a_0 = const_term`)
+    ///
+    /// This is how a dealer hides a secret in Shamir secret sharing.
+    pub fn sample_with_fixed_const_term(n: usize, const_term: S) -> Self {
+        let random_coefficients = (0..n).map(|_| S::new_random());
+        Self {
+            coefficients: std::iter::once(const_term)
+                .chain(random_coefficients)
+                .collect(),
+        }
+    }
+
+    /// Samples random polynomial of degree `n`
+    pub fn sample(n: usize) -> Self {
+        Self::sample_with_fixed_const_term(n, S::new_random())
+    }
+
+    /// Returns coefficients of the polynomial in ascending order, including leading zeroes (if any)
+    pub fn coefficients(&self) -> &[S] {
+        &self.coefficients
+    }
+
+    /// Consumes the polynomial, returns its coefficients in ascending order
+    pub fn into_coefficients(self) -> Vec<S> {
+        self.coefficients
+    }
+
+    /// Returns degree of the polynomial, or `None` if it's a zero polynomial
+    ///
+    /// Degree is the index of the highest non-zero coefficient.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.iter().rposition(|a| !is_zero_scalar(a))
+    }
+
+    /// Returns `true` if all coefficients of the polynomial are zero
+    pub fn is_zero(&self) -> bool {
+        self.degree().is_none()
+    }
+
+    /// Returns leading (highest non-zero) coefficient, or `None` if it's a zero polynomial
+    pub fn leading_coefficient(&self) -> Option<&S> {
+        self.degree().map(|n| &self.coefficients[n])
+    }
+
+    /// Takes scalar `x` and evaluates `f(x)` using Horner's rule
+    pub fn evaluate(&self, x: &S) -> S {
+        let n = match self.degree() {
+            Some(n) => n,
+            None => return S::zero(),
+        };
+        if n == 0 {
+            return self.coefficients[0].clone();
+        }
+        let mut reversed_coefficients = self.coefficients[..=n].iter().rev();
+        let head = reversed_coefficients.next().unwrap().clone();
+        reversed_coefficients.fold(head, |partial, coef| add(&mul(&partial, x), coef))
+    }
+
+    /// Takes point `x` that's convertable to BigInt, and evaluates `f(x)`
+    pub fn evaluate_bigint<B>(&self, x: B) -> S
+    where
+        BigInt: From<B>,
+    {
+        self.evaluate(&S::from(&BigInt::from(x)))
+    }
+
+    /// Evaluates polynomial at every point from `points`
+    pub fn evaluate_many(&self, points: &[S]) -> Vec<S> {
+        points.iter().map(|x| self.evaluate(x)).collect()
+    }
+
+    /// Evaluates polynomial at points `1, 2, ..., n`
+    ///
+    /// That's how shares are computed in [VerifiableSS](super::feldman_vss::VerifiableSS).
+    pub fn evaluate_at_indices(&self, indices: &[usize]) -> Vec<S> {
+        indices
+            .iter()
+            .map(|i| self.evaluate_bigint(*i as u64))
+            .collect()
+    }
+
+    /// Multiplies every coefficient of the polynomial by scalar `c`
+    pub fn scale(&self, c: &S) -> Self {
+        Self {
+            coefficients: self.coefficients.iter().map(|a| mul(a, c)).collect(),
+        }
+    }
+
+    /// Returns `-f(x)`
+    pub fn neg(&self) -> Self {
+        Self {
+            coefficients: self.coefficients.iter().map(negate).collect(),
+        }
+    }
+
+    /// Returns `f(x) + g(x)`
+    pub fn add(&self, other: &Self) -> Self {
+        let len = self.coefficients.len().max(other.coefficients.len());
+        let coefficients = (0..len)
+            .map(
+                |i| match (self.coefficients.get(i), other.coefficients.get(i)) {
+                    (Some(a), Some(b)) => add(a, b),
+                    (Some(a), None) => a.clone(),
+                    (None, Some(b)) => b.clone(),
+                    (None, None) => unreachable!(),
+                },
+            )
+            .collect();
+        Self { coefficients }
+    }
+
+    /// Returns `f(x) - g(x)`
+    pub fn sub(&self, other: &Self) -> Self {
+        let len = self.coefficients.len().max(other.coefficients.len());
+        let coefficients = (0..len)
+            .map(
+                |i| match (self.coefficients.get(i), other.coefficients.get(i)) {
+                    (Some(a), Some(b)) => sub(a, b),
+                    (Some(a), None) => a.clone(),
+                    (None, Some(b)) => negate(b),
+                    (None, None) => unreachable!(),
+                },
+            )
+            .collect();
+        Self { coefficients }
+    }
+
+    /// Returns `f(x) * g(x)` computed by schoolbook multiplication
+    ///
+    /// For scalar fields that admit radix-2 NTT, [mul_fft](Self::mul_fft) is asymptotically faster.
+    pub fn mul(&self, other: &Self) -> Self {
+        let (n, m) = match (self.degree(), other.degree()) {
+            (Some(n), Some(m)) => (n, m),
+            _ => return Self::zero(),
+        };
+        let mut coefficients: Vec<Option<S>> = vec![None; n + m + 1];
+        for (i, a) in self.coefficients[..=n].iter().enumerate() {
+            if is_zero_scalar(a) {
+                continue;
+            }
+            for (j, b) in other.coefficients[..=m].iter().enumerate() {
+                if is_zero_scalar(b) {
+                    continue;
+                }
+                let ab = mul(a, b);
+                coefficients[i + j] = Some(match coefficients[i + j].take() {
+                    Some(acc) => add(&acc, &ab),
+                    None => ab,
+                });
+            }
+        }
+        Self {
+            coefficients: coefficients
+                .into_iter()
+                .map(|a| a.unwrap_or_else(S::zero))
+                .collect(),
+        }
+    }
+
+    /// Divides `f(x)` by `g(x)`, returns quotient `q(x)` and remainder `r(x)` such that
+    /// `f(x) = q(x) * g(x) + r(x)` and `deg(r) < deg(g)`
+    ///
+    /// ## Panics
+    /// Panics if `g(x)` is a zero polynomial
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        let d = divisor
+            .degree()
+            .expect("division by zero polynomial is undefined");
+        let n = match self.degree() {
+            Some(n) if n >= d => n,
+            _ => return (Self::zero(), self.clone()),
+        };
+
+        let lead_inv = divisor.coefficients[d].invert();
+        let mut remainder = self.coefficients[..=n].to_vec();
+        let mut quotient = vec![S::zero(); n - d + 1];
+        for i in (0..quotient.len()).rev() {
+            // leading term is cancelled out by construction, so we don't need to compute it
+            let top = remainder.pop().unwrap();
+            if is_zero_scalar(&top) {
+                continue;
+            }
+            let c = mul(&top, &lead_inv);
+            for (j, b) in divisor.coefficients[..d].iter().enumerate() {
+                if !is_zero_scalar(b) {
+                    remainder[i + j] = sub(&remainder[i + j], &mul(&c, b));
+                }
+            }
+            quotient[i] = c;
+        }
+        (
+            Self {
+                coefficients: quotient,
+            },
+            Self {
+                coefficients: remainder,
+            },
+        )
+    }
+
+    /// Constructs the unique polynomial of degree `< n` passing through `n` points
+    /// `(points[i], values[i])` using Lagrange interpolation
+    ///
+    /// ## Panics
+    /// Panics if `points` and `values` differ in length, or if `points` contain duplicates.
+    pub fn interpolate(points: &[S], values: &[S]) -> Self {
+        assert_eq!(points.len(), values.len());
+        let vanishing = points.iter().fold(Self::one(), |acc, x| {
+            acc.mul(&Self::from_coefficients(vec![
+                negate(x),
+                S::from(&BigInt::one()),
+            ]))
+        });
+        points
+            .iter()
+            .zip(values)
+            .fold(Self::zero(), |acc, (x_i, y_i)| {
+                let x_minus_x_i =
+                    Self::from_coefficients(vec![negate(x_i), S::from(&BigInt::one())]);
+                let (basis, _) = vanishing.div_rem(&x_minus_x_i);
+                let denominator = basis.evaluate(x_i);
+                let coef = mul(y_i, &denominator.invert());
+                acc.add(&basis.scale(&coef))
+            })
+    }
+
+    /// Evaluates at point `x` the unique polynomial of degree `< n` passing through `n` points
+    /// `(points[i], values[i])`, without computing its coefficients
+    ///
+    /// Generalizes [VerifiableSS::lagrange_interpolation_at_zero] to arbitrary `x`.
+    ///
+    /// [VerifiableSS::lagrange_interpolation_at_zero]: super::feldman_vss::VerifiableSS::lagrange_interpolation_at_zero
+    pub fn lagrange_interpolation(x: &S, points: &[S], values: &[S]) -> S {
+        assert_eq!(points.len(), values.len());
+        assert!(!points.is_empty(), "at least one point is required");
+        if let Some(i) = points.iter().position(|x_i| scalars_eq(x_i, x)) {
+            return values[i].clone();
+        }
+        let mut terms = (0..points.len()).map(|i| {
+            let lambda = Self::lagrange_basis(x, i, points);
+            mul(&lambda, &values[i])
+        });
+        let head = terms.next().unwrap();
+        terms.fold(head, |acc, term| add(&acc, &term))
+    }
+
+    /// Evaluates `i`-th Lagrange basis polynomial defined by `points` at point `x`:
+    ///
+    /// `λ_i(x) = Π_{j != i} (x - points[j]) / (points[i] - points[j])`
+    ///
+    /// With `x = 0` that's the coefficient which maps a Shamir share to an additive one
+    /// (see [VerifiableSS::map_share_to_new_params]).
+    ///
+    /// [VerifiableSS::map_share_to_new_params]: super::feldman_vss::VerifiableSS::map_share_to_new_params
+    pub fn lagrange_basis(x: &S, i: usize, points: &[S]) -> S {
+        let x_i = &points[i];
+        let one: S = S::from(&BigInt::one());
+        let (num, denom) = points
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .fold((one.clone(), one), |(num, denom), (_, x_j)| {
+                (mul(&num, &sub(x, x_j)), mul(&denom, &sub(x_i, x_j)))
+            });
+        mul(&num, &denom.invert())
+    }
+
+    fn one() -> Self {
+        Self::from_coefficients(vec![S::from(&BigInt::one())])
+    }
+}
+
+impl<S> PartialEq for Polynomial<S>
+where
+    S: ECScalar + Clone + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        match (self.degree(), other.degree()) {
+            (Some(n), Some(m)) if n == m => self.coefficients[..=n] == other.coefficients[..=m],
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<S: ECScalar + Clone> ops::Add for &Polynomial<S> {
+    type Output = Polynomial<S>;
+    fn add(self, rhs: Self) -> Self::Output {
+        Polynomial::add(self, rhs)
+    }
+}
+
+impl<S: ECScalar + Clone> ops::Sub for &Polynomial<S> {
+    type Output = Polynomial<S>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Polynomial::sub(self, rhs)
+    }
+}
+
+impl<S: ECScalar + Clone> ops::Mul for &Polynomial<S> {
+    type Output = Polynomial<S>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Polynomial::mul(self, rhs)
+    }
+}
+
+impl<S: ECScalar + Clone> ops::Neg for &Polynomial<S> {
+    type Output = Polynomial<S>;
+    fn neg(self) -> Self::Output {
+        Polynomial::neg(self)
+    }
+}
+
+/// Scalar field that admits radix-2 number-theoretic transform (NTT), ie. `2^k` divides `q - 1`
+/// for some large enough `k`
+pub trait FftScalar: ECScalar + Clone {
+    /// Largest `k` such that `2^k` divides `q - 1`
+    const TWO_ADICITY: u32;
+    /// Primitive root of unity of order `2^TWO_ADICITY`
+    fn root_of_unity() -> Self;
+}
+
+impl<S> Polynomial<S>
+where
+    S: FftScalar,
+{
+    /// Returns `f(x) * g(x)` computed via number-theoretic transform in `O(n log n)`
+    ///
+    /// ## Panics
+    /// Panics if degree of the product exceeds `2^TWO_ADICITY - 1`
+    pub fn mul_fft(&self, other: &Self) -> Self {
+        let (n, m) = match (self.degree(), other.degree()) {
+            (Some(n), Some(m)) => (n, m),
+            _ => return Self::zero(),
+        };
+        let result_len = n + m + 1;
+        let log_size = log2_ceil(result_len);
+
+        let mut a = self.coefficients[..=n].to_vec();
+        let mut b = other.coefficients[..=m].to_vec();
+        a.resize(1 << log_size, S::zero());
+        b.resize(1 << log_size, S::zero());
+
+        let omega = root_of_unity_of_order::<S>(log_size);
+        ntt(&mut a, &omega);
+        ntt(&mut b, &omega);
+        let mut c: Vec<S> = a.iter().zip(&b).map(|(a, b)| mul(a, b)).collect();
+        intt(&mut c, &omega);
+        c.truncate(result_len);
+        Self { coefficients: c }
+    }
+
+    /// Evaluates polynomial at every `2^log_size`-th root of unity `ω^0, ω^1, ..., ω^(2^log_size - 1)`
+    ///
+    /// ## Panics
+    /// Panics if `log_size > TWO_ADICITY` or polynomial has more than `2^log_size` coefficients
+    pub fn evaluate_over_domain(&self, log_size: u32) -> Vec<S> {
+        let size = 1usize << log_size;
+        let len = self.degree().map(|n| n + 1).unwrap_or(0);
+        assert!(len <= size, "polynomial doesn't fit into evaluation domain");
+        let mut values = self.coefficients[..len].to_vec();
+        values.resize(size, S::zero());
+        ntt(&mut values, &root_of_unity_of_order::<S>(log_size));
+        values
+    }
+
+    /// Constructs polynomial from its values at every `2^k`-th root of unity, where `2^k = values.len()`
+    ///
+    /// Inverse of [evaluate_over_domain](Self::evaluate_over_domain).
+    ///
+    /// ## Panics
+    /// Panics if `values.len()` is not a power of two
+    pub fn interpolate_over_domain(values: &[S]) -> Self {
+        assert!(
+            values.len().is_power_of_two(),
+            "size of evaluation domain must be a power of two"
+        );
+        let mut coefficients = values.to_vec();
+        let omega = root_of_unity_of_order::<S>(log2_ceil(values.len()));
+        intt(&mut coefficients, &omega);
+        Self { coefficients }
+    }
+}
+
+fn log2_ceil(n: usize) -> u32 {
+    n.next_power_of_two().trailing_zeros()
+}
+
+fn root_of_unity_of_order<S: FftScalar>(log_size: u32) -> S {
+    assert!(
+        log_size <= S::TWO_ADICITY,
+        "scalar field doesn't have a root of unity of order 2^{}",
+        log_size
+    );
+    (log_size..S::TWO_ADICITY).fold(S::root_of_unity(), |omega, _| mul(&omega, &omega))
+}
+
+/// In-place iterative radix-2 Cooley-Tukey transform
+fn ntt<S: FftScalar>(values: &mut [S], omega: &S) {
+    let n = values.len();
+    if n <= 1 {
+        return;
+    }
+    let log_n = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - log_n);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let one: S = S::from(&BigInt::one());
+    let mut m = 1;
+    while m < n {
+        // w_m is primitive root of unity of order 2m
+        let w_m = (0..log_n - m.trailing_zeros() - 1).fold(omega.clone(), |w, _| mul(&w, &w));
+        for k in (0..n).step_by(2 * m) {
+            let mut w = one.clone();
+            for j in 0..m {
+                let t = mul(&values[k + j + m], &w);
+                let u = values[k + j].clone();
+                values[k + j] = add(&u, &t);
+                values[k + j + m] = sub(&u, &t);
+                w = mul(&w, &w_m);
+            }
+        }
+        m *= 2;
+    }
+}
+
+fn intt<S: FftScalar>(values: &mut [S], omega: &S) {
+    ntt(values, &omega.invert());
+    let n_inv = S::from(&BigInt::from(values.len() as u64)).invert();
+    for v in values.iter_mut() {
+        *v = mul(v, &n_inv);
+    }
+}
+
+fn is_zero_scalar<S: ECScalar>(s: &S) -> bool {
+    s.to_big_int().is_zero()
+}
+
+fn scalars_eq<S: ECScalar>(a: &S, b: &S) -> bool {
+    a.to_big_int() == b.to_big_int()
+}
+
+fn add<S: ECScalar>(a: &S, b: &S) -> S {
+    a.add(&b.get_element())
+}
+
+fn sub<S: ECScalar>(a: &S, b: &S) -> S {
+    a.sub(&b.get_element())
+}
+
+fn mul<S: ECScalar>(a: &S, b: &S) -> S {
+    a.mul(&b.get_element())
+}
+
+fn negate<S: ECScalar>(s: &S) -> S {
+    sub(&S::zero(), s)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+
+    use super::*;
+    use crate::elliptic::curves::bls12_381::g1::FE as BlsScalar;
+    use crate::elliptic::curves::bn254::g1::FE as Bn254Scalar;
+    use crate::elliptic::curves::traits::ECPoint;
+    use crate::test_for_all_curves;
+
+    fn scalar<S: ECScalar>(n: u32) -> S {
+        S::from(&BigInt::from(n))
+    }
+
+    test_for_all_curves!(evaluates_polynomial);
+    fn evaluates_polynomial<P>()
+    where
+        P: ECPoint,
+        P::Scalar: Clone + PartialEq + Debug,
+    {
+        // f(x) = 3 + 2x + x^2
+        let f = Polynomial::<P::Scalar>::from_coefficients(vec![scalar(3), scalar(2), scalar(1)]);
+        assert_eq!(f.degree(), Some(2));
+        assert_eq!(f.evaluate_bigint(0u32), scalar(3));
+        assert_eq!(f.evaluate_bigint(1u32), scalar(6));
+        assert_eq!(f.evaluate_bigint(5u32), scalar(38));
+        assert_eq!(
+            f.evaluate_at_indices(&[1, 2, 3]),
+            vec![scalar(6), scalar(11), scalar(18)]
+        );
+    }
+
+    test_for_all_curves!(degree_ignores_leading_zeroes);
+    fn degree_ignores_leading_zeroes<P>()
+    where
+        P: ECPoint,
+        P::Scalar: Clone + PartialEq + Debug,
+    {
+        let f = Polynomial::<P::Scalar>::from_coefficients(vec![
+            scalar(1),
+            scalar(2),
+            P::Scalar::zero(),
+            P::Scalar::zero(),
+        ]);
+        assert_eq!(f.degree(), Some(1));
+        assert_eq!(f.leading_coefficient(), Some(&scalar(2)));
+        assert_eq!(f, Polynomial::from_coefficients(vec![scalar(1), scalar(2)]));
+
+        assert_eq!(Polynomial::<P::Scalar>::zero().degree(), None);
+        assert!(Polynomial::<P::Scalar>::from_coefficients(vec![P::Scalar::zero()]).is_zero());
+        assert_eq!(Polynomial::<P::Scalar>::sample(5).degree(), Some(5));
+    }
+
+    test_for_all_curves!(arithmetic_is_consistent_with_evaluation);
+    fn arithmetic_is_consistent_with_evaluation<P>()
+    where
+        P: ECPoint,
+        P::Scalar: Clone + PartialEq + Debug,
+    {
+        let f = Polynomial::<P::Scalar>::sample(4);
+        let g = Polynomial::<P::Scalar>::sample(2);
+        let x: P::Scalar = ECScalar::new_random();
+
+        let (f_x, g_x) = (f.evaluate(&x), g.evaluate(&x));
+        assert_eq!((&f + &g).evaluate(&x), add(&f_x, &g_x));
+        assert_eq!((&f - &g).evaluate(&x), sub(&f_x, &g_x));
+        assert_eq!((&f * &g).evaluate(&x), mul(&f_x, &g_x));
+        assert_eq!((&f * &g).degree(), Some(6));
+        assert_eq!((-&f).evaluate(&x), negate(&f_x));
+    }
+
+    test_for_all_curves!(division_with_remainder);
+    fn division_with_remainder<P>()
+    where
+        P: ECPoint,
+        P::Scalar: Clone + PartialEq + Debug,
+    {
+        let f = Polynomial::<P::Scalar>::sample(7);
+        let g = Polynomial::<P::Scalar>::sample(3);
+        let (q, r) = f.div_rem(&g);
+        assert_eq!(q.degree(), Some(4));
+        assert!(r.degree().map(|d| d < 3).unwrap_or(true));
+        assert_eq!(&(&q * &g) + &r, f);
+
+        // dividing by a higher degree polynomial leaves dividend as remainder
+        let (q, r) = g.div_rem(&f);
+        assert!(q.is_zero());
+        assert_eq!(r, g);
+
+        // dividing a product gives no remainder
+        let h = Polynomial::<P::Scalar>::sample(2);
+        let (q, r) = (&f * &h).div_rem(&h);
+        assert_eq!(q, f);
+        assert!(r.is_zero());
+    }
+
+    test_for_all_curves!(interpolation_recovers_polynomial);
+    fn interpolation_recovers_polynomial<P>()
+    where
+        P: ECPoint,
+        P::Scalar: Clone + PartialEq + Debug,
+    {
+        let f = Polynomial::<P::Scalar>::sample(3);
+        let points: Vec<P::Scalar> = (0..4).map(|_| ECScalar::new_random()).collect();
+        let values = f.evaluate_many(&points);
+
+        assert_eq!(Polynomial::interpolate(&points, &values), f);
+
+        let x: P::Scalar = ECScalar::new_random();
+        assert_eq!(
+            Polynomial::lagrange_interpolation(&x, &points, &values),
+            f.evaluate(&x)
+        );
+        assert_eq!(
+            Polynomial::lagrange_interpolation(&points[2], &points, &values),
+            values[2]
+        );
+    }
+
+    test_for_all_curves!(lagrange_basis_at_zero_matches_vss);
+    fn lagrange_basis_at_zero_matches_vss<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone + PartialEq + Debug,
+    {
+        use crate::cryptographic_primitives::secret_sharing::feldman_vss::{
+            ShamirSecretSharing, VerifiableSS,
+        };
+
+        let params = ShamirSecretSharing {
+            threshold: 2,
+            share_count: 5,
+        };
+        let s = [0, 2, 4];
+        let points: Vec<P::Scalar> = s.iter().map(|i| scalar(*i as u32 + 1)).collect();
+        for (k, i) in s.iter().enumerate() {
+            assert_eq!(
+                Polynomial::lagrange_basis(&P::Scalar::zero(), k, &points),
+                VerifiableSS::<P>::map_share_to_new_params(&params, *i, &s)
+            );
+        }
+    }
+
+    #[test]
+    fn fft_multiplication_matches_schoolbook() {
+        for &(n, m) in &[(0, 0), (1, 0), (3, 4), (15, 16), (40, 23)] {
+            let f = Polynomial::<BlsScalar>::sample(n);
+            let g = Polynomial::<BlsScalar>::sample(m);
+            assert_eq!(f.mul_fft(&g), f.mul(&g));
+        }
+        let f = Polynomial::<BlsScalar>::sample(3);
+        assert!(f.mul_fft(&Polynomial::zero()).is_zero());
+    }
+
+    #[test]
+    fn fft_evaluation_over_domain() {
+        let f = Polynomial::<BlsScalar>::sample(5);
+        let values = f.evaluate_over_domain(3);
+        let omega = root_of_unity_of_order::<BlsScalar>(3);
+        let mut x: BlsScalar = scalar(1);
+        for value in &values {
+            assert_eq!(*value, f.evaluate(&x));
+            x = mul(&x, &omega);
+        }
+        assert_eq!(x, scalar(1));
+        assert_eq!(Polynomial::interpolate_over_domain(&values), f);
+    }
+
+    #[test]
+    fn fft_over_bn254_scalar_field() {
+        let omega: Bn254Scalar = Bn254Scalar::root_of_unity();
+        let half_order = BigInt::one() << (Bn254Scalar::TWO_ADICITY as usize - 1);
+        let minus_one: Bn254Scalar = negate(&scalar(1));
+        assert_eq!(
+            <Bn254Scalar as ECScalar>::from(&BigInt::mod_pow(
+                &omega.to_big_int(),
+                &half_order,
+                &Bn254Scalar::q()
+            )),
+            minus_one
+        );
+
+        let f = Polynomial::<Bn254Scalar>::sample(20);
+        let g = Polynomial::<Bn254Scalar>::sample(13);
+        assert_eq!(f.mul_fft(&g), f.mul(&g));
+    }
+}
@@ -0,0 +1,391 @@
+#![allow(non_snake_case)]
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+use std::fmt::Debug;
+
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use super::additive::{check_distinct, party_points};
+use super::feldman_vss::VerifiableSS;
+use super::polynomial::Polynomial;
+use crate::cryptographic_primitives::proofs::sigma_ec_ddh::*;
+use crate::elliptic::curves::traits::*;
+use crate::ErrorSS::{self, InvalidPartyError, TooFewPartiesError, VerifyShareError};
+
+/// Publicly verifiable secret sharing, based on  Ignacio Cascudo and Bernardo David. 2017.
+/// SCRAPE: Scalable Randomness Attested by Public Entities. In ACNS 2017.
+/// (which in turn builds on Berry Schoenmakers. 1999. A Simple Publicly Verifiable Secret Sharing
+/// Scheme and Its Application to Electronic Voting. In CRYPTO 1999.)
+///
+/// implementation details: party `i` (indexed from 0, as in [VerifiableSS::reconstruct]) has key
+/// pair `(sk_i, pk_i = sk_i*G)`. Dealer shares secret `s` with [VerifiableSS] and publishes, for
+/// every party, commitment to its share `X_i = s_i*G` and its share encrypted under `pk_i`:
+/// `Y_i = s_i*pk_i`. [ECDDHProof] proves that `X_i` and `Y_i` have the same discrete log.
+///
+/// Anyone can then check the sharing: [verify](Self::verify) checks that `X_i` lie on a polynomial
+/// of degree `t` with SCRAPE's dual code test (O(n) scalar multiplications), while
+/// [verify_feldman](Self::verify_feldman) recomputes every `X_i` from Feldman commitments
+/// (O(n·t) scalar multiplications). Party `i` decrypts `s_i*G = sk_i^-1 * Y_i` and publishes it
+/// with a proof of correct decryption, and any `t+1` decrypted shares reconstruct `s*G`.
+#[derive(Serialize, Deserialize, Derivative)]
+#[derivative(Clone(bound = "P: Clone, P::Scalar: Clone"))]
+#[derivative(Debug(bound = "P: Debug, P::Scalar: Debug"))]
+#[derivative(PartialEq(bound = "P: PartialEq, P::Scalar: PartialEq"))]
+#[serde(bound(serialize = "P: Serialize, P::Scalar: Serialize"))]
+#[serde(bound(deserialize = "P: Deserialize<'de>, P::Scalar: Deserialize<'de>"))]
+pub struct PubliclyVerifiableSS<P: ECPoint> {
+    pub vss: VerifiableSS<P>,
+    pub share_commitments: Vec<P>,
+    pub encrypted_shares: Vec<P>,
+    pub proofs: Vec<ECDDHProof<P>>,
+}
+
+/// Share `s_i*G` decrypted by party `index` along with proof of correct decryption
+#[derive(Serialize, Deserialize, Derivative)]
+#[derivative(Clone(bound = "P: Clone, P::Scalar: Clone"))]
+#[derivative(Debug(bound = "P: Debug, P::Scalar: Debug"))]
+#[derivative(PartialEq(bound = "P: PartialEq, P::Scalar: PartialEq"))]
+#[serde(bound(serialize = "P: Serialize, P::Scalar: Serialize"))]
+#[serde(bound(deserialize = "P: Deserialize<'de>, P::Scalar: Deserialize<'de>"))]
+pub struct DecryptedShare<P: ECPoint> {
+    pub index: usize,
+    pub share: P,
+    pub proof: ECDDHProof<P>,
+}
+
+impl<P> PubliclyVerifiableSS<P>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Zeroize + Clone,
+{
+    // shares the secret among owners of public_keys, any t+1 of them can reconstruct secret*G
+    pub fn share(t: usize, secret: &P::Scalar, public_keys: &[P]) -> Self {
+        let n = public_keys.len();
+        let (vss, mut secret_shares) = VerifiableSS::<P>::share(t, n, secret);
+
+        let G: P = ECPoint::generator();
+        let mut share_commitments = Vec::with_capacity(n);
+        let mut encrypted_shares = Vec::with_capacity(n);
+        let mut proofs = Vec::with_capacity(n);
+        for (s_i, pk_i) in secret_shares.iter().zip(public_keys) {
+            let x_i = G.clone() * s_i.clone();
+            let y_i = pk_i.clone() * s_i.clone();
+            let statement = ECDDHStatement {
+                g1: G.clone(),
+                h1: x_i.clone(),
+                g2: pk_i.clone(),
+                h2: y_i.clone(),
+            };
+            let witness = ECDDHWitness { x: s_i.clone() };
+            proofs.push(ECDDHProof::prove(&witness, &statement));
+            share_commitments.push(x_i);
+            encrypted_shares.push(y_i);
+        }
+        secret_shares.iter_mut().for_each(|s_i| s_i.zeroize());
+
+        PubliclyVerifiableSS {
+            vss,
+            share_commitments,
+            encrypted_shares,
+            proofs,
+        }
+    }
+
+    /// Verifies the sharing using SCRAPE's dual code test
+    ///
+    /// Points `(0, C_0), (1, X_0), ..., (n, X_(n-1))`, where `C_0 = s*G` is the first Feldman
+    /// commitment, lie on a polynomial of degree `t` iff `Σ c_j * V_j = 0` for a random codeword
+    /// `c` of the dual code. Codeword `c_j = f(j) * Π_{k != j} (j - k)^-1` is derived from a random
+    /// polynomial `f` of degree `n - t - 1`.
+    pub fn verify(&self, public_keys: &[P]) -> Result<(), ErrorSS> {
+        self.check_lengths(public_keys)?;
+        let n = self.share_commitments.len();
+        let t = self.vss.parameters.threshold;
+
+        let mut points = vec![P::Scalar::zero()];
+        points.extend(party_points::<P::Scalar>(&(0..n).collect::<Vec<_>>()));
+        let f = Polynomial::<P::Scalar>::sample(n - t - 1);
+        let mut codeword = points.iter().enumerate().map(|(j, x_j)| {
+            let denom = points
+                .iter()
+                .enumerate()
+                .filter(|(k, _)| *k != j)
+                .fold(None, |acc: Option<P::Scalar>, (_, x_k)| {
+                    let diff = x_j.sub(&x_k.get_element());
+                    Some(match acc {
+                        Some(acc) => acc * diff,
+                        None => diff,
+                    })
+                })
+                .expect("at least two points");
+            f.evaluate(x_j) * denom.invert()
+        });
+
+        // checks Σ_{j>0} c_j*X_(j-1) = -c_0*C_0, to avoid constructing point at infinity
+        let c_0 = codeword.next().unwrap();
+        let minus_c_0 = P::Scalar::zero().sub(&c_0.get_element());
+        let rhs = self.vss.commitments[0].clone() * minus_c_0;
+        let mut terms = self
+            .share_commitments
+            .iter()
+            .zip(codeword)
+            .map(|(x_i, c_i)| x_i.clone() * c_i);
+        let head = terms.next().unwrap();
+        let lhs = terms.fold(head, |acc, term| acc + term);
+        if lhs != rhs {
+            return Err(VerifyShareError);
+        }
+
+        self.verify_encryption(public_keys)
+    }
+
+    /// Verifies the sharing by evaluating Feldman commitments at every party's point
+    pub fn verify_feldman(&self, public_keys: &[P]) -> Result<(), ErrorSS> {
+        self.check_lengths(public_keys)?;
+        for (i, x_i) in self.share_commitments.iter().enumerate() {
+            self.vss.validate_share_public(x_i, i + 1)?;
+        }
+        self.verify_encryption(public_keys)
+    }
+
+    pub fn decrypt_share(
+        &self,
+        index: usize,
+        secret_key: &P::Scalar,
+    ) -> Result<DecryptedShare<P>, ErrorSS> {
+        let G: P = ECPoint::generator();
+        let y_i = self
+            .encrypted_shares
+            .get(index)
+            .ok_or(InvalidPartyError)?
+            .clone();
+        let share = y_i.clone() * secret_key.invert();
+        let statement = ECDDHStatement {
+            g1: G.clone(),
+            h1: G * secret_key.clone(),
+            g2: share.clone(),
+            h2: y_i,
+        };
+        let witness = ECDDHWitness {
+            x: secret_key.clone(),
+        };
+        Ok(DecryptedShare {
+            index,
+            share,
+            proof: ECDDHProof::prove(&witness, &statement),
+        })
+    }
+
+    pub fn verify_decrypted_share(
+        &self,
+        decrypted: &DecryptedShare<P>,
+        public_key: &P,
+    ) -> Result<(), ErrorSS> {
+        let y_i = self
+            .encrypted_shares
+            .get(decrypted.index)
+            .ok_or(VerifyShareError)?;
+        let statement = ECDDHStatement {
+            g1: ECPoint::generator(),
+            h1: public_key.clone(),
+            g2: decrypted.share.clone(),
+            h2: y_i.clone(),
+        };
+        decrypted
+            .proof
+            .verify(&statement)
+            .map_err(|_| VerifyShareError)
+    }
+
+    // reconstructs secret*G from decrypted shares of at least t+1 parties, every share must pass
+    // verify_decrypted_share against the public key of its party
+    pub fn reconstruct(
+        &self,
+        shares: &[DecryptedShare<P>],
+        public_keys: &[P],
+    ) -> Result<P, ErrorSS> {
+        if shares.len() < self.vss.reconstruct_limit() {
+            return Err(TooFewPartiesError);
+        }
+        let indices: Vec<usize> = shares.iter().map(|s| s.index).collect();
+        check_distinct(&indices)?;
+        for share in shares {
+            let public_key = public_keys.get(share.index).ok_or(InvalidPartyError)?;
+            self.verify_decrypted_share(share, public_key)?;
+        }
+        let points = party_points::<P::Scalar>(&indices);
+        let mut terms = shares.iter().enumerate().map(|(i, s)| {
+            let lambda = Polynomial::lagrange_basis(&P::Scalar::zero(), i, &points);
+            s.share.clone() * lambda
+        });
+        let head = terms.next().unwrap();
+        Ok(terms.fold(head, |acc, term| acc + term))
+    }
+
+    fn check_lengths(&self, public_keys: &[P]) -> Result<(), ErrorSS> {
+        let n = public_keys.len();
+        let params = &self.vss.parameters;
+        if params.share_count != n
+            || params.threshold >= n
+            || self.vss.commitments.len() != params.threshold + 1
+            || self.share_commitments.len() != n
+            || self.encrypted_shares.len() != n
+            || self.proofs.len() != n
+        {
+            return Err(VerifyShareError);
+        }
+        Ok(())
+    }
+
+    fn verify_encryption(&self, public_keys: &[P]) -> Result<(), ErrorSS> {
+        let G: P = ECPoint::generator();
+        for (((x_i, y_i), pk_i), proof) in self
+            .share_commitments
+            .iter()
+            .zip(&self.encrypted_shares)
+            .zip(public_keys)
+            .zip(&self.proofs)
+        {
+            let statement = ECDDHStatement {
+                g1: G.clone(),
+                h1: x_i.clone(),
+                g2: pk_i.clone(),
+                h2: y_i.clone(),
+            };
+            proof.verify(&statement).map_err(|_| VerifyShareError)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_for_all_curves;
+
+    fn keys<P>(n: usize) -> (Vec<P::Scalar>, Vec<P>)
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone,
+    {
+        let G: P = ECPoint::generator();
+        let sks: Vec<P::Scalar> = (0..n).map(|_| ECScalar::new_random()).collect();
+        let pks = sks.iter().map(|sk| G.clone() * sk.clone()).collect();
+        (sks, pks)
+    }
+
+    test_for_all_curves!(test_pvss_3_out_of_5);
+    fn test_pvss_3_out_of_5<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Zeroize + Clone,
+    {
+        let (sks, pks) = keys::<P>(5);
+        let secret: P::Scalar = ECScalar::new_random();
+        let pvss = PubliclyVerifiableSS::share(2, &secret, &pks);
+        assert!(pvss.verify(&pks).is_ok());
+        assert!(pvss.verify_feldman(&pks).is_ok());
+
+        let decrypted: Vec<_> = [4, 0, 2]
+            .iter()
+            .map(|i| pvss.decrypt_share(*i, &sks[*i]).unwrap())
+            .collect();
+        for share in &decrypted {
+            assert!(pvss
+                .verify_decrypted_share(share, &pks[share.index])
+                .is_ok());
+        }
+        let G: P = ECPoint::generator();
+        assert!(pvss.reconstruct(&decrypted, &pks).unwrap() == G * secret);
+    }
+
+    test_for_all_curves!(test_pvss_reconstruct_rejects_invalid_shares);
+    fn test_pvss_reconstruct_rejects_invalid_shares<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Zeroize + Clone,
+    {
+        let (sks, pks) = keys::<P>(5);
+        let secret: P::Scalar = ECScalar::new_random();
+        let pvss = PubliclyVerifiableSS::share(2, &secret, &pks);
+        assert_eq!(
+            pvss.decrypt_share(5, &sks[0]).err(),
+            Some(InvalidPartyError)
+        );
+
+        let decrypted: Vec<_> = (0..5)
+            .map(|i| pvss.decrypt_share(i, &sks[i]).unwrap())
+            .collect();
+        let reconstruct = |shares: &[DecryptedShare<P>]| pvss.reconstruct(shares, &pks);
+        assert_eq!(reconstruct(&decrypted[..2]).err(), Some(TooFewPartiesError));
+        let duplicate = [
+            decrypted[0].clone(),
+            decrypted[1].clone(),
+            decrypted[1].clone(),
+        ];
+        assert_eq!(
+            reconstruct(&duplicate).err(),
+            Some(ErrorSS::DuplicatePartyError)
+        );
+        let mut out_of_range = decrypted[..3].to_vec();
+        out_of_range[2].index = 5;
+        assert_eq!(reconstruct(&out_of_range).err(), Some(InvalidPartyError));
+        let mut forged = decrypted[..3].to_vec();
+        forged[2].share = forged[2].share.clone() + P::generator();
+        assert_eq!(reconstruct(&forged).err(), Some(VerifyShareError));
+        assert!(reconstruct(&decrypted[2..]).is_ok());
+    }
+
+    test_for_all_curves!(test_pvss_rejects_wrong_encryption);
+    fn test_pvss_rejects_wrong_encryption<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Zeroize + Clone,
+    {
+        let (_, pks) = keys::<P>(4);
+        let secret: P::Scalar = ECScalar::new_random();
+        let mut pvss = PubliclyVerifiableSS::share(1, &secret, &pks);
+        pvss.encrypted_shares[1] = pvss.encrypted_shares[2].clone();
+        assert!(pvss.verify(&pks).is_err());
+        assert!(pvss.verify_feldman(&pks).is_err());
+    }
+
+    test_for_all_curves!(test_pvss_rejects_high_degree_sharing);
+    fn test_pvss_rejects_high_degree_sharing<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Zeroize + Clone,
+    {
+        let (_, pks) = keys::<P>(5);
+        let secret: P::Scalar = ECScalar::new_random();
+        // shares of a degree 3 polynomial are consistent with their proofs, but not with t = 1
+        let mut pvss = PubliclyVerifiableSS::share(3, &secret, &pks);
+        let honest = PubliclyVerifiableSS::share(1, &secret, &pks);
+        pvss.vss.parameters = honest.vss.parameters.clone();
+        pvss.vss.commitments.truncate(2);
+        assert!(pvss.verify(&pks).is_err());
+        assert!(pvss.verify_feldman(&pks).is_err());
+    }
+
+    test_for_all_curves!(test_pvss_rejects_wrong_decryption);
+    fn test_pvss_rejects_wrong_decryption<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Zeroize + Clone,
+    {
+        let (sks, pks) = keys::<P>(3);
+        let secret: P::Scalar = ECScalar::new_random();
+        let pvss = PubliclyVerifiableSS::share(1, &secret, &pks);
+        let mut decrypted = pvss.decrypt_share(0, &sks[0]).unwrap();
+        assert!(pvss.verify_decrypted_share(&decrypted, &pks[1]).is_err());
+        decrypted.share = decrypted.share.clone() + P::generator();
+        assert!(pvss.verify_decrypted_share(&decrypted, &pks[0]).is_err());
+    }
+}
@@ -0,0 +1,351 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::additive::{check_parties, party_points, AdditiveShare};
+use super::feldman_vss::ShamirSecretSharing;
+use super::polynomial::Polynomial;
+use crate::elliptic::curves::traits::*;
+use crate::ErrorSS::{self, TooFewPartiesError};
+
+/// (t,n) replicated secret sharing, based on  Ronald Cramer, Ivan Damgård, and Yuval Ishai. 2005.
+/// Share Conversion, Pseudorandom Secret-Sharing and Applications to Secure Computation. In TCC 2005.
+///
+/// implementation details: the secret is split additively into one component `r_T` per set `T`
+/// of `t` parties (see [unqualified_sets](Self::unqualified_sets)), and `r_T` is given to every
+/// party outside of `T`. As in [ShamirSecretSharing], any `t+1` parties can reconstruct the
+/// secret, while any `t` learn nothing. The number of components is `C(n, t)`, so the scheme
+/// is only practical for a small number of parties.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ReplicatedSecretSharing {
+    pub threshold: usize,   //t
+    pub share_count: usize, //n
+}
+
+/// Share of party `index`: components `r_T` keyed by position of `T` in
+/// [ReplicatedSecretSharing::unqualified_sets]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ReplicatedShare<S> {
+    pub parameters: ReplicatedSecretSharing,
+    pub index: usize,
+    pub components: BTreeMap<usize, S>,
+}
+
+impl ReplicatedSecretSharing {
+    pub fn reconstruct_limit(&self) -> usize {
+        self.threshold + 1
+    }
+
+    // all subsets of 0..n of size t in lexicographic order
+    pub fn unqualified_sets(&self) -> Vec<Vec<usize>> {
+        let mut sets = vec![];
+        let mut current = vec![];
+        combinations(0, self.share_count, self.threshold, &mut current, &mut sets);
+        sets
+    }
+
+    pub fn share<S>(&self, secret: &S) -> Vec<ReplicatedShare<S>>
+    where
+        S: ECScalar + Clone,
+    {
+        assert!(self.threshold < self.share_count);
+        let sets = self.unqualified_sets();
+        let mut components: Vec<S> = (1..sets.len()).map(|_| S::new_random()).collect();
+        let last = components
+            .iter()
+            .fold(secret.clone(), |acc, r| acc.sub(&r.get_element()));
+        components.push(last);
+
+        (0..self.share_count)
+            .map(|index| ReplicatedShare {
+                parameters: self.clone(),
+                index,
+                components: sets
+                    .iter()
+                    .zip(&components)
+                    .enumerate()
+                    .filter(|(_, (set, _))| !set.contains(&index))
+                    .map(|(k, (_, r))| (k, r.clone()))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    pub fn reconstruct<S>(&self, shares: &[ReplicatedShare<S>]) -> S
+    where
+        S: ECScalar + Clone,
+    {
+        assert!(shares.len() >= self.reconstruct_limit());
+        assert!(shares.iter().all(|s| s.parameters == *self));
+        (0..self.unqualified_sets().len()).fold(S::zero(), |acc, k| {
+            let r = shares
+                .iter()
+                .find_map(|s| s.components.get(&k))
+                .expect("shares of t+1 distinct parties are required");
+            acc + r.clone()
+        })
+    }
+}
+
+impl<S> ReplicatedShare<S>
+where
+    S: ECScalar + Clone,
+{
+    pub fn add(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a.clone() + b.clone())
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a.sub(&b.get_element()))
+    }
+
+    pub fn mul_by_constant(&self, c: &S) -> Self {
+        ReplicatedShare {
+            parameters: self.parameters.clone(),
+            index: self.index,
+            components: self
+                .components
+                .iter()
+                .map(|(k, r)| (*k, r.clone() * c.clone()))
+                .collect(),
+        }
+    }
+
+    // the constant is added to the component of the last set, which every holder updates
+    pub fn add_constant(&self, c: &S) -> Self {
+        let last = self.parameters.unqualified_sets().len() - 1;
+        let mut result = self.clone();
+        if let Some(r) = result.components.get_mut(&last) {
+            *r = r.clone() + c.clone();
+        }
+        result
+    }
+
+    /// Locally converts replicated share into a share of `(t,n)` Shamir sharing of the same
+    /// secret
+    ///
+    /// Every component `r_T` is multiplied by `f_T(index+1)`, where `f_T` is the polynomial of
+    /// degree `t` with `f_T(0) = 1` that vanishes at the points of all parties in `T`.
+    pub fn to_shamir(&self) -> (ShamirSecretSharing, S) {
+        let sets = self.parameters.unqualified_sets();
+        let x = party_points::<S>(&[self.index]).remove(0);
+        let share = self.components.iter().fold(S::zero(), |acc, (k, r)| {
+            let mut points = vec![S::zero()];
+            points.extend(party_points::<S>(&sets[*k]));
+            let f_t = Polynomial::lagrange_basis(&x, 0, &points);
+            acc + r.clone() * f_t
+        });
+        (
+            ShamirSecretSharing {
+                threshold: self.parameters.threshold,
+                share_count: self.parameters.share_count,
+            },
+            share,
+        )
+    }
+
+    /// Locally converts replicated share into an additive share among `parties`
+    ///
+    /// Every component is assigned to the first party in `parties` that holds it. As in
+    /// [AdditiveShare::from_shamir], the resulting share has index equal to position of
+    /// `index` in `parties`. Returns an error if `parties` lists some party more than once, has
+    /// less than `t+1` parties, or doesn't fit into the sharing parameters.
+    pub fn to_additive(&self, parties: &[usize]) -> Result<AdditiveShare<S>, ErrorSS> {
+        let position = check_parties(parties, self.index, self.parameters.share_count)?;
+        if parties.len() < self.parameters.reconstruct_limit() {
+            return Err(TooFewPartiesError);
+        }
+        let sets = self.parameters.unqualified_sets();
+        let value = self
+            .components
+            .iter()
+            .filter(|(k, _)| {
+                let holder = parties.iter().find(|p| !sets[**k].contains(p));
+                holder == Some(&self.index)
+            })
+            .fold(S::zero(), |acc, (_, r)| acc + r.clone());
+        Ok(AdditiveShare {
+            index: position,
+            value,
+        })
+    }
+
+    fn zip_with(&self, other: &Self, f: impl Fn(&S, &S) -> S) -> Self {
+        assert_eq!(self.parameters, other.parameters);
+        assert_eq!(self.index, other.index);
+        ReplicatedShare {
+            parameters: self.parameters.clone(),
+            index: self.index,
+            components: self
+                .components
+                .iter()
+                .map(|(k, a)| (*k, f(a, &other.components[k])))
+                .collect(),
+        }
+    }
+}
+
+fn combinations(
+    start: usize,
+    n: usize,
+    k: usize,
+    current: &mut Vec<usize>,
+    out: &mut Vec<Vec<usize>>,
+) {
+    if current.len() == k {
+        out.push(current.clone());
+        return;
+    }
+    for i in start..n {
+        current.push(i);
+        combinations(i + 1, n, k, current, out);
+        current.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptographic_primitives::secret_sharing::additive::AdditiveSecretSharing;
+    use crate::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+    use crate::test_for_all_curves;
+
+    #[test]
+    fn unqualified_sets_are_all_t_subsets() {
+        let scheme = ReplicatedSecretSharing {
+            threshold: 2,
+            share_count: 4,
+        };
+        let sets = scheme.unqualified_sets();
+        assert_eq!(
+            sets,
+            vec![
+                vec![0, 1],
+                vec![0, 2],
+                vec![0, 3],
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 3]
+            ]
+        );
+    }
+
+    test_for_all_curves!(replicated_sharing_reconstructs);
+    fn replicated_sharing_reconstructs<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone + PartialEq + std::fmt::Debug,
+    {
+        let secret = P::Scalar::new_random();
+        let scheme = ReplicatedSecretSharing {
+            threshold: 2,
+            share_count: 5,
+        };
+        let shares = scheme.share(&secret);
+        // each party misses exactly the components of sets it belongs to
+        assert!(shares.iter().all(|s| s.components.len() == 6));
+
+        let subset = [shares[4].clone(), shares[0].clone(), shares[2].clone()];
+        assert_eq!(scheme.reconstruct(&subset), secret);
+        assert_eq!(scheme.reconstruct(&shares), secret);
+    }
+
+    test_for_all_curves!(replicated_share_arithmetic);
+    fn replicated_share_arithmetic<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone + PartialEq + std::fmt::Debug,
+    {
+        let scheme = ReplicatedSecretSharing {
+            threshold: 1,
+            share_count: 3,
+        };
+        let (a, b, c) = (
+            P::Scalar::new_random(),
+            P::Scalar::new_random(),
+            P::Scalar::new_random(),
+        );
+        let a_shares = scheme.share(&a);
+        let b_shares = scheme.share(&b);
+        let result: Vec<_> = a_shares
+            .iter()
+            .zip(&b_shares)
+            .map(|(a_i, b_i)| a_i.sub(b_i).mul_by_constant(&c).add(a_i).add_constant(&c))
+            .collect();
+        let expected = a.sub(&b.get_element()) * c.clone() + a + c;
+        assert_eq!(scheme.reconstruct(&result[1..]), expected);
+        assert_eq!(scheme.reconstruct(&result[..2]), expected);
+    }
+
+    test_for_all_curves!(replicated_to_shamir);
+    fn replicated_to_shamir<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone + PartialEq + std::fmt::Debug,
+    {
+        let secret = P::Scalar::new_random();
+        let scheme = ReplicatedSecretSharing {
+            threshold: 2,
+            share_count: 4,
+        };
+        let converted: Vec<_> = scheme
+            .share(&secret)
+            .iter()
+            .map(|s| s.to_shamir())
+            .collect();
+        let vss = VerifiableSS::<P> {
+            parameters: converted[0].0.clone(),
+            commitments: vec![],
+        };
+        let shares: Vec<_> = converted.into_iter().map(|(_, s)| s).collect();
+        assert_eq!(vss.reconstruct(&[0, 1, 2], &shares[..3]), secret);
+        assert_eq!(vss.reconstruct(&[1, 2, 3], &shares[1..]), secret);
+        // shares lie on a single polynomial of degree t
+        let points = party_points::<P::Scalar>(&[0, 1, 2, 3]);
+        let poly = Polynomial::interpolate(&points, &shares);
+        assert_eq!(poly.degree(), Some(2));
+    }
+
+    test_for_all_curves!(replicated_to_additive);
+    fn replicated_to_additive<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone + PartialEq + std::fmt::Debug,
+    {
+        let secret = P::Scalar::new_random();
+        let scheme = ReplicatedSecretSharing {
+            threshold: 1,
+            share_count: 4,
+        };
+        let shares = scheme.share(&secret);
+        let parties = [3, 1];
+        let additive: Vec<_> = parties
+            .iter()
+            .map(|i| shares[*i].to_additive(&parties).unwrap())
+            .collect();
+        assert_eq!(
+            AdditiveSecretSharing { share_count: 2 }.reconstruct(&additive),
+            secret
+        );
+        assert_eq!(
+            shares[1].to_additive(&[1, 3, 1]),
+            Err(ErrorSS::DuplicatePartyError)
+        );
+        assert_eq!(shares[1].to_additive(&[1]), Err(TooFewPartiesError));
+        assert_eq!(
+            shares[1].to_additive(&[1, 4]),
+            Err(ErrorSS::InvalidPartyError)
+        );
+        assert_eq!(
+            shares[1].to_additive(&[0, 3]),
+            Err(ErrorSS::InvalidPartyError)
+        );
+    }
+}
@@ -9,10 +9,16 @@
 //! Bob chooses at random a secret "b" and sends to Alice B = bG.
 //! Both parties can compute a joint secret: C = aB = bA = abG which cannot be computed by
 //! a man in the middle attacker.
+//!
+//! Ed25519 key pairs can also agree on a key with standard X25519 peers (RFC 7748), see
+//! [compute_x25519_shared_secret].
 
 use serde::{Deserialize, Serialize};
 
+use crate::elliptic::curves::ed25519::Ed25519Point;
 use crate::elliptic::curves::traits::*;
+use crate::elliptic::curves::x25519;
+use crate::ErrorKey;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EcKeyPair<P: ECPoint> {
@@ -102,6 +108,28 @@ where
     other_share_public_share.clone() * local_share.secret_share.clone()
 }
 
+impl EcKeyPair<Ed25519Point> {
+    /// X25519 public key (Montgomery u-coordinate of `public_share`) to be sent to X25519 peers
+    pub fn x25519_public_key(&self) -> [u8; 32] {
+        self.public_share.to_montgomery_u()
+    }
+}
+
+/// Computes the shared secret with an X25519 peer
+///
+/// Result equals to what the peer computes as `x25519(peer_secret, local_share.x25519_public_key())`.
+/// Returns error if `other_public_key` has small order, as the result wouldn't depend on the
+/// secret share. Small order component of other keys is cleared, see [x25519::mul_u].
+pub fn compute_x25519_shared_secret(
+    local_share: &EcKeyPair<Ed25519Point>,
+    other_public_key: &[u8; 32],
+) -> Result<[u8; 32], ErrorKey> {
+    if x25519::is_small_order_u(other_public_key) {
+        return Err(ErrorKey::InvalidPublicKey);
+    }
+    Ok(x25519::mul_u(&local_share.secret_share, other_public_key))
+}
+
 #[cfg(test)]
 mod tests {
     use std::fmt::Debug;
@@ -166,4 +194,45 @@ mod tests {
             g * secret_party_2
         );
     }
+
+    #[test]
+    fn test_x25519_interoperability() {
+        use crate::elliptic::curves::ed25519::GE;
+
+        let (party_one_first_message, ec_key_pair_party1) = Party1FirstMessage::<GE>::first();
+        // peer is a standard X25519 implementation
+        let peer_secret = [0x42u8; 32];
+        let peer_public_key = x25519::x25519_public_key(peer_secret);
+
+        let shared_secret =
+            compute_x25519_shared_secret(&ec_key_pair_party1, &peer_public_key).unwrap();
+        assert_eq!(
+            shared_secret,
+            x25519::x25519(peer_secret, ec_key_pair_party1.x25519_public_key())
+        );
+        assert_eq!(
+            ec_key_pair_party1.x25519_public_key(),
+            party_one_first_message.public_share.to_montgomery_u()
+        );
+
+        // adding a point of order 8 to the peer's key doesn't change the shared secret
+        let mut torsion_u = [0u8; 32];
+        torsion_u.copy_from_slice(
+            &hex::decode("5f9c95bca3508c24b1d0b1559c83ef5b04445cc4581c8e86d8224eddd09f1157")
+                .unwrap(),
+        );
+        let torsion = GE::from_montgomery_u(&torsion_u, false).unwrap();
+        let peer_point = GE::from_montgomery_u(&peer_public_key, false).unwrap();
+        let mixed_order_key = (peer_point + torsion).to_montgomery_u();
+        assert_eq!(
+            compute_x25519_shared_secret(&ec_key_pair_party1, &mixed_order_key).unwrap(),
+            shared_secret
+        );
+
+        // u = 0 and u = 1 have small order
+        let mut small_order = [0u8; 32];
+        assert!(compute_x25519_shared_secret(&ec_key_pair_party1, &small_order).is_err());
+        small_order[0] = 1;
+        assert!(compute_x25519_shared_secret(&ec_key_pair_party1, &small_order).is_err());
+    }
 }
@@ -0,0 +1,322 @@
+//! Baby Jubjub: twisted Edwards curve defined over the scalar field of BN254 ([EIP-2494])
+//!
+//! Coordinates are the ones of EIP-2494 (`168700x^2 + y^2 = 1 + 168696x^2y^2`), generator of
+//! the prime order subgroup is `Base8`. Points are packed the same way as in circomlib:
+//! little-endian `y` coordinate with the most significant bit set if `x > (p-1)/2`.
+//!
+//! Arithmetic is done by arkworks on the isomorphic curve with `a = 1`.
+//!
+//! [EIP-2494]: https://eips.ethereum.org/EIPS/eip-2494
+
+use super::fixed_base::{select_array, CtLookup, WindowTable};
+use super::prime_group::{impl_prime_group_curve, PrimeGroupBackend};
+use super::traits::{try_and_increment, BasePointTable, ECPoint, ECScalar};
+use crate::arithmetic::traits::*;
+use crate::BigInt;
+use crate::ErrorKey;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ed_on_bn254::{EdwardsAffine, EdwardsProjective, Fq, Fr};
+use ark_ff::{BigInt as ArkBigInt, BigInteger, Field, PrimeField, Zero};
+use subtle::Choice;
+
+pub type SK = Fr;
+pub type PK = EdwardsProjective;
+
+/// Coefficient `a` of EIP-2494 curve equation
+const COEFF_A: u64 = 168700;
+/// Coefficient `d` of EIP-2494 curve equation
+const COEFF_D: u64 = 168696;
+
+/// `Base8` point of EIP-2494
+const GENERATOR_X: &str =
+    "5299619240641551281634865583518297030282874472190772894086521144482721001553";
+const GENERATOR_Y: &str =
+    "16950150798460657717958625567821834550301663161624707787222815936182638968203";
+
+lazy_static::lazy_static! {
+    static ref BASE_FIELD_MODULUS: BigInt = BigInt::from_bytes(&Fq::MODULUS.to_bytes_be());
+    /// `sqrt(a)` maps `x` coordinate of EIP-2494 curve to `x` of the curve used by arkworks
+    static ref SQRT_A: Fq = Fq::from(COEFF_A).sqrt().expect("a is a square");
+    static ref SQRT_A_INV: Fq = SQRT_A.inverse().expect("a is not zero");
+    static ref GENERATOR: PK = BabyJubjub::point_from_coordinates(
+        &BigInt::from_str_radix(GENERATOR_X, 10).unwrap(),
+        &BigInt::from_str_radix(GENERATOR_Y, 10).unwrap(),
+    )
+    .expect("Base8 is in the prime order subgroup");
+}
+
+struct BabyJubjub;
+
+impl PrimeGroupBackend for BabyJubjub {
+    type Scalar = SK;
+    type Point = PK;
+
+    fn curve_order() -> BigInt {
+        BigInt::from_bytes(&Fr::MODULUS.to_bytes_be())
+    }
+
+    fn scalar_from_bytes_be(bytes: &[u8; 32]) -> SK {
+        Fr::from_be_bytes_mod_order(bytes)
+    }
+
+    fn scalar_to_bytes_be(scalar: &SK) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&scalar.into_bigint().to_bytes_be());
+        bytes
+    }
+
+    fn scalar_invert(scalar: &SK) -> Option<SK> {
+        scalar.inverse()
+    }
+
+    fn scalar_zero() -> SK {
+        Fr::zero()
+    }
+
+    fn identity() -> PK {
+        EdwardsProjective::zero()
+    }
+
+    fn generator() -> PK {
+        *GENERATOR
+    }
+
+    fn point_to_bytes(point: &PK) -> [u8; 32] {
+        let (x, y) = eip2494_coordinates(&point.into_affine());
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&y.to_bytes_be_padded(32));
+        bytes.reverse();
+        if x > (&*BASE_FIELD_MODULUS - BigInt::one()) / BigInt::from(2) {
+            bytes[31] |= 0x80;
+        }
+        bytes
+    }
+
+    fn point_from_bytes(bytes: &[u8; 32]) -> Option<PK> {
+        let point = decode_on_curve(bytes)?;
+        if point.is_in_correct_subgroup_assuming_on_curve() {
+            Some(point.into_group())
+        } else {
+            None
+        }
+    }
+
+    fn coordinates(point: &PK) -> Option<(BigInt, BigInt)> {
+        Some(eip2494_coordinates(&point.into_affine()))
+    }
+
+    fn point_from_coordinates(x: &BigInt, y: &BigInt) -> Option<PK> {
+        let point =
+            EdwardsAffine::new_unchecked(fq_from_big_int(x)? * *SQRT_A, fq_from_big_int(y)?);
+        if point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve() {
+            Some(point.into_group())
+        } else {
+            None
+        }
+    }
+
+    fn hash_to_point(message: &[u8]) -> PK {
+        try_and_increment(message, |digest| {
+            let mut encoding = [0u8; 32];
+            encoding.copy_from_slice(digest);
+            let point = decode_on_curve(&encoding)?.mul_by_cofactor_to_group();
+            if point.is_zero() {
+                None
+            } else {
+                Some(point)
+            }
+        })
+    }
+}
+
+/// Table entries are Montgomery limbs of `x`, `y`, `t` and `z` coordinates
+impl CtLookup for PK {
+    type Entry = [[u64; 4]; 4];
+
+    fn to_entry(&self) -> Self::Entry {
+        [(self.x.0).0, (self.y.0).0, (self.t.0).0, (self.z.0).0]
+    }
+
+    fn from_entry(entry: &Self::Entry) -> PK {
+        let [x, y, t, z] = entry;
+        let coordinate = |limbs: &[u64; 4]| Fq::new_unchecked(ArkBigInt(*limbs));
+        PK::new_unchecked(coordinate(x), coordinate(y), coordinate(t), coordinate(z))
+    }
+
+    fn conditional_select(a: &Self::Entry, b: &Self::Entry, choice: Choice) -> Self::Entry {
+        let mut selected = *a;
+        for (s, b) in selected.iter_mut().zip(b.iter()) {
+            *s = select_array(s, b, choice);
+        }
+        selected
+    }
+}
+
+impl_prime_group_curve! {
+    backend: BabyJubjub,
+    scalar: BabyJubjubScalar,
+    point: BabyJubjubPoint,
+    table: BabyJubjubTable,
+}
+
+fn fq_to_big_int(x: &Fq) -> BigInt {
+    BigInt::from_bytes(&x.into_bigint().to_bytes_be())
+}
+
+fn fq_from_big_int(n: &BigInt) -> Option<Fq> {
+    if *n < BigInt::zero() || *n >= *BASE_FIELD_MODULUS {
+        return None;
+    }
+    Some(Fq::from_be_bytes_mod_order(&n.to_bytes()))
+}
+
+fn eip2494_coordinates(point: &EdwardsAffine) -> (BigInt, BigInt) {
+    (
+        fq_to_big_int(&(point.x * *SQRT_A_INV)),
+        fq_to_big_int(&point.y),
+    )
+}
+
+/// Unpacks a point without checking that it belongs to the prime order subgroup
+fn decode_on_curve(bytes: &[u8; 32]) -> Option<EdwardsAffine> {
+    let x_is_negative = bytes[31] & 0x80 != 0;
+    let mut y_bytes = *bytes;
+    y_bytes[31] &= 0x7f;
+    let y = fq_from_big_int(&BigInt::from_bytes_le(&y_bytes))?;
+
+    // x^2 = (1 - y^2) / (a - d y^2)
+    let y2 = y.square();
+    let denominator = (Fq::from(COEFF_A) - Fq::from(COEFF_D) * y2).inverse()?;
+    let mut x = ((Fq::one() - y2) * denominator).sqrt()?;
+    let half_modulus = (&*BASE_FIELD_MODULUS - BigInt::one()) / BigInt::from(2);
+    let x_int = fq_to_big_int(&x);
+    if x_int.is_zero() && x_is_negative {
+        return None;
+    }
+    if (x_int > half_modulus) != x_is_negative {
+        x = -x;
+    }
+    Some(EdwardsAffine::new_unchecked(x * *SQRT_A, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BabyJubjubPoint, FE, GE};
+    use crate::arithmetic::traits::*;
+    use crate::elliptic::curves::traits::{ECPoint, ECScalar};
+    use crate::BigInt;
+
+    fn point(x: &str, y: &str) -> GE {
+        GE::from_coor(
+            &BigInt::from_str_radix(x, 10).unwrap(),
+            &BigInt::from_str_radix(y, 10).unwrap(),
+        )
+    }
+
+    #[test]
+    fn serialize_deserialize_point() {
+        let point = GE::generator() * FE::new_random();
+        let s = serde_json::to_string(&point).expect("Failed in serialization");
+        let des_point: GE = serde_json::from_str(&s).expect("Failed in deserialization");
+        assert_eq!(des_point, point);
+        let encoded = bincode::serialize(&point).unwrap();
+        let decoded: BabyJubjubPoint = bincode::deserialize(encoded.as_slice()).unwrap();
+        assert_eq!(point, decoded);
+    }
+
+    #[test]
+    fn serialize_deserialize_scalar() {
+        let scalar: FE = ECScalar::new_random();
+        let s = serde_json::to_string(&scalar).expect("Failed in serialization");
+        let des_scalar: FE = serde_json::from_str(&s).expect("Failed in deserialization");
+        assert_eq!(des_scalar, scalar);
+    }
+
+    #[test]
+    fn eip2494_vectors() {
+        let l = BigInt::from_str_radix(
+            "2736030358979909402780800718157159386076813972158567259200215660948447373041",
+            10,
+        )
+        .unwrap();
+        assert_eq!(FE::q(), l);
+
+        // addition
+        let p1 = point(
+            "17777552123799933955779906779655732241715742912184938656739573121738514868268",
+            "2626589144620713026669568689430873010625803728049924121243784502389097019475",
+        );
+        let p2 = point(
+            "16540640123574156134436876038791482806971768689494387082833631921987005038935",
+            "20819045374670962167435360035096875258406992893633759881276124905556507972311",
+        );
+        let sum = point(
+            "7916061937171219682591368294088513039687205273691143098332585753343424131937",
+            "14035240266687799601661095864649209771790948434046947201833777492504781204499",
+        );
+        assert_eq!(p1 + p2, sum);
+
+        // doubling
+        let double = point(
+            "6890855772600357754907169075114257697580319025794532037257385534741338397365",
+            "4338620300185947561074059802482547481416142213883829469920100239455078257889",
+        );
+        assert_eq!(p1 + p1, double);
+        assert_eq!(p1 * <FE as ECScalar>::from(&BigInt::from(2)), double);
+    }
+
+    #[test]
+    fn generator_is_base8() {
+        let g = GE::generator();
+        assert_eq!(
+            g.x_coor().unwrap().to_str_radix(10),
+            "5299619240641551281634865583518297030282874472190772894086521144482721001553"
+        );
+        assert_eq!(
+            g.y_coor().unwrap().to_str_radix(10),
+            "16950150798460657717958625567821834550301663161624707787222815936182638968203"
+        );
+        // x > (p-1)/2, so sign bit is set
+        assert_eq!(
+            hex::encode(g.pk_to_key_slice()),
+            "8b7d2d877a253c4b7733e1b91f05e0fcedf96bd11c2e572549b2a0f703727925"
+        );
+        assert_eq!(GE::from_bytes(&g.pk_to_key_slice()).unwrap(), g);
+    }
+
+    #[test]
+    fn rejects_points_out_of_subgroup() {
+        // generator of the whole curve (of order 8l) isn't in the prime order subgroup
+        let packed_g =
+            hex::decode("010000fc647df850245c6e1e12fa0c4a175660a06d11146e0a684cb89c13190c")
+                .unwrap();
+        assert!(GE::from_bytes(&packed_g).is_err());
+        // y >= p
+        assert!(GE::from_bytes(&[0x7f; 32]).is_err());
+        assert!(GE::from_bytes(&[0; 33]).is_err());
+    }
+
+    #[test]
+    fn identity_encoding() {
+        let zero = GE::generator() * FE::zero();
+        assert_eq!(zero.x_coor(), Some(BigInt::zero()));
+        assert_eq!(zero.y_coor(), Some(BigInt::one()));
+        assert_eq!(GE::from_bytes(&zero.pk_to_key_slice()).unwrap(), zero);
+    }
+
+    #[test]
+    fn base_point2_nothing_up_my_sleeve() {
+        let g = GE::generator();
+        let h = GE::hash_to_curve(&g.pk_to_key_slice());
+        assert_eq!(GE::base_point2(), h);
+        assert_ne!(h, g);
+        assert_eq!(GE::from_bytes(&h.pk_to_key_slice()).unwrap(), h);
+    }
+
+    #[test]
+    fn test_invert() {
+        let a: FE = ECScalar::new_random();
+        let one: FE = ECScalar::from(&BigInt::one());
+        assert_eq!(a * a.invert(), one);
+    }
+}
@@ -21,6 +21,7 @@ use pairing_plus::serdes::SerDes;
 use pairing_plus::EncodedPoint;
 use pairing_plus::{CurveAffine, CurveProjective, Engine};
 use sha2::Sha256;
+use subtle::{Choice, ConditionallySelectable};
 
 use serde::de::{self, Error, MapAccess, SeqAccess, Visitor};
 use serde::ser::SerializeStruct;
@@ -31,6 +32,7 @@ pub type SK = <pairing_plus::bls12_381::Bls12 as ScalarEngine>::Fr;
 pub type PK = <pairing_plus::bls12_381::Bls12 as Engine>::G1Affine;
 
 use crate::arithmetic::traits::*;
+use crate::cryptographic_primitives::secret_sharing::polynomial::FftScalar;
 use crate::BigInt;
 use crate::ErrorKey::{self};
 
@@ -38,6 +40,8 @@ use std::ptr;
 use std::sync::atomic;
 use zeroize::Zeroize;
 
+use crate::elliptic::curves::fixed_base::{CtLookup, WindowTable};
+use crate::elliptic::curves::traits::BasePointTable;
 use crate::elliptic::curves::traits::ECPoint;
 use crate::elliptic::curves::traits::ECScalar;
 #[cfg(feature = "merkle")]
@@ -93,21 +97,10 @@ impl ECScalar for FieldScalar {
 
     fn from(n: &BigInt) -> FieldScalar {
         let n_mod = BigInt::modulus(n, &FE::q());
-        let mut v = BigInt::to_bytes(&n_mod);
-        let mut bytes_array: [u8; SECRET_KEY_SIZE];
-        if v.len() < SECRET_KEY_SIZE {
-            let mut template = vec![0; SECRET_KEY_SIZE - v.len()];
-            template.extend_from_slice(&v);
-            v = template;
-        }
-        bytes_array = [0; SECRET_KEY_SIZE];
-        let bytes = &v[..SECRET_KEY_SIZE];
-        bytes_array.copy_from_slice(&bytes);
-
-        // bytes_array.reverse();
+        let bytes = n_mod.to_bytes_be_padded(SECRET_KEY_SIZE);
 
         let mut repr = SK::default().into_repr();
-        repr.read_be(Cursor::new(&bytes_array[..])).unwrap();
+        repr.read_be(Cursor::new(&bytes[..])).unwrap();
         FieldScalar {
             purpose: "from_big_int",
             fe: Fr::from_repr(repr).unwrap(),
@@ -184,6 +177,17 @@ impl ECScalar for FieldScalar {
     }
 }
 
+impl FftScalar for FieldScalar {
+    const TWO_ADICITY: u32 = Fr::S;
+
+    fn root_of_unity() -> FieldScalar {
+        FieldScalar {
+            purpose: "root_of_unity",
+            fe: Fr::root_of_unity(),
+        }
+    }
+}
+
 impl Debug for FieldScalar {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -290,22 +294,10 @@ impl ECPoint for G1Point {
     type SecretKey = SK;
     type PublicKey = PK;
     type Scalar = FieldScalar;
+    type Table = G1Table;
 
     fn base_point2() -> G1Point {
-        const BASE_POINT2: [u8; 96] = [
-            10, 18, 122, 36, 178, 251, 236, 31, 139, 88, 242, 163, 21, 198, 168, 208, 122, 195,
-            135, 122, 7, 153, 197, 255, 160, 0, 89, 138, 39, 245, 105, 108, 99, 113, 78, 70, 130,
-            172, 183, 57, 170, 180, 39, 32, 173, 29, 238, 62, 13, 166, 109, 90, 181, 17, 76, 247,
-            26, 155, 130, 211, 18, 42, 235, 137, 225, 184, 210, 140, 54, 83, 233, 228, 226, 70,
-            194, 50, 55, 116, 229, 2, 115, 227, 223, 31, 165, 39, 191, 209, 49, 127, 106, 196, 123,
-            71, 70, 243,
-        ];
-        let mut point = G1Uncompressed::empty();
-        point.as_mut().copy_from_slice(&BASE_POINT2);
-        G1Point {
-            purpose: "base_ge2",
-            ge: point.into_affine().expect("invalid base_point"),
-        }
+        *BASE_POINT2
     }
 
     fn generator() -> G1Point {
@@ -400,6 +392,108 @@ impl ECPoint for G1Point {
         // TODO
         unimplemented!();
     }
+
+    fn hash_to_curve(message: &[u8]) -> G1Point {
+        // resolves to inherent method G1Point::hash_to_curve
+        G1Point::hash_to_curve(message)
+    }
+
+    fn mul_generator(scalar: &FieldScalar) -> G1Point {
+        GENERATOR_TABLE.mul(scalar)
+    }
+
+    fn mul_base_point2(scalar: &FieldScalar) -> G1Point {
+        BASE_POINT2_TABLE.mul(scalar)
+    }
+}
+
+/// Table entries are uncompressed encodings, which are decoded without subgroup check
+impl CtLookup for G1 {
+    type Entry = G1Uncompressed;
+
+    fn to_entry(&self) -> G1Uncompressed {
+        G1Uncompressed::from_affine(self.into_affine())
+    }
+
+    fn from_entry(entry: &G1Uncompressed) -> G1 {
+        entry
+            .into_affine_unchecked()
+            .expect("table entries are valid points")
+            .into_projective()
+    }
+
+    fn conditional_select(
+        a: &G1Uncompressed,
+        b: &G1Uncompressed,
+        choice: Choice,
+    ) -> G1Uncompressed {
+        let mut selected = *a;
+        for (s, b) in selected.as_mut().iter_mut().zip(b.as_ref()) {
+            s.conditional_assign(b, choice);
+        }
+        selected
+    }
+}
+
+pub struct G1Table {
+    base: G1Point,
+    table: WindowTable<G1>,
+}
+
+impl BasePointTable<G1Point> for G1Table {
+    fn new(base: &G1Point) -> G1Table {
+        let table = WindowTable::new(
+            base.ge.into_projective(),
+            FieldScalar::q().bit_length(),
+            |a, b| {
+                let mut sum = *a;
+                sum.add_assign(b);
+                sum
+            },
+            |a| {
+                let mut neg = *a;
+                neg.negate();
+                neg
+            },
+        );
+        G1Table { base: *base, table }
+    }
+
+    fn base(&self) -> G1Point {
+        self.base
+    }
+
+    fn mul(&self, scalar: &FieldScalar) -> G1Point {
+        let mut point = G1::zero();
+        for entry in self.table.lookup(&scalar.to_big_int()) {
+            point.add_assign(&entry);
+        }
+        G1Point {
+            purpose: "mul_table",
+            ge: point.into_affine(),
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref BASE_POINT2: G1Point = {
+        const BASE_POINT2: [u8; 96] = [
+            10, 18, 122, 36, 178, 251, 236, 31, 139, 88, 242, 163, 21, 198, 168, 208, 122, 195,
+            135, 122, 7, 153, 197, 255, 160, 0, 89, 138, 39, 245, 105, 108, 99, 113, 78, 70, 130,
+            172, 183, 57, 170, 180, 39, 32, 173, 29, 238, 62, 13, 166, 109, 90, 181, 17, 76, 247,
+            26, 155, 130, 211, 18, 42, 235, 137, 225, 184, 210, 140, 54, 83, 233, 228, 226, 70,
+            194, 50, 55, 116, 229, 2, 115, 227, 223, 31, 165, 39, 191, 209, 49, 127, 106, 196, 123,
+            71, 70, 243,
+        ];
+        let mut point = G1Uncompressed::empty();
+        point.as_mut().copy_from_slice(&BASE_POINT2);
+        G1Point {
+            purpose: "base_ge2",
+            ge: point.into_affine().expect("invalid base_point"),
+        }
+    };
+    static ref GENERATOR_TABLE: G1Table = G1Table::new(&G1Point::generator());
+    static ref BASE_POINT2_TABLE: G1Table = G1Table::new(&BASE_POINT2);
 }
 
 impl From<pairing_plus::bls12_381::G1Affine> for G1Point {
@@ -24,6 +24,7 @@ use pairing_plus::serdes::SerDes;
 use pairing_plus::EncodedPoint;
 use pairing_plus::{CurveAffine, CurveProjective, Engine};
 use sha2::Sha256;
+use subtle::{Choice, ConditionallySelectable};
 
 use serde::de::{self, Error, MapAccess, SeqAccess, Visitor};
 use serde::ser::SerializeStruct;
@@ -41,6 +42,8 @@ use std::ptr;
 use std::sync::atomic;
 use zeroize::Zeroize;
 
+use crate::elliptic::curves::fixed_base::{CtLookup, WindowTable};
+use crate::elliptic::curves::traits::BasePointTable;
 use crate::elliptic::curves::traits::ECPoint;
 use crate::elliptic::curves::traits::ECScalar;
 #[cfg(feature = "merkle")]
@@ -96,21 +99,10 @@ impl ECScalar for FieldScalar {
 
     fn from(n: &BigInt) -> FieldScalar {
         let n_mod = BigInt::modulus(n, &FE::q());
-        let mut v = BigInt::to_bytes(&n_mod);
-        let mut bytes_array: [u8; SECRET_KEY_SIZE];
-        if v.len() < SECRET_KEY_SIZE {
-            let mut template = vec![0; SECRET_KEY_SIZE - v.len()];
-            template.extend_from_slice(&v);
-            v = template;
-        }
-        bytes_array = [0; SECRET_KEY_SIZE];
-        let bytes = &v[..SECRET_KEY_SIZE];
-        bytes_array.copy_from_slice(&bytes);
-
-        // bytes_array.reverse();
+        let bytes = n_mod.to_bytes_be_padded(SECRET_KEY_SIZE);
 
         let mut repr = SK::default().into_repr();
-        repr.read_be(Cursor::new(&bytes_array[..])).unwrap();
+        repr.read_be(Cursor::new(&bytes[..])).unwrap();
         FieldScalar {
             purpose: "from_big_int",
             fe: Fr::from_repr(repr).unwrap(),
@@ -292,28 +284,10 @@ impl ECPoint for G2Point {
     type SecretKey = SK;
     type PublicKey = PK;
     type Scalar = FieldScalar;
+    type Table = G2Table;
 
     fn base_point2() -> G2Point {
-        const BASE_POINT2: [u8; 192] = [
-            0, 204, 165, 72, 21, 96, 36, 119, 117, 242, 58, 55, 105, 140, 136, 76, 180, 140, 92,
-            212, 55, 3, 146, 72, 120, 181, 37, 205, 165, 221, 144, 86, 57, 124, 16, 19, 160, 215,
-            21, 251, 236, 99, 91, 147, 237, 113, 223, 70, 14, 223, 81, 150, 157, 235, 107, 225,
-            151, 227, 119, 53, 195, 46, 25, 54, 57, 158, 156, 122, 75, 152, 119, 51, 137, 131, 43,
-            34, 68, 24, 24, 210, 18, 75, 36, 20, 232, 76, 38, 84, 44, 112, 213, 217, 192, 122, 177,
-            186, 5, 113, 25, 229, 205, 55, 65, 191, 147, 1, 212, 194, 151, 141, 43, 223, 68, 185,
-            183, 66, 163, 62, 96, 92, 36, 209, 216, 40, 16, 132, 231, 104, 179, 248, 189, 53, 154,
-            106, 83, 159, 5, 54, 86, 87, 45, 68, 52, 247, 3, 90, 148, 187, 234, 213, 114, 244, 52,
-            137, 201, 13, 165, 57, 217, 190, 150, 103, 223, 193, 129, 198, 47, 86, 122, 196, 22,
-            200, 123, 89, 178, 216, 11, 238, 155, 106, 172, 125, 164, 95, 2, 136, 132, 137, 27,
-            184, 237, 169,
-        ];
-
-        let mut point = G2Uncompressed::empty();
-        point.as_mut().copy_from_slice(&BASE_POINT2);
-        G2Point {
-            purpose: "base_ge2",
-            ge: point.into_affine().expect("invalid base_point"),
-        }
+        *BASE_POINT2
     }
 
     fn generator() -> G2Point {
@@ -409,6 +383,114 @@ impl ECPoint for G2Point {
         // TODO
         unimplemented!();
     }
+
+    fn hash_to_curve(message: &[u8]) -> G2Point {
+        // resolves to inherent method G2Point::hash_to_curve
+        G2Point::hash_to_curve(message)
+    }
+
+    fn mul_generator(scalar: &FieldScalar) -> G2Point {
+        GENERATOR_TABLE.mul(scalar)
+    }
+
+    fn mul_base_point2(scalar: &FieldScalar) -> G2Point {
+        BASE_POINT2_TABLE.mul(scalar)
+    }
+}
+
+/// Table entries are uncompressed encodings, which are decoded without subgroup check
+impl CtLookup for G2 {
+    type Entry = G2Uncompressed;
+
+    fn to_entry(&self) -> G2Uncompressed {
+        G2Uncompressed::from_affine(self.into_affine())
+    }
+
+    fn from_entry(entry: &G2Uncompressed) -> G2 {
+        entry
+            .into_affine_unchecked()
+            .expect("table entries are valid points")
+            .into_projective()
+    }
+
+    fn conditional_select(
+        a: &G2Uncompressed,
+        b: &G2Uncompressed,
+        choice: Choice,
+    ) -> G2Uncompressed {
+        let mut selected = *a;
+        for (s, b) in selected.as_mut().iter_mut().zip(b.as_ref()) {
+            s.conditional_assign(b, choice);
+        }
+        selected
+    }
+}
+
+pub struct G2Table {
+    base: G2Point,
+    table: WindowTable<G2>,
+}
+
+impl BasePointTable<G2Point> for G2Table {
+    fn new(base: &G2Point) -> G2Table {
+        let table = WindowTable::new(
+            base.ge.into_projective(),
+            FieldScalar::q().bit_length(),
+            |a, b| {
+                let mut sum = *a;
+                sum.add_assign(b);
+                sum
+            },
+            |a| {
+                let mut neg = *a;
+                neg.negate();
+                neg
+            },
+        );
+        G2Table { base: *base, table }
+    }
+
+    fn base(&self) -> G2Point {
+        self.base
+    }
+
+    fn mul(&self, scalar: &FieldScalar) -> G2Point {
+        let mut point = G2::zero();
+        for entry in self.table.lookup(&scalar.to_big_int()) {
+            point.add_assign(&entry);
+        }
+        G2Point {
+            purpose: "mul_table",
+            ge: point.into_affine(),
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref BASE_POINT2: G2Point = {
+        const BASE_POINT2: [u8; 192] = [
+            0, 204, 165, 72, 21, 96, 36, 119, 117, 242, 58, 55, 105, 140, 136, 76, 180, 140, 92,
+            212, 55, 3, 146, 72, 120, 181, 37, 205, 165, 221, 144, 86, 57, 124, 16, 19, 160, 215,
+            21, 251, 236, 99, 91, 147, 237, 113, 223, 70, 14, 223, 81, 150, 157, 235, 107, 225,
+            151, 227, 119, 53, 195, 46, 25, 54, 57, 158, 156, 122, 75, 152, 119, 51, 137, 131, 43,
+            34, 68, 24, 24, 210, 18, 75, 36, 20, 232, 76, 38, 84, 44, 112, 213, 217, 192, 122, 177,
+            186, 5, 113, 25, 229, 205, 55, 65, 191, 147, 1, 212, 194, 151, 141, 43, 223, 68, 185,
+            183, 66, 163, 62, 96, 92, 36, 209, 216, 40, 16, 132, 231, 104, 179, 248, 189, 53, 154,
+            106, 83, 159, 5, 54, 86, 87, 45, 68, 52, 247, 3, 90, 148, 187, 234, 213, 114, 244, 52,
+            137, 201, 13, 165, 57, 217, 190, 150, 103, 223, 193, 129, 198, 47, 86, 122, 196, 22,
+            200, 123, 89, 178, 216, 11, 238, 155, 106, 172, 125, 164, 95, 2, 136, 132, 137, 27,
+            184, 237, 169,
+        ];
+
+        let mut point = G2Uncompressed::empty();
+        point.as_mut().copy_from_slice(&BASE_POINT2);
+        G2Point {
+            purpose: "base_ge2",
+            ge: point.into_affine().expect("invalid base_point"),
+        }
+    };
+    static ref GENERATOR_TABLE: G2Table = G2Table::new(&G2Point::generator());
+    static ref BASE_POINT2_TABLE: G2Table = G2Table::new(&BASE_POINT2);
 }
 
 impl From<pairing_plus::bls12_381::G2Affine> for G2Point {
@@ -0,0 +1,804 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+pub const SECRET_KEY_SIZE: usize = 32;
+pub const FIELD_SIZE: usize = 32;
+/// Size of point encoded as in EIP-196: `x || y`, identity is encoded as zeroes
+pub const UNCOMPRESSED_SIZE: usize = 2 * FIELD_SIZE;
+/// Size of compressed point: `2` or `3` depending on parity of `y`, followed by `x`
+pub const COMPRESSED_SIZE: usize = FIELD_SIZE + 1;
+
+use std::fmt;
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use sha3::{Digest, Keccak256};
+use substrate_bn::arith::U256;
+use substrate_bn::{AffineG1, Fq, Fr, Group, G1};
+use subtle::Choice;
+
+use serde::de::{self, Visitor};
+use serde::ser::{Serialize, Serializer};
+use serde::{Deserialize, Deserializer};
+
+pub type SK = Fr;
+pub type PK = G1;
+
+use crate::arithmetic::traits::*;
+use crate::cryptographic_primitives::secret_sharing::polynomial::FftScalar;
+use crate::BigInt;
+use crate::ErrorKey::{self};
+
+use std::ptr;
+use std::sync::atomic;
+use zeroize::Zeroize;
+
+use crate::elliptic::curves::fixed_base::{select_array, CtLookup, WindowTable};
+use crate::elliptic::curves::traits::BasePointTable;
+use crate::elliptic::curves::traits::ECPoint;
+use crate::elliptic::curves::traits::ECScalar;
+
+#[derive(Clone, Copy)]
+pub struct FieldScalar {
+    purpose: &'static str,
+    fe: SK,
+}
+#[derive(Clone, Copy)]
+pub struct G1Point {
+    purpose: &'static str,
+    ge: PK,
+}
+pub type GE = G1Point;
+pub type FE = FieldScalar;
+
+impl Zeroize for FieldScalar {
+    fn zeroize(&mut self) {
+        unsafe { ptr::write_volatile(self, FE::zero()) };
+        atomic::fence(atomic::Ordering::SeqCst);
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+    }
+}
+
+impl ECScalar for FieldScalar {
+    type SecretKey = SK;
+
+    fn new_random() -> FieldScalar {
+        let rnd_bn = BigInt::sample_below(&FE::q());
+        ECScalar::from(&rnd_bn)
+    }
+
+    fn zero() -> FieldScalar {
+        FieldScalar {
+            purpose: "zero",
+            fe: Fr::zero(),
+        }
+    }
+
+    fn get_element(&self) -> SK {
+        self.fe
+    }
+    fn set_element(&mut self, element: SK) {
+        self.fe = element
+    }
+
+    fn from(n: &BigInt) -> FieldScalar {
+        let n_mod = BigInt::modulus(n, &FE::q());
+        let bytes = n_mod.to_bytes_be_padded(SECRET_KEY_SIZE);
+        let repr = U256::from_slice(&bytes).expect("scalar is exactly 32 bytes long");
+        FieldScalar {
+            purpose: "from_big_int",
+            fe: Fr::new(repr).expect("scalar is reduced modulo q"),
+        }
+    }
+
+    fn to_big_int(&self) -> BigInt {
+        let mut bytes = [0u8; SECRET_KEY_SIZE];
+        self.fe
+            .into_u256()
+            .to_big_endian(&mut bytes)
+            .expect("buffer is exactly 32 bytes long");
+        BigInt::from_bytes(&bytes)
+    }
+
+    fn q() -> BigInt {
+        BigInt::from_hex("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001")
+            .unwrap()
+    }
+
+    fn add(&self, other: &SK) -> FieldScalar {
+        FieldScalar {
+            purpose: "add",
+            fe: self.fe + *other,
+        }
+    }
+
+    fn mul(&self, other: &SK) -> FieldScalar {
+        FieldScalar {
+            purpose: "mul",
+            fe: self.fe * *other,
+        }
+    }
+
+    fn sub(&self, other: &SK) -> FieldScalar {
+        FieldScalar {
+            purpose: "sub",
+            fe: self.fe - *other,
+        }
+    }
+
+    fn invert(&self) -> FieldScalar {
+        FieldScalar {
+            purpose: "inverse",
+            fe: self.fe.inverse().unwrap(),
+        }
+    }
+}
+
+impl FftScalar for FieldScalar {
+    const TWO_ADICITY: u32 = 28;
+
+    /// `5^((q - 1) / 2^28)`, `5` being the smallest generator of the multiplicative group
+    fn root_of_unity() -> FieldScalar {
+        let root =
+            BigInt::from_hex("2a3c09f0a58a7e8500e0a7eb8ef62abc402d111e41112ed49bd61b6e725b19f0")
+                .unwrap();
+        FieldScalar {
+            purpose: "root_of_unity",
+            ..ECScalar::from(&root)
+        }
+    }
+}
+
+impl PartialEq for FieldScalar {
+    fn eq(&self, other: &FieldScalar) -> bool {
+        self.get_element() == other.get_element()
+    }
+}
+
+impl Mul<FieldScalar> for FieldScalar {
+    type Output = FieldScalar;
+    fn mul(self, other: FieldScalar) -> FieldScalar {
+        ECScalar::mul(&self, &other.get_element())
+    }
+}
+
+impl<'o> Mul<&'o FieldScalar> for FieldScalar {
+    type Output = FieldScalar;
+    fn mul(self, other: &'o FieldScalar) -> FieldScalar {
+        ECScalar::mul(&self, &other.get_element())
+    }
+}
+
+impl Add<FieldScalar> for FieldScalar {
+    type Output = FieldScalar;
+    fn add(self, other: FieldScalar) -> FieldScalar {
+        ECScalar::add(&self, &other.get_element())
+    }
+}
+
+impl<'o> Add<&'o FieldScalar> for FieldScalar {
+    type Output = FieldScalar;
+    fn add(self, other: &'o FieldScalar) -> FieldScalar {
+        ECScalar::add(&self, &other.get_element())
+    }
+}
+
+impl Sub<FieldScalar> for FieldScalar {
+    type Output = FieldScalar;
+    fn sub(self, other: FieldScalar) -> FieldScalar {
+        ECScalar::sub(&self, &other.get_element())
+    }
+}
+
+impl<'o> Sub<&'o FieldScalar> for FieldScalar {
+    type Output = FieldScalar;
+    fn sub(self, other: &'o FieldScalar) -> FieldScalar {
+        ECScalar::sub(&self, &other.get_element())
+    }
+}
+
+impl Serialize for FieldScalar {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!(
+            "{:0>width$}",
+            self.to_big_int().to_hex(),
+            width = 2 * SECRET_KEY_SIZE
+        ))
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldScalar {
+    fn deserialize<D>(deserializer: D) -> Result<FieldScalar, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(Bn254ScalarVisitor)
+    }
+}
+
+struct Bn254ScalarVisitor;
+
+impl<'de> Visitor<'de> for Bn254ScalarVisitor {
+    type Value = FieldScalar;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("bn254")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<FieldScalar, E> {
+        let v = BigInt::from_hex(s).map_err(E::custom)?;
+        Ok(ECScalar::from(&v))
+    }
+}
+
+/// Big-endian encoding of base field element
+pub(super) fn fq_to_bytes(x: Fq) -> [u8; FIELD_SIZE] {
+    let mut bytes = [0u8; FIELD_SIZE];
+    x.to_big_endian(&mut bytes)
+        .expect("buffer is exactly 32 bytes long");
+    bytes
+}
+
+/// Parses big-endian encoding of base field element, rejects values that aren't reduced
+pub(super) fn fq_from_bytes(bytes: &[u8]) -> Result<Fq, ErrorKey> {
+    Fq::from_slice(bytes).map_err(|_| ErrorKey::InvalidPublicKey)
+}
+
+pub(super) fn fq_to_big_int(x: Fq) -> BigInt {
+    BigInt::from_bytes(&fq_to_bytes(x))
+}
+
+pub(super) fn fq_from_big_int(x: &BigInt) -> Result<Fq, ErrorKey> {
+    if x.bit_length() > 8 * FIELD_SIZE {
+        return Err(ErrorKey::InvalidPublicKey);
+    }
+    fq_from_bytes(&x.to_bytes_be_padded(FIELD_SIZE))
+}
+
+impl Debug for FieldScalar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Scalar {{ purpose: {:?}, fe: {:?} }}",
+            self.purpose, self.fe
+        )
+    }
+}
+
+impl Debug for G1Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Point {{ purpose: {:?}, bytes: {:?} }}",
+            self.purpose,
+            hex::encode(self.pk_to_key_slice())
+        )
+    }
+}
+
+impl PartialEq for G1Point {
+    fn eq(&self, other: &G1Point) -> bool {
+        self.get_element() == other.get_element()
+    }
+}
+
+impl Zeroize for G1Point {
+    fn zeroize(&mut self) {
+        unsafe { ptr::write_volatile(self, GE::generator()) };
+        atomic::fence(atomic::Ordering::SeqCst);
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+    }
+}
+
+impl ECPoint for G1Point {
+    type SecretKey = SK;
+    type PublicKey = PK;
+    type Scalar = FieldScalar;
+    type Table = G1Table;
+
+    fn base_point2() -> G1Point {
+        *BASE_POINT2
+    }
+
+    fn generator() -> G1Point {
+        G1Point {
+            purpose: "base_fe",
+            ge: G1::one(),
+        }
+    }
+
+    fn get_element(&self) -> PK {
+        self.ge
+    }
+
+    fn x_coor(&self) -> Option<BigInt> {
+        AffineG1::from_jacobian(self.ge).map(|p| fq_to_big_int(p.x()))
+    }
+
+    fn y_coor(&self) -> Option<BigInt> {
+        AffineG1::from_jacobian(self.ge).map(|p| fq_to_big_int(p.y()))
+    }
+
+    /// Point at infinity is represented by zero
+    fn bytes_compressed_to_big_int(&self) -> BigInt {
+        match AffineG1::from_jacobian(self.ge) {
+            Some(p) => {
+                let y_is_odd = fq_to_bytes(p.y())[FIELD_SIZE - 1] & 1 == 1;
+                let mut bytes = vec![if y_is_odd { 3 } else { 2 }];
+                bytes.extend_from_slice(&fq_to_bytes(p.x()));
+                BigInt::from_bytes(&bytes)
+            }
+            None => BigInt::zero(),
+        }
+    }
+
+    /// Takes either EIP-196 encoding (`x || y`, zeroes for point at infinity) or compressed point
+    fn from_bytes(bytes: &[u8]) -> Result<G1Point, ErrorKey> {
+        let ge = match bytes.len() {
+            UNCOMPRESSED_SIZE => {
+                let x = fq_from_bytes(&bytes[..FIELD_SIZE])?;
+                let y = fq_from_bytes(&bytes[FIELD_SIZE..])?;
+                if x.is_zero() && y.is_zero() {
+                    G1::zero()
+                } else {
+                    AffineG1::new(x, y)
+                        .map_err(|_| ErrorKey::InvalidPublicKey)?
+                        .into()
+                }
+            }
+            COMPRESSED_SIZE => {
+                G1::from_compressed(bytes).map_err(|_| ErrorKey::InvalidPublicKey)?
+            }
+            _ => return Err(ErrorKey::InvalidPublicKey),
+        };
+        Ok(G1Point {
+            purpose: "from_bytes",
+            ge,
+        })
+    }
+
+    /// Encodes point as in EIP-196, i.e. as expected by `ecAdd`, `ecMul` and `ecPairing`
+    /// precompiles
+    fn pk_to_key_slice(&self) -> Vec<u8> {
+        let mut bytes = vec![0u8; UNCOMPRESSED_SIZE];
+        if let Some(p) = AffineG1::from_jacobian(self.ge) {
+            bytes[..FIELD_SIZE].copy_from_slice(&fq_to_bytes(p.x()));
+            bytes[FIELD_SIZE..].copy_from_slice(&fq_to_bytes(p.y()));
+        }
+        bytes
+    }
+
+    fn scalar_mul(&self, fe: &SK) -> G1Point {
+        G1Point {
+            purpose: "scalar_point_mul",
+            ge: self.ge * *fe,
+        }
+    }
+
+    fn add_point(&self, other: &PK) -> G1Point {
+        G1Point {
+            purpose: "combine",
+            ge: self.ge + *other,
+        }
+    }
+
+    fn sub_point(&self, other: &PK) -> G1Point {
+        G1Point {
+            purpose: "sub",
+            ge: self.ge - *other,
+        }
+    }
+
+    fn from_coor(x: &BigInt, y: &BigInt) -> G1Point {
+        let x = fq_from_big_int(x).expect("x is not a field element");
+        let y = fq_from_big_int(y).expect("y is not a field element");
+        G1Point {
+            purpose: "from_coor",
+            ge: AffineG1::new(x, y).expect("point is not on curve").into(),
+        }
+    }
+
+    fn hash_to_curve(message: &[u8]) -> G1Point {
+        // resolves to inherent method G1Point::hash_to_curve
+        G1Point::hash_to_curve(message)
+    }
+
+    fn mul_generator(scalar: &FieldScalar) -> G1Point {
+        GENERATOR_TABLE.mul(scalar)
+    }
+
+    fn mul_base_point2(scalar: &FieldScalar) -> G1Point {
+        BASE_POINT2_TABLE.mul(scalar)
+    }
+}
+
+/// Limbs of canonical representation of field element, constant-time selectable
+pub(super) fn fq_to_limbs(fq: Fq) -> [u128; 2] {
+    fq.into_u256().0
+}
+
+pub(super) fn fq_from_limbs(limbs: &[u128; 2]) -> Fq {
+    Fq::from_u256(U256(*limbs)).expect("limbs are taken from a field element")
+}
+
+/// Table entries are limbs of jacobian coordinates
+impl CtLookup for G1 {
+    type Entry = [[u128; 2]; 3];
+
+    fn to_entry(&self) -> Self::Entry {
+        [self.x(), self.y(), self.z()].map(fq_to_limbs)
+    }
+
+    fn from_entry(entry: &Self::Entry) -> G1 {
+        let [x, y, z] = entry;
+        G1::new(fq_from_limbs(x), fq_from_limbs(y), fq_from_limbs(z))
+    }
+
+    fn conditional_select(a: &Self::Entry, b: &Self::Entry, choice: Choice) -> Self::Entry {
+        let mut selected = *a;
+        for (s, b) in selected.iter_mut().zip(b.iter()) {
+            *s = select_array(s, b, choice);
+        }
+        selected
+    }
+}
+
+pub struct G1Table {
+    base: G1Point,
+    table: WindowTable<G1>,
+}
+
+impl BasePointTable<G1Point> for G1Table {
+    fn new(base: &G1Point) -> G1Table {
+        let table = WindowTable::new(
+            base.ge,
+            FieldScalar::q().bit_length(),
+            |a, b| *a + *b,
+            |a| -*a,
+        );
+        G1Table { base: *base, table }
+    }
+
+    fn base(&self) -> G1Point {
+        self.base
+    }
+
+    fn mul(&self, scalar: &FieldScalar) -> G1Point {
+        let point = self
+            .table
+            .lookup(&scalar.to_big_int())
+            .fold(G1::zero(), |acc, entry| acc + entry);
+        G1Point {
+            purpose: "mul_table",
+            ge: point,
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Hash of EIP-196 encoding of the generator, so nobody knows its discrete logarithm
+    static ref BASE_POINT2: G1Point = G1Point {
+        purpose: "base_ge2",
+        ..G1Point::hash_to_curve(&G1Point::generator().pk_to_key_slice())
+    };
+    static ref GENERATOR_TABLE: G1Table = G1Table::new(&G1Point::generator());
+    static ref BASE_POINT2_TABLE: G1Table = G1Table::new(&BASE_POINT2);
+}
+
+impl From<G1> for G1Point {
+    fn from(point: PK) -> Self {
+        G1Point {
+            purpose: "from_point",
+            ge: point,
+        }
+    }
+}
+
+impl Mul<FieldScalar> for G1Point {
+    type Output = G1Point;
+    fn mul(self, other: FieldScalar) -> G1Point {
+        self.scalar_mul(&other.get_element())
+    }
+}
+
+impl<'o> Mul<&'o FieldScalar> for G1Point {
+    type Output = G1Point;
+    fn mul(self, other: &'o FieldScalar) -> G1Point {
+        self.scalar_mul(&other.get_element())
+    }
+}
+
+impl<'o> Mul<&'o FieldScalar> for &'o G1Point {
+    type Output = G1Point;
+    fn mul(self, other: &'o FieldScalar) -> G1Point {
+        self.scalar_mul(&other.get_element())
+    }
+}
+
+impl Add<G1Point> for G1Point {
+    type Output = G1Point;
+    fn add(self, other: G1Point) -> G1Point {
+        self.add_point(&other.get_element())
+    }
+}
+
+impl<'o> Add<&'o G1Point> for G1Point {
+    type Output = G1Point;
+    fn add(self, other: &'o G1Point) -> G1Point {
+        self.add_point(&other.get_element())
+    }
+}
+
+impl<'o> Add<&'o G1Point> for &'o G1Point {
+    type Output = G1Point;
+    fn add(self, other: &'o G1Point) -> G1Point {
+        self.add_point(&other.get_element())
+    }
+}
+
+impl Sub<G1Point> for G1Point {
+    type Output = G1Point;
+    fn sub(self, other: G1Point) -> G1Point {
+        self.sub_point(&other.get_element())
+    }
+}
+
+impl<'o> Sub<&'o G1Point> for G1Point {
+    type Output = G1Point;
+    fn sub(self, other: &'o G1Point) -> G1Point {
+        self.sub_point(&other.get_element())
+    }
+}
+
+impl<'o> Sub<&'o G1Point> for &'o G1Point {
+    type Output = G1Point;
+    fn sub(self, other: &'o G1Point) -> G1Point {
+        self.sub_point(&other.get_element())
+    }
+}
+
+impl Neg for G1Point {
+    type Output = Self;
+    fn neg(mut self) -> Self {
+        self.ge = -self.ge;
+        self.purpose = "negated";
+        self
+    }
+}
+
+impl Serialize for G1Point {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&hex::encode(self.pk_to_key_slice()))
+    }
+}
+
+impl<'de> Deserialize<'de> for G1Point {
+    fn deserialize<D>(deserializer: D) -> Result<G1Point, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(Bn254G1PointVisitor)
+    }
+}
+
+struct Bn254G1PointVisitor;
+
+impl<'de> Visitor<'de> for Bn254G1PointVisitor {
+    type Value = G1Point;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Bn254G1Point")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<G1Point, E> {
+        let bytes = hex::decode(s).map_err(E::custom)?;
+        G1Point::from_bytes(&bytes).map_err(|_| E::custom("failed to parse g1 point"))
+    }
+}
+
+impl G1Point {
+    /// Converts message to G1 point.
+    ///
+    /// Try-and-increment as implemented by common Solidity BN254 libraries: starts with
+    /// `x = keccak256(message) mod p` and increments `x` until `x^3 + 3` is a square, then takes
+    /// `y = (x^3 + 3)^((p + 1) / 4)`. Not constant time.
+    pub fn hash_to_curve(message: &[u8]) -> Self {
+        let digest = Keccak256::digest(message);
+        let p = BigInt::from_bytes(&fq_to_bytes(-Fq::one())) + BigInt::one();
+        let mut x = fq_from_big_int(&BigInt::modulus(&BigInt::from_bytes(&digest), &p))
+            .expect("reduced modulo p");
+        loop {
+            let y_squared = x * x * x + G1::b();
+            if let Some(y) = y_squared.sqrt() {
+                return G1Point {
+                    purpose: "hash_to_curve",
+                    ge: AffineG1::new(x, y).expect("point is on curve").into(),
+                };
+            }
+            x = x + Fq::one();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{G1Point, FE, GE};
+    use crate::arithmetic::traits::*;
+    use crate::elliptic::curves::traits::ECPoint;
+    use crate::elliptic::curves::traits::ECScalar;
+    use crate::BigInt;
+    use crate::ErrorKey;
+
+    fn point(hex_str: &str) -> GE {
+        GE::from_bytes(&hex::decode(hex_str).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_serdes_pk() {
+        for pk in &[
+            GE::generator(),
+            GE::base_point2(),
+            GE::generator() * FE::zero(),
+        ] {
+            let s = serde_json::to_string(pk).expect("Failed in serialization");
+            let des_pk: GE = serde_json::from_str(&s).expect("Failed in deserialization");
+            assert_eq!(des_pk, *pk);
+
+            let bin = bincode::serialize(pk).unwrap();
+            let decoded: G1Point = bincode::deserialize(bin.as_slice()).unwrap();
+            assert_eq!(decoded, *pk);
+        }
+    }
+
+    #[test]
+    fn test_serdes_scalar() {
+        let scalar: FE = ECScalar::from(&BigInt::from(123456));
+        let s = serde_json::to_string(&scalar).expect("Failed in serialization");
+        assert_eq!(s, format!("\"{:0>64}\"", "1e240"));
+        let des: FE = serde_json::from_str(&s).expect("Failed in deserialization");
+        assert_eq!(des, scalar);
+    }
+
+    #[test]
+    fn test_eip196_encoding() {
+        let g = GE::generator();
+        let mut expected = [0u8; 64];
+        expected[31] = 1;
+        expected[63] = 2;
+        assert_eq!(g.pk_to_key_slice(), expected.to_vec());
+        assert_eq!(
+            (GE::generator() * FE::zero()).pk_to_key_slice(),
+            vec![0u8; 64]
+        );
+
+        // (1, 3) isn't on the curve
+        expected[63] = 3;
+        assert_eq!(
+            GE::from_bytes(&expected).unwrap_err(),
+            ErrorKey::InvalidPublicKey
+        );
+        // coordinates must be reduced modulo p
+        let mut not_reduced = g.pk_to_key_slice();
+        not_reduced[..32].copy_from_slice(
+            &hex::decode("30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd48")
+                .unwrap(),
+        );
+        assert!(GE::from_bytes(&not_reduced).is_err());
+        assert!(GE::from_bytes(&[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_compressed_encoding() {
+        for _ in 0..10 {
+            let p = GE::generator() * FE::new_random();
+            let compressed = BigInt::to_bytes(&p.bytes_compressed_to_big_int());
+            assert_eq!(compressed.len(), 33);
+            assert_eq!(GE::from_bytes(&compressed).unwrap(), p);
+        }
+    }
+
+    #[test]
+    fn test_ec_add_vector() {
+        // go-ethereum bn256Add test vector "chfast1"
+        let a = point(
+            "18b18acfb4c2c30276db5411368e7185b311dd124691610c5d3b74034e093dc9\
+             063c909c4720840cb5134cb9f59fa749755796819658d32efc0d288198f37266",
+        );
+        let b = point(
+            "07c2b7f58a84bd6145f00c9c2bc0bb1a187f20ff2c92963a88019e7c6a014eed\
+             06614e20c147e940f2d70da3f74c9a17df361706a4485c742bd6788478fa17d7",
+        );
+        let sum = point(
+            "2243525c5efd4b9c3d3c45ac0ca3fe4dd85e830a4ce6b65fa1eeaee202839703\
+             301d1d33be6da8e509df21cc35964723180eed7532537db9ae5e7d48f195c915",
+        );
+        assert_eq!(a + b, sum);
+    }
+
+    #[test]
+    fn test_ec_mul_vector() {
+        // go-ethereum bn256ScalarMul test vector "chfast1"
+        let a = point(
+            "2bd3e6d0f3b142924f5ca7b49ce5b9d54c4703d7ae5648e61d02268b1a0a9fb7\
+             21611ce0a6af85915e2f1d70300909ce2e49dfad4a4619c8390cae66cefdb204",
+        );
+        let k: FE = ECScalar::from(&BigInt::from_hex("11138ce750fa15c2").unwrap());
+        let product = point(
+            "070a8d6a982153cae4be29d434e8faef8a47b274a053f5a4ee2a6c9c13c31e5c\
+             031b8ce914eba3a9ffb989f9cdd5b0f01943074bf4f0f315690ec3cec6981afc",
+        );
+        assert_eq!(a * k, product);
+    }
+
+    #[test]
+    fn test_hash_to_curve_vector() {
+        // x = keccak256("abc") mod p is not a valid x-coordinate, try-and-increment moves on
+        let h = GE::hash_to_curve(b"abc");
+        assert_eq!(
+            hex::encode(h.pk_to_key_slice()),
+            "1d9f1708091409260f8435f1a5477e0a29507c51d1f2d5a9b0246978c8b06efe\
+             04fc97f7d6ed51fdf2920eea84eb1be09aa77322c1111593cde486d72188402f"
+        );
+        let h = GE::hash_to_curve(b"");
+        assert_eq!(
+            hex::encode(h.pk_to_key_slice()),
+            "04410c360230a295b13d66d8d6c1a24a86fb0c0e28bafd068b78a7a8fb91af55\
+             03d24e04de149099b8a34d87fffbf964f27c7ad7e56cb75eaa7874368ec572bc"
+        );
+    }
+
+    #[test]
+    fn test_base_point2() {
+        let g = GE::generator();
+        assert_eq!(GE::base_point2(), GE::hash_to_curve(&g.pk_to_key_slice()));
+        assert!(GE::base_point2() != g);
+    }
+
+    #[test]
+    fn test_from_coor() {
+        let p = GE::generator() * FE::new_random();
+        let q = GE::from_coor(&p.x_coor().unwrap(), &p.y_coor().unwrap());
+        assert_eq!(p, q);
+        assert!((GE::generator() * FE::zero()).x_coor().is_none());
+    }
+
+    #[test]
+    fn test_minus_point() {
+        let a: FE = ECScalar::new_random();
+        let b: FE = ECScalar::new_random();
+        let base: GE = ECPoint::generator();
+        let point_ab1 = base * (a - b);
+        let point_ab2 = (base * a).sub_point(&(base * b).get_element());
+        assert_eq!(point_ab1, point_ab2);
+        assert_eq!(-(base * a), base * (FE::zero() - a));
+    }
+
+    #[test]
+    fn test_invert() {
+        let a: FE = ECScalar::new_random();
+        let a_bn = a.to_big_int();
+        let a_inv = a.invert();
+        let a_inv_bn_1 = BigInt::mod_inv(&a_bn, &FE::q()).unwrap();
+        assert_eq!(a_inv_bn_1, a_inv.to_big_int());
+    }
+
+    #[test]
+    fn test_scalar_reduction() {
+        let q: FE = ECScalar::from(&FE::q());
+        assert_eq!(q, FE::zero());
+        let q_minus_one: FE = ECScalar::from(&(FE::q() - BigInt::one()));
+        assert_eq!(q_minus_one.to_big_int(), FE::q() - BigInt::one());
+        let one: FE = ECScalar::from(&BigInt::one());
+        assert_eq!(q_minus_one + one, FE::zero());
+    }
+}
@@ -0,0 +1,575 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+/// Size of point encoded as in EIP-197: `x_im || x_re || y_im || y_re`, identity is encoded as
+/// zeroes
+pub const UNCOMPRESSED_SIZE: usize = 4 * FIELD_SIZE;
+/// Size of compressed point in substrate-bn format: `10` or `11` depending on `y`, followed by
+/// `x_im * p + x_re`
+pub const COMPRESSED_SIZE: usize = 2 * FIELD_SIZE + 1;
+
+use std::fmt;
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use sha2::{Digest, Sha512};
+use substrate_bn::{AffineG2, Fq, Fq2, Group, G2};
+use subtle::Choice;
+
+use serde::de::{self, Visitor};
+use serde::ser::{Serialize, Serializer};
+use serde::{Deserialize, Deserializer};
+
+pub use super::g1::{FieldScalar, FE, SK};
+pub type PK = G2;
+
+use super::g1::{
+    fq_from_big_int, fq_from_bytes, fq_from_limbs, fq_to_big_int, fq_to_bytes, fq_to_limbs,
+    FIELD_SIZE,
+};
+use crate::arithmetic::traits::*;
+use crate::BigInt;
+use crate::ErrorKey::{self};
+
+use std::ptr;
+use std::sync::atomic;
+use zeroize::Zeroize;
+
+use crate::elliptic::curves::fixed_base::{select_array, CtLookup, WindowTable};
+use crate::elliptic::curves::traits::ECPoint;
+use crate::elliptic::curves::traits::ECScalar;
+use crate::elliptic::curves::traits::{try_and_increment, BasePointTable};
+
+#[derive(Clone, Copy)]
+pub struct G2Point {
+    purpose: &'static str,
+    ge: PK,
+}
+pub type GE = G2Point;
+
+/// EIP-197 encoding of `Fq2` element: imaginary part goes first
+fn fq2_to_bytes(x: Fq2) -> Vec<u8> {
+    let mut bytes = fq_to_bytes(x.imaginary()).to_vec();
+    bytes.extend_from_slice(&fq_to_bytes(x.real()));
+    bytes
+}
+
+fn fq2_from_bytes(bytes: &[u8]) -> Result<Fq2, ErrorKey> {
+    let im = fq_from_bytes(&bytes[..FIELD_SIZE])?;
+    let re = fq_from_bytes(&bytes[FIELD_SIZE..])?;
+    Ok(Fq2::new(re, im))
+}
+
+fn fq_modulus() -> BigInt {
+    fq_to_big_int(-Fq::one()) + BigInt::one()
+}
+
+/// `x_im * p + x_re`, the way substrate-bn orders `Fq2` elements
+fn fq2_to_big_int(x: Fq2) -> BigInt {
+    fq_to_big_int(x.imaginary()) * fq_modulus() + fq_to_big_int(x.real())
+}
+
+impl Debug for G2Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Point {{ purpose: {:?}, bytes: {:?} }}",
+            self.purpose,
+            hex::encode(self.pk_to_key_slice())
+        )
+    }
+}
+
+impl PartialEq for G2Point {
+    fn eq(&self, other: &G2Point) -> bool {
+        self.get_element() == other.get_element()
+    }
+}
+
+impl Zeroize for G2Point {
+    fn zeroize(&mut self) {
+        unsafe { ptr::write_volatile(self, GE::generator()) };
+        atomic::fence(atomic::Ordering::SeqCst);
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+    }
+}
+
+impl ECPoint for G2Point {
+    type SecretKey = SK;
+    type PublicKey = PK;
+    type Scalar = FieldScalar;
+    type Table = G2Table;
+
+    fn base_point2() -> G2Point {
+        *BASE_POINT2
+    }
+
+    fn generator() -> G2Point {
+        G2Point {
+            purpose: "base_fe",
+            ge: G2::one(),
+        }
+    }
+
+    fn get_element(&self) -> PK {
+        self.ge
+    }
+
+    /// Returns EIP-197 encoding of x coordinate (`x_im || x_re`) as a number
+    fn x_coor(&self) -> Option<BigInt> {
+        AffineG2::from_jacobian(self.ge).map(|p| BigInt::from_bytes(&fq2_to_bytes(p.x())))
+    }
+
+    /// Returns EIP-197 encoding of y coordinate (`y_im || y_re`) as a number
+    fn y_coor(&self) -> Option<BigInt> {
+        AffineG2::from_jacobian(self.ge).map(|p| BigInt::from_bytes(&fq2_to_bytes(p.y())))
+    }
+
+    /// Point at infinity is represented by zero
+    fn bytes_compressed_to_big_int(&self) -> BigInt {
+        match AffineG2::from_jacobian(self.ge) {
+            Some(p) => {
+                let y_is_greater = fq2_to_big_int(p.y()) > fq2_to_big_int(-p.y());
+                let mut bytes = vec![if y_is_greater { 11 } else { 10 }];
+                bytes.extend(fq2_to_big_int(p.x()).to_bytes_be_padded(2 * FIELD_SIZE));
+                BigInt::from_bytes(&bytes)
+            }
+            None => BigInt::zero(),
+        }
+    }
+
+    /// Takes either EIP-197 encoding (zeroes for point at infinity) or compressed point. Points
+    /// outside of the prime order subgroup are rejected.
+    fn from_bytes(bytes: &[u8]) -> Result<G2Point, ErrorKey> {
+        let ge = match bytes.len() {
+            UNCOMPRESSED_SIZE => {
+                let x = fq2_from_bytes(&bytes[..2 * FIELD_SIZE])?;
+                let y = fq2_from_bytes(&bytes[2 * FIELD_SIZE..])?;
+                if x.is_zero() && y.is_zero() {
+                    G2::zero()
+                } else {
+                    AffineG2::new(x, y)
+                        .map_err(|_| ErrorKey::InvalidPublicKey)?
+                        .into()
+                }
+            }
+            COMPRESSED_SIZE => {
+                G2::from_compressed(bytes).map_err(|_| ErrorKey::InvalidPublicKey)?
+            }
+            _ => return Err(ErrorKey::InvalidPublicKey),
+        };
+        Ok(G2Point {
+            purpose: "from_bytes",
+            ge,
+        })
+    }
+
+    /// Encodes point as in EIP-197, i.e. as expected by `ecPairing` precompile
+    fn pk_to_key_slice(&self) -> Vec<u8> {
+        match AffineG2::from_jacobian(self.ge) {
+            Some(p) => {
+                let mut bytes = fq2_to_bytes(p.x());
+                bytes.extend(fq2_to_bytes(p.y()));
+                bytes
+            }
+            None => vec![0u8; UNCOMPRESSED_SIZE],
+        }
+    }
+
+    fn scalar_mul(&self, fe: &SK) -> G2Point {
+        G2Point {
+            purpose: "scalar_point_mul",
+            ge: self.ge * *fe,
+        }
+    }
+
+    fn add_point(&self, other: &PK) -> G2Point {
+        G2Point {
+            purpose: "combine",
+            ge: self.ge + *other,
+        }
+    }
+
+    fn sub_point(&self, other: &PK) -> G2Point {
+        G2Point {
+            purpose: "sub",
+            ge: self.ge - *other,
+        }
+    }
+
+    /// Takes coordinates in the form returned by [x_coor](Self::x_coor) and
+    /// [y_coor](Self::y_coor)
+    fn from_coor(x: &BigInt, y: &BigInt) -> G2Point {
+        let mut bytes = x.to_bytes_be_padded(2 * FIELD_SIZE);
+        bytes.extend(y.to_bytes_be_padded(2 * FIELD_SIZE));
+        G2Point::from_bytes(&bytes).expect("point is not on curve")
+    }
+
+    fn hash_to_curve(message: &[u8]) -> G2Point {
+        // resolves to inherent method G2Point::hash_to_curve
+        G2Point::hash_to_curve(message)
+    }
+
+    fn mul_generator(scalar: &FieldScalar) -> G2Point {
+        GENERATOR_TABLE.mul(scalar)
+    }
+
+    fn mul_base_point2(scalar: &FieldScalar) -> G2Point {
+        BASE_POINT2_TABLE.mul(scalar)
+    }
+}
+
+/// Table entries are limbs of real and imaginary parts of jacobian coordinates
+impl CtLookup for G2 {
+    type Entry = [[u128; 2]; 6];
+
+    fn to_entry(&self) -> Self::Entry {
+        let (x, y, z) = (self.x(), self.y(), self.z());
+        [
+            x.real(),
+            x.imaginary(),
+            y.real(),
+            y.imaginary(),
+            z.real(),
+            z.imaginary(),
+        ]
+        .map(fq_to_limbs)
+    }
+
+    fn from_entry(entry: &Self::Entry) -> G2 {
+        let coordinate = |real, imaginary| Fq2::new(fq_from_limbs(real), fq_from_limbs(imaginary));
+        G2::new(
+            coordinate(&entry[0], &entry[1]),
+            coordinate(&entry[2], &entry[3]),
+            coordinate(&entry[4], &entry[5]),
+        )
+    }
+
+    fn conditional_select(a: &Self::Entry, b: &Self::Entry, choice: Choice) -> Self::Entry {
+        let mut selected = *a;
+        for (s, b) in selected.iter_mut().zip(b.iter()) {
+            *s = select_array(s, b, choice);
+        }
+        selected
+    }
+}
+
+pub struct G2Table {
+    base: G2Point,
+    table: WindowTable<G2>,
+}
+
+impl BasePointTable<G2Point> for G2Table {
+    fn new(base: &G2Point) -> G2Table {
+        let table = WindowTable::new(
+            base.ge,
+            FieldScalar::q().bit_length(),
+            |a, b| *a + *b,
+            |a| -*a,
+        );
+        G2Table { base: *base, table }
+    }
+
+    fn base(&self) -> G2Point {
+        self.base
+    }
+
+    fn mul(&self, scalar: &FieldScalar) -> G2Point {
+        let point = self
+            .table
+            .lookup(&scalar.to_big_int())
+            .fold(G2::zero(), |acc, entry| acc + entry);
+        G2Point {
+            purpose: "mul_table",
+            ge: point,
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Hash of EIP-197 encoding of the generator, so nobody knows its discrete logarithm
+    static ref BASE_POINT2: G2Point = G2Point {
+        purpose: "base_ge2",
+        ..G2Point::hash_to_curve(&G2Point::generator().pk_to_key_slice())
+    };
+    static ref GENERATOR_TABLE: G2Table = G2Table::new(&G2Point::generator());
+    static ref BASE_POINT2_TABLE: G2Table = G2Table::new(&BASE_POINT2);
+    /// Cofactor of G2, i.e. `2p - q`
+    static ref COFACTOR: BigInt = BigInt::from_str_radix(
+        "21888242871839275222246405745257275088844257914179612981679871602714643921549",
+        10,
+    )
+    .unwrap();
+}
+
+impl From<G2> for G2Point {
+    fn from(point: PK) -> Self {
+        G2Point {
+            purpose: "from_point",
+            ge: point,
+        }
+    }
+}
+
+impl Mul<FieldScalar> for G2Point {
+    type Output = G2Point;
+    fn mul(self, other: FieldScalar) -> G2Point {
+        self.scalar_mul(&other.get_element())
+    }
+}
+
+impl<'o> Mul<&'o FieldScalar> for G2Point {
+    type Output = G2Point;
+    fn mul(self, other: &'o FieldScalar) -> G2Point {
+        self.scalar_mul(&other.get_element())
+    }
+}
+
+impl<'o> Mul<&'o FieldScalar> for &'o G2Point {
+    type Output = G2Point;
+    fn mul(self, other: &'o FieldScalar) -> G2Point {
+        self.scalar_mul(&other.get_element())
+    }
+}
+
+impl Add<G2Point> for G2Point {
+    type Output = G2Point;
+    fn add(self, other: G2Point) -> G2Point {
+        self.add_point(&other.get_element())
+    }
+}
+
+impl<'o> Add<&'o G2Point> for G2Point {
+    type Output = G2Point;
+    fn add(self, other: &'o G2Point) -> G2Point {
+        self.add_point(&other.get_element())
+    }
+}
+
+impl<'o> Add<&'o G2Point> for &'o G2Point {
+    type Output = G2Point;
+    fn add(self, other: &'o G2Point) -> G2Point {
+        self.add_point(&other.get_element())
+    }
+}
+
+impl Sub<G2Point> for G2Point {
+    type Output = G2Point;
+    fn sub(self, other: G2Point) -> G2Point {
+        self.sub_point(&other.get_element())
+    }
+}
+
+impl<'o> Sub<&'o G2Point> for G2Point {
+    type Output = G2Point;
+    fn sub(self, other: &'o G2Point) -> G2Point {
+        self.sub_point(&other.get_element())
+    }
+}
+
+impl<'o> Sub<&'o G2Point> for &'o G2Point {
+    type Output = G2Point;
+    fn sub(self, other: &'o G2Point) -> G2Point {
+        self.sub_point(&other.get_element())
+    }
+}
+
+impl Neg for G2Point {
+    type Output = Self;
+    fn neg(mut self) -> Self {
+        self.ge = -self.ge;
+        self.purpose = "negated";
+        self
+    }
+}
+
+impl Serialize for G2Point {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&hex::encode(self.pk_to_key_slice()))
+    }
+}
+
+impl<'de> Deserialize<'de> for G2Point {
+    fn deserialize<D>(deserializer: D) -> Result<G2Point, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(Bn254G2PointVisitor)
+    }
+}
+
+struct Bn254G2PointVisitor;
+
+impl<'de> Visitor<'de> for Bn254G2PointVisitor {
+    type Value = G2Point;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Bn254G2Point")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<G2Point, E> {
+        let bytes = hex::decode(s).map_err(E::custom)?;
+        G2Point::from_bytes(&bytes).map_err(|_| E::custom("failed to parse g2 point"))
+    }
+}
+
+impl G2Point {
+    /// Converts message to G2 point.
+    ///
+    /// Try-and-increment: derives `x` from the counter-extended hash of the message until
+    /// `x^3 + b` is a square, then clears the cofactor. Not constant time.
+    pub fn hash_to_curve(message: &[u8]) -> Self {
+        let p = fq_modulus();
+        try_and_increment(message, |digest| {
+            let wide = Sha512::digest(digest);
+            let re = fq_from_big_int(&BigInt::modulus(&BigInt::from_bytes(&wide[..32]), &p));
+            let im = fq_from_big_int(&BigInt::modulus(&BigInt::from_bytes(&wide[32..]), &p));
+            let x = Fq2::new(re.ok()?, im.ok()?);
+            let y = (x * x * x + G2::b()).sqrt()?;
+            let ge = clear_cofactor(G2::new(x, y, Fq2::one()));
+            if ge.is_zero() {
+                None
+            } else {
+                Some(G2Point {
+                    purpose: "hash_to_curve",
+                    ge,
+                })
+            }
+        })
+    }
+}
+
+/// Multiplies point on the twist by the cofactor, mapping it into the prime order subgroup
+fn clear_cofactor(point: G2) -> G2 {
+    (0..COFACTOR.bit_length()).rev().fold(G2::zero(), |acc, i| {
+        let acc = acc + acc;
+        if COFACTOR.test_bit(i) {
+            acc + point
+        } else {
+            acc
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use substrate_bn::{AffineG2, Fq, Fq2, G2};
+
+    use super::{G2Point, FE, GE};
+    use crate::arithmetic::traits::*;
+    use crate::elliptic::curves::traits::ECPoint;
+    use crate::elliptic::curves::traits::ECScalar;
+    use crate::BigInt;
+    use crate::ErrorKey;
+
+    #[test]
+    fn test_serdes_pk() {
+        for pk in &[
+            GE::generator(),
+            GE::base_point2(),
+            GE::generator() * FE::zero(),
+        ] {
+            let s = serde_json::to_string(pk).expect("Failed in serialization");
+            let des_pk: GE = serde_json::from_str(&s).expect("Failed in deserialization");
+            assert_eq!(des_pk, *pk);
+
+            let bin = bincode::serialize(pk).unwrap();
+            let decoded: G2Point = bincode::deserialize(bin.as_slice()).unwrap();
+            assert_eq!(decoded, *pk);
+        }
+    }
+
+    #[test]
+    fn test_eip197_generator_encoding() {
+        assert_eq!(
+            hex::encode(GE::generator().pk_to_key_slice()),
+            "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+             1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+             090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+             12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"
+        );
+    }
+
+    #[test]
+    fn test_compressed_encoding() {
+        for _ in 0..5 {
+            let p = GE::generator() * FE::new_random();
+            let compressed = BigInt::to_bytes(&p.bytes_compressed_to_big_int());
+            assert_eq!(compressed.len(), 65);
+            assert_eq!(GE::from_bytes(&compressed).unwrap(), p);
+        }
+    }
+
+    #[test]
+    fn test_from_coor() {
+        let p = GE::generator() * FE::new_random();
+        let q = GE::from_coor(&p.x_coor().unwrap(), &p.y_coor().unwrap());
+        assert_eq!(p, q);
+    }
+
+    #[test]
+    fn test_hash_to_curve_is_in_subgroup() {
+        for msg in &[&b""[..], b"abc", b"hash to g2"] {
+            let h = GE::hash_to_curve(msg);
+            let affine = AffineG2::from_jacobian(h.get_element()).unwrap();
+            // AffineG2::new checks the subgroup membership
+            assert!(AffineG2::new(affine.x(), affine.y()).is_ok());
+            assert_eq!(
+                h * <FE as ECScalar>::from(&FE::q()),
+                GE::generator() * FE::zero()
+            );
+        }
+        assert_ne!(GE::hash_to_curve(b"abc"), GE::hash_to_curve(b"abd"));
+    }
+
+    #[test]
+    fn test_base_point2() {
+        let g = GE::generator();
+        assert_eq!(GE::base_point2(), GE::hash_to_curve(&g.pk_to_key_slice()));
+        assert!(GE::base_point2() != g);
+    }
+
+    #[test]
+    fn test_minus_point() {
+        let a: FE = ECScalar::new_random();
+        let b: FE = ECScalar::new_random();
+        let base: GE = ECPoint::generator();
+        let point_ab1 = base * (a - b);
+        let point_ab2 = (base * a).sub_point(&(base * b).get_element());
+        assert_eq!(point_ab1, point_ab2);
+        assert_eq!(-(base * a), base * (FE::zero() - a));
+    }
+
+    #[test]
+    fn test_rejects_point_outside_subgroup() {
+        // the first point on the twist with x = 1 + n*i, it's not in the prime order subgroup
+        let (x, y) = (1..)
+            .find_map(|n| {
+                let x = Fq2::new(Fq::one(), fq(n));
+                (x * x * x + G2::b()).sqrt().map(|y| (x, y))
+            })
+            .unwrap();
+        assert!(AffineG2::new(x, y).is_err());
+        let mut bytes = vec![];
+        for coordinate in &[x.imaginary(), x.real(), y.imaginary(), y.real()] {
+            let mut buf = [0u8; 32];
+            coordinate.to_big_endian(&mut buf).unwrap();
+            bytes.extend_from_slice(&buf);
+        }
+        assert_eq!(
+            GE::from_bytes(&bytes).unwrap_err(),
+            ErrorKey::InvalidPublicKey
+        );
+    }
+
+    fn fq(n: u64) -> Fq {
+        Fq::from_str(&n.to_string()).unwrap()
+    }
+}
@@ -0,0 +1,176 @@
+//! BN254 (also known as alt_bn128) pairing-friendly curve
+//!
+//! Points are encoded the same way as in Ethereum's `ecAdd`, `ecMul` and `ecPairing` precompiles
+//! ([EIP-196], [EIP-197]), so they can be passed to Solidity verifiers as is.
+//!
+//! [EIP-196]: https://eips.ethereum.org/EIPS/eip-196
+//! [EIP-197]: https://eips.ethereum.org/EIPS/eip-197
+
+pub mod g1;
+pub mod g2;
+
+use std::fmt;
+
+use crate::elliptic::curves::bn254::g1::GE as GE1;
+use crate::elliptic::curves::bn254::g2::GE as GE2;
+use crate::elliptic::curves::traits::ECPoint;
+use crate::ErrorKey;
+
+use substrate_bn::{pairing, pairing_batch, Gt};
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Pair {
+    pub e: Gt,
+}
+
+impl Pair {
+    pub fn compute_pairing(g1_ge: &GE1, g2_ge: &GE2) -> Self {
+        Pair {
+            e: pairing(g1_ge.get_element(), g2_ge.get_element()),
+        }
+    }
+
+    /// Efficiently computes product of pairings.
+    ///
+    /// Computes `e(g1,g2) * e(g3,g4)` with a single final exponentiation.
+    pub fn efficient_pairing_mul(g1: &GE1, g2: &GE2, g3: &GE1, g4: &GE2) -> Self {
+        Pair {
+            e: pairing_batch(&[
+                (g1.get_element(), g2.get_element()),
+                (g3.get_element(), g4.get_element()),
+            ]),
+        }
+    }
+
+    pub fn add_pair(&self, other: &Pair) -> Self {
+        Pair {
+            e: self.e * other.e,
+        }
+    }
+
+    /// Checks that product of pairings `e(a_1, b_1) * ... * e(a_k, b_k)` equals one.
+    ///
+    /// That's the check performed by `ecPairing` precompile (EIP-197).
+    pub fn pairing_check(pairs: &[(GE1, GE2)]) -> bool {
+        let pairs: Vec<_> = pairs
+            .iter()
+            .map(|(a, b)| (a.get_element(), b.get_element()))
+            .collect();
+        pairing_batch(&pairs) == Gt::one()
+    }
+
+    /// Parses `ecPairing` precompile input (sequence of EIP-196 encoded G1 points followed by
+    /// EIP-197 encoded G2 points) and performs [pairing_check](Self::pairing_check)
+    pub fn pairing_check_from_bytes(input: &[u8]) -> Result<bool, ErrorKey> {
+        const PAIR_SIZE: usize = g1::UNCOMPRESSED_SIZE + g2::UNCOMPRESSED_SIZE;
+        if !input.len().is_multiple_of(PAIR_SIZE) {
+            return Err(ErrorKey::InvalidPublicKey);
+        }
+        let pairs = input
+            .chunks(PAIR_SIZE)
+            .map(|pair| {
+                let (a, b) = pair.split_at(g1::UNCOMPRESSED_SIZE);
+                Ok((GE1::from_bytes(a)?, GE2::from_bytes(b)?))
+            })
+            .collect::<Result<Vec<_>, ErrorKey>>()?;
+        Ok(Pair::pairing_check(&pairs))
+    }
+}
+
+impl fmt::Debug for Pair {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // substrate-bn doesn't expose representation of Gt elements
+        f.debug_struct("Pair").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pair;
+    use crate::elliptic::curves::bn254::g1::FE;
+    use crate::elliptic::curves::bn254::g1::GE as GE1;
+    use crate::elliptic::curves::bn254::g2::GE as GE2;
+    use crate::elliptic::curves::traits::ECPoint;
+    use crate::elliptic::curves::traits::ECScalar;
+
+    #[test]
+    fn powers_of_g1_and_g2() {
+        let a: GE1 = ECPoint::generator();
+        let b: GE2 = ECPoint::generator();
+        let scalar_factor: FE = ECScalar::new_random();
+        let res_mul_a = a.scalar_mul(&scalar_factor.get_element());
+        let res_mul_b = b.scalar_mul(&scalar_factor.get_element());
+        let res_a_power = Pair::compute_pairing(&res_mul_a, &b);
+        let res_b_power = Pair::compute_pairing(&a, &res_mul_b);
+        assert_eq!(res_a_power, res_b_power);
+    }
+
+    // e(P,Q)e(P,R) = e(P, Q+ R)
+    #[test]
+    fn pairing() {
+        let p: GE1 = ECPoint::generator();
+        let q: GE2 = ECPoint::generator();
+        let r: GE2 = ECPoint::base_point2();
+        let q_plus_r = q + r;
+        let e_p_q = Pair::compute_pairing(&p, &q);
+        let e_p_r = Pair::compute_pairing(&p, &r);
+        let e_p_q_r = Pair::compute_pairing(&p, &q_plus_r);
+        let e_p_q_add_e_p_r = e_p_q.add_pair(&e_p_r);
+        assert_eq!(e_p_q_add_e_p_r, e_p_q_r);
+        assert_eq!(Pair::efficient_pairing_mul(&p, &q, &p, &r), e_p_q_add_e_p_r);
+    }
+
+    #[test]
+    fn bls_signature_pairing_check() {
+        // verification of BLS signature over hash-to-G1, as done by Solidity verifiers
+        let sk: FE = ECScalar::new_random();
+        let pk = GE2::generator() * sk;
+        let h = GE1::hash_to_curve(b"message");
+        let sig = h * sk;
+        assert!(Pair::pairing_check(&[(sig, -GE2::generator()), (h, pk)]));
+        let other = GE1::hash_to_curve(b"other message");
+        assert!(!Pair::pairing_check(&[
+            (sig, -GE2::generator()),
+            (other, pk)
+        ]));
+    }
+
+    #[test]
+    fn pairing_check_vectors() {
+        // go-ethereum bn256Pairing test vector "jeff1"
+        let input = hex::decode(
+            "1c76476f4def4bb94541d57ebba1193381ffa7aa76ada664dd31c16024c43f59\
+             3034dd2920f673e204fee2811c678745fc819b55d3e9d294e45c9b03a76aef41\
+             209dd15ebff5d46c4bd888e51a93cf99a7329636c63514396b4a452003a35bf7\
+             04bf11ca01483bfa8b34b43561848d28905960114c8ac04049af4b6315a41678\
+             2bb8324af6cfc93537a2ad1a445cfd0ca2a71acd7ac41fadbf933c2a51be344d\
+             120a2a4cf30c1bf9845f20c6fe39e07ea2cce61f0c9bb048165fe5e4de877550\
+             111e129f1cf1097710d41c4ac70fcdfa5ba2023c6ff1cbeac322de49d1b6df7c\
+             2032c61a830e3c17286de9462bf242fca2883585b93870a73853face6a6bf411\
+             198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+             1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+             090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+             12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+        )
+        .unwrap();
+        assert_eq!(Pair::pairing_check_from_bytes(&input), Ok(true));
+
+        // e(G1, G2) * e(-G1, G2) = 1, while e(G1, G2) alone isn't
+        let g2 = "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+                  1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+                  090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+                  12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa";
+        let g1 = "0000000000000000000000000000000000000000000000000000000000000001\
+                  0000000000000000000000000000000000000000000000000000000000000002";
+        let minus_g1 = "0000000000000000000000000000000000000000000000000000000000000001\
+                        30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45";
+        let input = hex::decode(format!("{}{}{}{}", g1, g2, minus_g1, g2)).unwrap();
+        assert_eq!(Pair::pairing_check_from_bytes(&input), Ok(true));
+        let input = hex::decode(format!("{}{}", g1, g2)).unwrap();
+        assert_eq!(Pair::pairing_check_from_bytes(&input), Ok(false));
+
+        // empty input is accepted by the precompile
+        assert_eq!(Pair::pairing_check_from_bytes(&[]), Ok(true));
+        assert!(Pair::pairing_check_from_bytes(&input[1..]).is_err());
+    }
+}
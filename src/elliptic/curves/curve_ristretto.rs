@@ -6,21 +6,23 @@
     License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
 */
 
-use super::traits::{ECPoint, ECScalar};
+use super::traits::{BasePointTable, ECPoint, ECScalar};
 use crate::arithmetic::traits::*;
 use crate::cryptographic_primitives::hashing::hash_sha256::HSha256;
 use crate::cryptographic_primitives::hashing::traits::Hash;
 use crate::BigInt;
 use crate::ErrorKey::{self, InvalidPublicKey};
 use curve25519_dalek::constants::BASEPOINT_ORDER;
-use curve25519_dalek::constants::RISTRETTO_BASEPOINT_COMPRESSED;
-use curve25519_dalek::ristretto::CompressedRistretto;
+use curve25519_dalek::constants::{RISTRETTO_BASEPOINT_POINT, RISTRETTO_BASEPOINT_TABLE};
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoBasepointTable, RistrettoPoint};
 use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::{MultiscalarMul, VartimeMultiscalarMul};
 use rand::thread_rng;
 use serde::de::{self, Error, MapAccess, SeqAccess, Visitor};
 use serde::ser::SerializeStruct;
 use serde::ser::{Serialize, Serializer};
 use serde::{Deserialize, Deserializer};
+use sha2::{Digest as _, Sha512};
 use std::fmt;
 use std::ops::{Add, Mul};
 use std::str;
@@ -40,13 +42,14 @@ use crypto::sha3::Sha3;
 use merkle::Hashable;
 
 pub type SK = Scalar;
-pub type PK = CompressedRistretto;
+pub type PK = RistrettoPoint;
 
 #[derive(Clone, Debug, Copy)]
 pub struct RistrettoScalar {
     purpose: &'static str,
     fe: SK,
 }
+/// Point is kept decompressed, it's only compressed for serialization and hashing
 #[derive(Clone, Debug, Copy)]
 pub struct RistrettoCurvPoint {
     purpose: &'static str,
@@ -89,34 +92,18 @@ impl ECScalar for RistrettoScalar {
     }
 
     fn from(n: &BigInt) -> RistrettoScalar {
-        let mut v = BigInt::to_bytes(n);
+        let v = n.to_bytes_le();
         //TODO: add consistency check for sizes max 32/ max 64
-        let mut bytes_array_32: [u8; 32];
-        let mut bytes_array_64: [u8; 64];
-        if v.len() < SECRET_KEY_SIZE {
-            let mut template = vec![0; SECRET_KEY_SIZE - v.len()];
-            template.extend_from_slice(&v);
-            v = template;
-        }
-        if v.len() > SECRET_KEY_SIZE && v.len() < 2 * SECRET_KEY_SIZE {
-            let mut template = vec![0; 2 * SECRET_KEY_SIZE - v.len()];
-            template.extend_from_slice(&v);
-            v = template;
-        }
-        if v.len() == SECRET_KEY_SIZE {
-            bytes_array_32 = [0; SECRET_KEY_SIZE];
-            let bytes = &v[..];
-            bytes_array_32.copy_from_slice(&bytes);
-            bytes_array_32.reverse();
+        if v.len() <= SECRET_KEY_SIZE {
+            let mut bytes_array_32 = [0u8; SECRET_KEY_SIZE];
+            bytes_array_32[..v.len()].copy_from_slice(&v);
             RistrettoScalar {
                 purpose: "from_big_int",
                 fe: SK::from_bytes_mod_order(bytes_array_32),
             }
         } else {
-            bytes_array_64 = [0; 2 * SECRET_KEY_SIZE];
-            let bytes = &v[..];
-            bytes_array_64.copy_from_slice(&bytes);
-            bytes_array_64.reverse();
+            let mut bytes_array_64 = [0u8; 2 * SECRET_KEY_SIZE];
+            bytes_array_64[..v.len()].copy_from_slice(&v);
             RistrettoScalar {
                 purpose: "from_big_int",
                 fe: SK::from_bytes_mod_order_wide(&bytes_array_64),
@@ -125,10 +112,7 @@ impl ECScalar for RistrettoScalar {
     }
 
     fn to_big_int(&self) -> BigInt {
-        let t1 = &self.fe.to_bytes()[0..self.fe.to_bytes().len()];
-        let mut t2 = t1.to_vec();
-        t2.reverse();
-        BigInt::from_bytes(&t2[0..self.fe.to_bytes().len()])
+        BigInt::from_bytes_le(&self.fe.to_bytes())
     }
 
     fn q() -> BigInt {
@@ -255,22 +239,16 @@ impl ECPoint for RistrettoCurvPoint {
     type SecretKey = SK;
     type PublicKey = PK;
     type Scalar = RistrettoScalar;
+    type Table = RistrettoTable;
 
     fn base_point2() -> RistrettoCurvPoint {
-        let g: GE = ECPoint::generator();
-        let hash = HSha256::create_hash(&[&g.bytes_compressed_to_big_int()]);
-        let bytes = BigInt::to_bytes(&hash);
-        let h: GE = ECPoint::from_bytes(&bytes[..]).unwrap();
-        RistrettoCurvPoint {
-            purpose: "random",
-            ge: h.get_element(),
-        }
+        BASE_POINT2_TABLE.base()
     }
 
     fn generator() -> RistrettoCurvPoint {
         RistrettoCurvPoint {
             purpose: "base_fe",
-            ge: RISTRETTO_BASEPOINT_COMPRESSED,
+            ge: RISTRETTO_BASEPOINT_POINT,
         }
     }
 
@@ -283,7 +261,7 @@ impl ECPoint for RistrettoCurvPoint {
     }
 
     fn y_coor(&self) -> Option<BigInt> {
-        let y_fe = SK::from_bytes_mod_order(self.ge.to_bytes());
+        let y_fe = SK::from_bytes_mod_order(self.ge.compress().to_bytes());
         let y_fe = RistrettoScalar {
             purpose: "y_coor",
             fe: y_fe,
@@ -292,7 +270,7 @@ impl ECPoint for RistrettoCurvPoint {
     }
 
     fn bytes_compressed_to_big_int(&self) -> BigInt {
-        BigInt::from_bytes(self.ge.to_bytes()[0..self.ge.to_bytes().len()].as_ref())
+        BigInt::from_bytes(self.ge.compress().as_bytes())
     }
     fn from_bytes(bytes: &[u8]) -> Result<RistrettoCurvPoint, ErrorKey> {
         let bytes_vec = bytes.to_vec();
@@ -305,13 +283,13 @@ impl ECPoint for RistrettoCurvPoint {
                 let bytes_vec = template;
                 let bytes_slice = &bytes_vec[0..32];
                 bytes_array_32.copy_from_slice(&bytes_slice);
-                let r_point: PK = CompressedRistretto::from_slice(&bytes_array_32);
+                let r_point = CompressedRistretto::from_slice(&bytes_array_32);
                 let r_point_compress = r_point.decompress();
                 match r_point_compress {
                     Some(x) => {
                         let new_point = RistrettoCurvPoint {
                             purpose: "random",
-                            ge: x.compress(),
+                            ge: x,
                         };
                         Ok(new_point)
                     }
@@ -322,13 +300,13 @@ impl ECPoint for RistrettoCurvPoint {
             _ => {
                 let bytes_slice = &bytes_vec[0..32];
                 bytes_array_32.copy_from_slice(&bytes_slice);
-                let r_point: PK = CompressedRistretto::from_slice(&bytes_array_32);
+                let r_point = CompressedRistretto::from_slice(&bytes_array_32);
                 let r_point_compress = r_point.decompress();
                 match r_point_compress {
                     Some(x) => {
                         let new_point = RistrettoCurvPoint {
                             purpose: "random",
-                            ge: x.compress(),
+                            ge: x,
                         };
                         Ok(new_point)
                     }
@@ -339,37 +317,126 @@ impl ECPoint for RistrettoCurvPoint {
     }
 
     fn pk_to_key_slice(&self) -> Vec<u8> {
-        let result = self.ge.to_bytes();
-        result.to_vec()
+        self.ge.compress().to_bytes().to_vec()
     }
 
     fn scalar_mul(&self, fe: &SK) -> RistrettoCurvPoint {
-        let skpk = fe * (self.ge.decompress().unwrap());
+        // multiplication of the basepoint uses precomputed table
+        let skpk = if self.ge == RISTRETTO_BASEPOINT_POINT {
+            &RISTRETTO_BASEPOINT_TABLE * fe
+        } else {
+            fe * self.ge
+        };
         RistrettoCurvPoint {
             purpose: "scalar_point_mul",
-            ge: skpk.compress(),
+            ge: skpk,
         }
     }
 
     fn add_point(&self, other: &PK) -> RistrettoCurvPoint {
-        let pkpk = self.ge.decompress().unwrap() + other.decompress().unwrap();
         RistrettoCurvPoint {
             purpose: "combine",
-            ge: pkpk.compress(),
+            ge: self.ge + other,
         }
     }
 
     fn sub_point(&self, other: &PK) -> RistrettoCurvPoint {
-        let pkpk = self.ge.decompress().unwrap() - other.decompress().unwrap();
         RistrettoCurvPoint {
             purpose: "sub",
-            ge: pkpk.compress(),
+            ge: self.ge - other,
         }
     }
 
     fn from_coor(_x: &BigInt, _y: &BigInt) -> RistrettoCurvPoint {
         unimplemented!();
     }
+
+    fn hash_to_curve(message: &[u8]) -> RistrettoCurvPoint {
+        let digest = Sha512::digest(message);
+        let mut uniform_bytes = [0u8; 64];
+        uniform_bytes.copy_from_slice(&digest);
+        RistrettoCurvPoint {
+            purpose: "hash_to_curve",
+            ge: RistrettoPoint::from_uniform_bytes(&uniform_bytes),
+        }
+    }
+
+    fn mul_generator(scalar: &RistrettoScalar) -> RistrettoCurvPoint {
+        RistrettoCurvPoint {
+            purpose: "mul_generator",
+            ge: &RISTRETTO_BASEPOINT_TABLE * &scalar.fe,
+        }
+    }
+
+    fn mul_base_point2(scalar: &RistrettoScalar) -> RistrettoCurvPoint {
+        BASE_POINT2_TABLE.mul(scalar)
+    }
+
+    fn multi_scalar_mul(scalars: &[RistrettoScalar], points: &[RistrettoCurvPoint]) -> Self {
+        assert_eq!(
+            scalars.len(),
+            points.len(),
+            "number of scalars doesn't match number of points"
+        );
+        RistrettoCurvPoint {
+            purpose: "multi_scalar_mul",
+            ge: RistrettoPoint::multiscalar_mul(
+                scalars.iter().map(|s| s.fe),
+                points.iter().map(|p| p.ge),
+            ),
+        }
+    }
+
+    fn vartime_multi_scalar_mul(
+        scalars: &[RistrettoScalar],
+        points: &[RistrettoCurvPoint],
+    ) -> Self {
+        assert_eq!(
+            scalars.len(),
+            points.len(),
+            "number of scalars doesn't match number of points"
+        );
+        RistrettoCurvPoint {
+            purpose: "multi_scalar_mul",
+            ge: RistrettoPoint::vartime_multiscalar_mul(
+                scalars.iter().map(|s| s.fe),
+                points.iter().map(|p| p.ge),
+            ),
+        }
+    }
+}
+
+/// Fixed-base table backed by curve25519-dalek's [RistrettoBasepointTable]
+pub struct RistrettoTable(Box<RistrettoBasepointTable>);
+
+impl BasePointTable<RistrettoCurvPoint> for RistrettoTable {
+    fn new(base: &RistrettoCurvPoint) -> RistrettoTable {
+        RistrettoTable(Box::new(RistrettoBasepointTable::create(&base.ge)))
+    }
+
+    fn base(&self) -> RistrettoCurvPoint {
+        RistrettoCurvPoint {
+            purpose: "random",
+            ge: self.0.basepoint(),
+        }
+    }
+
+    fn mul(&self, scalar: &RistrettoScalar) -> RistrettoCurvPoint {
+        RistrettoCurvPoint {
+            purpose: "mul_table",
+            ge: &*self.0 * &scalar.fe,
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref BASE_POINT2_TABLE: RistrettoTable = {
+        let g: GE = ECPoint::generator();
+        let hash = HSha256::create_hash(&[&g.bytes_compressed_to_big_int()]);
+        let bytes = BigInt::to_bytes(&hash);
+        let h: GE = ECPoint::from_bytes(&bytes[..]).unwrap();
+        RistrettoTable::new(&h)
+    };
 }
 
 impl Mul<RistrettoScalar> for RistrettoCurvPoint {
@@ -586,6 +653,31 @@ mod tests {
         assert_eq!(a_inv_bn_1, a_inv_bn_2);
     }
 
+    #[test]
+    fn test_generator_mul_matches_variable_base_mul() {
+        let a: FE = ECScalar::new_random();
+        let g: GE = ECPoint::generator();
+        let g_copy = GE::from_bytes(&g.pk_to_key_slice()).unwrap();
+        assert_eq!(
+            (g * a).get_element(),
+            a.get_element() * g_copy.get_element()
+        );
+    }
+
+    #[test]
+    fn test_multi_scalar_mul() {
+        let scalars: Vec<FE> = (0..5).map(|_| ECScalar::new_random()).collect();
+        let points: Vec<GE> = (0..5u8).map(|i| GE::hash_to_curve(&[i])).collect();
+        let expected = scalars
+            .iter()
+            .zip(&points)
+            .map(|(s, p)| *p * *s)
+            .reduce(|acc, p| acc + p)
+            .unwrap();
+        assert_eq!(GE::multi_scalar_mul(&scalars, &points), expected);
+        assert_eq!(GE::vartime_multi_scalar_mul(&scalars, &points), expected);
+    }
+
     #[test]
     fn test_from_bytes_3() {
         let test_vec = [
@@ -0,0 +1,691 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+//! Curve chosen at runtime
+//!
+//! [DynPoint] and [DynScalar] hold a point or a scalar of any supported curve together with
+//! its [CurveId]. They are serialized with the curve name, e.g.
+//! `{"curve":"secp256k1","point":...}`, and operations on values of different curves fail with
+//! [CurveMismatch] instead of mixing them up.
+//!
+//! Generic code (any function over `P: ECPoint`) can be run on a curve known only at runtime
+//! with [CurveId::dispatch]. Proofs and secret sharing have ready-made runtime counterparts,
+//! e.g. [DynDLogProof] and [DynVerifiableSS].
+//!
+//! [DynDLogProof]: crate::cryptographic_primitives::proofs::sigma_dlog::DynDLogProof
+//! [DynVerifiableSS]: crate::cryptographic_primitives::secret_sharing::feldman_vss::DynVerifiableSS
+
+use std::error::Error;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use serde::de::{self, DeserializeOwned, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use zeroize::Zeroize;
+
+use super::traits::{ECPoint, ECScalar};
+use crate::BigInt;
+use crate::ErrorKey;
+
+/// Calls `$callback!` with the list of supported curves: `Variant => "name", PointType;`
+macro_rules! for_each_curve {
+    ($callback:ident) => {
+        $callback! {
+            Secp256k1 => "secp256k1", $crate::elliptic::curves::secp256_k1::GE;
+            P256 => "p256", $crate::elliptic::curves::p256::GE;
+            P384 => "p384", $crate::elliptic::curves::p384::GE;
+            P521 => "p521", $crate::elliptic::curves::p521::GE;
+            Ed25519 => "ed25519", $crate::elliptic::curves::ed25519::GE;
+            Ristretto => "ristretto", $crate::elliptic::curves::curve_ristretto::GE;
+            Bls12_381 => "bls12_381", $crate::elliptic::curves::bls12_381::g1::GE;
+            Bn254 => "bn254", $crate::elliptic::curves::bn254::g1::GE;
+            Jubjub => "jubjub", $crate::elliptic::curves::jubjub::GE;
+            BabyJubjub => "baby_jubjub", $crate::elliptic::curves::baby_jubjub::GE;
+            Pallas => "pallas", $crate::elliptic::curves::pasta::pallas::GE;
+            Vesta => "vesta", $crate::elliptic::curves::pasta::vesta::GE;
+        }
+    };
+}
+pub(crate) use for_each_curve;
+
+/// Implements `Serialize` and `Deserialize` for an enum holding a value of one of the curves:
+/// `impl_curve_tagged_serde!(Type, "content"; Variant => InnerType; ...)`
+///
+/// The value is serialized as a struct `{curve, content}`. Unlike serde's adjacently tagged
+/// enums, it works with formats which aren't self-describing, such as bincode.
+macro_rules! impl_curve_tagged_serde {
+    ($ty:ident, $content:literal; $($variant:ident => $inner:ty;)*) => {
+        impl $crate::elliptic::curves::dynamic::CurveTagged for $ty {
+            const NAME: &'static str = stringify!($ty);
+            const FIELDS: &'static [&'static str] = &["curve", $content];
+
+            fn curve(&self) -> $crate::elliptic::curves::dynamic::CurveId {
+                $ty::curve(self)
+            }
+
+            fn serialize_content<S: serde::ser::SerializeStruct>(
+                &self,
+                state: &mut S,
+            ) -> Result<(), S::Error> {
+                match self {
+                    $($ty::$variant(value) => state.serialize_field($content, value),)*
+                }
+            }
+
+            fn deserialize_content<'de, D: serde::Deserializer<'de>>(
+                curve: $crate::elliptic::curves::dynamic::CurveId,
+                deserializer: D,
+            ) -> Result<Self, D::Error> {
+                match curve {
+                    $($crate::elliptic::curves::dynamic::CurveId::$variant => {
+                        <$inner as serde::Deserialize>::deserialize(deserializer).map($ty::$variant)
+                    })*
+                }
+            }
+        }
+
+        impl serde::Serialize for $ty {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                $crate::elliptic::curves::dynamic::serialize_curve_tagged(self, serializer)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $ty {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                $crate::elliptic::curves::dynamic::deserialize_curve_tagged(deserializer)
+            }
+        }
+    };
+}
+pub(crate) use impl_curve_tagged_serde;
+
+/// Enum holding a value of one of the curves, see [impl_curve_tagged_serde]
+pub(crate) trait CurveTagged: Sized {
+    const NAME: &'static str;
+    const FIELDS: &'static [&'static str];
+
+    fn curve(&self) -> CurveId;
+    fn serialize_content<S: SerializeStruct>(&self, state: &mut S) -> Result<(), S::Error>;
+    fn deserialize_content<'de, D: Deserializer<'de>>(
+        curve: CurveId,
+        deserializer: D,
+    ) -> Result<Self, D::Error>;
+}
+
+pub(crate) fn serialize_curve_tagged<T: CurveTagged, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut state = serializer.serialize_struct(T::NAME, 2)?;
+    state.serialize_field("curve", &value.curve())?;
+    value.serialize_content(&mut state)?;
+    state.end()
+}
+
+pub(crate) fn deserialize_curve_tagged<'de, T: CurveTagged, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    deserializer.deserialize_struct(T::NAME, T::FIELDS, CurveTaggedVisitor(PhantomData))
+}
+
+struct CurveTaggedVisitor<T>(PhantomData<T>);
+
+impl<'de, T: CurveTagged> Visitor<'de> for CurveTaggedVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "struct {}", T::NAME)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        let curve: CurveId = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        seq.next_element_seed(ContentSeed(curve, PhantomData))?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))
+    }
+
+    // The content can only be parsed once the curve is known, so the curve has to come first.
+    // Otherwise the rest of the map is only scanned to tell a misplaced curve from a missing one.
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<T, A::Error> {
+        match map.next_key::<String>()? {
+            Some(key) if key == "curve" => (),
+            Some(key) if key == T::FIELDS[1] => {
+                map.next_value::<de::IgnoredAny>()?;
+                while let Some(key) = map.next_key::<String>()? {
+                    if key == "curve" {
+                        return Err(de::Error::custom(format_args!(
+                            "field `curve` must precede field `{}`",
+                            T::FIELDS[1]
+                        )));
+                    }
+                    map.next_value::<de::IgnoredAny>()?;
+                }
+                return Err(de::Error::missing_field("curve"));
+            }
+            Some(key) => return Err(de::Error::unknown_field(&key, T::FIELDS)),
+            None => return Err(de::Error::missing_field("curve")),
+        }
+        let curve: CurveId = map.next_value()?;
+        match map.next_key::<String>()? {
+            Some(key) if key == T::FIELDS[1] => (),
+            Some(key) => return Err(de::Error::unknown_field(&key, T::FIELDS)),
+            None => return Err(de::Error::missing_field(T::FIELDS[1])),
+        }
+        let value = map.next_value_seed(ContentSeed(curve, PhantomData))?;
+        match map.next_key::<String>()? {
+            Some(key) => Err(de::Error::unknown_field(&key, T::FIELDS)),
+            None => Ok(value),
+        }
+    }
+}
+
+struct ContentSeed<T>(CurveId, PhantomData<T>);
+
+impl<'de, T: CurveTagged> DeserializeSeed<'de> for ContentSeed<T> {
+    type Value = T;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+        T::deserialize_content(self.0, deserializer)
+    }
+}
+
+/// Curve of the point or scalar does not match the expected one
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CurveMismatch {
+    pub expected: CurveId,
+    pub found: CurveId,
+}
+
+impl fmt::Display for CurveMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected {} value, found {}", self.expected, self.found)
+    }
+}
+
+impl Error for CurveMismatch {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownCurve(pub String);
+
+impl fmt::Display for UnknownCurve {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown curve: {}", self.0)
+    }
+}
+
+impl Error for UnknownCurve {}
+
+/// Curve which can be chosen at runtime
+pub trait DynCurve: ECPoint + Clone {
+    const CURVE: CurveId;
+
+    fn into_dyn_point(self) -> DynPoint;
+    fn from_dyn_point(point: &DynPoint) -> Option<Self>;
+    fn into_dyn_scalar(scalar: Self::Scalar) -> DynScalar;
+    fn from_dyn_scalar(scalar: &DynScalar) -> Option<Self::Scalar>;
+}
+
+/// Code generic over the curve, to be run on the curve chosen at runtime with
+/// [CurveId::dispatch]
+pub trait CurveVisitor {
+    type Output;
+
+    fn visit<P>(self) -> Self::Output
+    where
+        P: DynCurve + Debug + Serialize + DeserializeOwned,
+        P::Scalar: Clone + PartialEq + Debug + Zeroize + Serialize + DeserializeOwned;
+}
+
+macro_rules! impl_dynamic_curves {
+    ($($variant:ident => $name:literal, $point:ty;)*) => {
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        pub enum CurveId {
+            $(
+                #[serde(rename = $name)]
+                $variant,
+            )*
+        }
+
+        impl CurveId {
+            pub const ALL: &'static [CurveId] = &[$(CurveId::$variant),*];
+
+            /// Curve name, same as used in serialization
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(CurveId::$variant => $name,)*
+                }
+            }
+
+            /// Order of the group
+            pub fn order(self) -> BigInt {
+                match self {
+                    $(CurveId::$variant => <$point as ECPoint>::Scalar::q(),)*
+                }
+            }
+
+            /// Runs `visitor` on the curve
+            pub fn dispatch<V: CurveVisitor>(self, visitor: V) -> V::Output {
+                match self {
+                    $(CurveId::$variant => visitor.visit::<$point>(),)*
+                }
+            }
+        }
+
+        impl FromStr for CurveId {
+            type Err = UnknownCurve;
+
+            fn from_str(s: &str) -> Result<CurveId, UnknownCurve> {
+                match s {
+                    $($name => Ok(CurveId::$variant),)*
+                    _ => Err(UnknownCurve(s.to_string())),
+                }
+            }
+        }
+
+        /// Point of the curve chosen at runtime
+        #[derive(Clone, Debug, PartialEq)]
+        pub enum DynPoint {
+            $(
+                $variant($point),
+            )*
+        }
+
+        /// Scalar of the curve chosen at runtime
+        #[derive(Clone, Debug, PartialEq)]
+        pub enum DynScalar {
+            $(
+                $variant(<$point as ECPoint>::Scalar),
+            )*
+        }
+
+        impl_curve_tagged_serde!(DynPoint, "point"; $($variant => $point;)*);
+        impl_curve_tagged_serde!(DynScalar, "scalar"; $($variant => <$point as ECPoint>::Scalar;)*);
+
+        $(
+            impl DynCurve for $point {
+                const CURVE: CurveId = CurveId::$variant;
+
+                fn into_dyn_point(self) -> DynPoint {
+                    DynPoint::$variant(self)
+                }
+
+                fn from_dyn_point(point: &DynPoint) -> Option<Self> {
+                    match point {
+                        DynPoint::$variant(point) => Some(point.clone()),
+                        _ => None,
+                    }
+                }
+
+                fn into_dyn_scalar(scalar: Self::Scalar) -> DynScalar {
+                    DynScalar::$variant(scalar)
+                }
+
+                fn from_dyn_scalar(scalar: &DynScalar) -> Option<Self::Scalar> {
+                    match scalar {
+                        DynScalar::$variant(scalar) => Some(scalar.clone()),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+
+        impl DynPoint {
+            pub fn curve(&self) -> CurveId {
+                match self {
+                    $(DynPoint::$variant(_) => CurveId::$variant,)*
+                }
+            }
+
+            pub fn generator(curve: CurveId) -> DynPoint {
+                match curve {
+                    $(CurveId::$variant => DynPoint::$variant(ECPoint::generator()),)*
+                }
+            }
+
+            pub fn base_point2(curve: CurveId) -> DynPoint {
+                match curve {
+                    $(CurveId::$variant => DynPoint::$variant(ECPoint::base_point2()),)*
+                }
+            }
+
+            /// Parses a point encoded by [DynPoint::to_bytes] or the way [ECPoint::from_bytes] of
+            /// the curve expects
+            pub fn from_bytes(curve: CurveId, bytes: &[u8]) -> Result<DynPoint, ErrorKey> {
+                // secp256k1 encoding is prefixed with 0x04 while its from_bytes expects x || y
+                let bytes = match (curve, bytes) {
+                    (CurveId::Secp256k1, [4, x_y @ ..]) if x_y.len() == 64 => x_y,
+                    _ => bytes,
+                };
+                match curve {
+                    $(CurveId::$variant => {
+                        <$point as ECPoint>::from_bytes(bytes).map(DynPoint::$variant)
+                    })*
+                }
+            }
+
+            /// Point encoding, see [ECPoint::pk_to_key_slice]. Parsed back by [DynPoint::from_bytes].
+            pub fn to_bytes(&self) -> Vec<u8> {
+                match self {
+                    $(DynPoint::$variant(point) => point.pk_to_key_slice(),)*
+                }
+            }
+
+            pub fn try_add(&self, other: &DynPoint) -> Result<DynPoint, CurveMismatch> {
+                match (self, other) {
+                    $((DynPoint::$variant(a), DynPoint::$variant(b)) => {
+                        Ok(DynPoint::$variant(a.add_point(&b.get_element())))
+                    })*
+                    _ => Err(self.mismatch(other.curve())),
+                }
+            }
+
+            pub fn try_sub(&self, other: &DynPoint) -> Result<DynPoint, CurveMismatch> {
+                match (self, other) {
+                    $((DynPoint::$variant(a), DynPoint::$variant(b)) => {
+                        Ok(DynPoint::$variant(a.sub_point(&b.get_element())))
+                    })*
+                    _ => Err(self.mismatch(other.curve())),
+                }
+            }
+
+            pub fn try_mul(&self, scalar: &DynScalar) -> Result<DynPoint, CurveMismatch> {
+                match (self, scalar) {
+                    $((DynPoint::$variant(point), DynScalar::$variant(scalar)) => {
+                        Ok(DynPoint::$variant(point.scalar_mul(&scalar.get_element())))
+                    })*
+                    _ => Err(self.mismatch(scalar.curve())),
+                }
+            }
+        }
+
+        impl DynScalar {
+            pub fn curve(&self) -> CurveId {
+                match self {
+                    $(DynScalar::$variant(_) => CurveId::$variant,)*
+                }
+            }
+
+            pub fn new_random(curve: CurveId) -> DynScalar {
+                match curve {
+                    $(CurveId::$variant => DynScalar::$variant(ECScalar::new_random()),)*
+                }
+            }
+
+            pub fn zero(curve: CurveId) -> DynScalar {
+                match curve {
+                    $(CurveId::$variant => DynScalar::$variant(ECScalar::zero()),)*
+                }
+            }
+
+            /// Converts `n` to a scalar, reducing it modulo the group order
+            pub fn from_big_int(curve: CurveId, n: &BigInt) -> DynScalar {
+                match curve {
+                    $(CurveId::$variant => DynScalar::$variant(ECScalar::from(n)),)*
+                }
+            }
+
+            pub fn to_big_int(&self) -> BigInt {
+                match self {
+                    $(DynScalar::$variant(scalar) => scalar.to_big_int(),)*
+                }
+            }
+
+            pub fn invert(&self) -> DynScalar {
+                match self {
+                    $(DynScalar::$variant(scalar) => DynScalar::$variant(scalar.invert()),)*
+                }
+            }
+
+            pub fn try_add(&self, other: &DynScalar) -> Result<DynScalar, CurveMismatch> {
+                match (self, other) {
+                    $((DynScalar::$variant(a), DynScalar::$variant(b)) => {
+                        Ok(DynScalar::$variant(ECScalar::add(a, &b.get_element())))
+                    })*
+                    _ => Err(self.mismatch(other.curve())),
+                }
+            }
+
+            pub fn try_sub(&self, other: &DynScalar) -> Result<DynScalar, CurveMismatch> {
+                match (self, other) {
+                    $((DynScalar::$variant(a), DynScalar::$variant(b)) => {
+                        Ok(DynScalar::$variant(ECScalar::sub(a, &b.get_element())))
+                    })*
+                    _ => Err(self.mismatch(other.curve())),
+                }
+            }
+
+            pub fn try_mul(&self, other: &DynScalar) -> Result<DynScalar, CurveMismatch> {
+                match (self, other) {
+                    $((DynScalar::$variant(a), DynScalar::$variant(b)) => {
+                        Ok(DynScalar::$variant(ECScalar::mul(a, &b.get_element())))
+                    })*
+                    _ => Err(self.mismatch(other.curve())),
+                }
+            }
+        }
+    };
+}
+
+for_each_curve!(impl_dynamic_curves);
+
+impl fmt::Display for CurveId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl DynPoint {
+    pub fn new<P: DynCurve>(point: P) -> DynPoint {
+        point.into_dyn_point()
+    }
+
+    /// Returns the point if it belongs to curve `P`
+    pub fn downcast<P: DynCurve>(&self) -> Result<P, CurveMismatch> {
+        P::from_dyn_point(self).ok_or_else(|| self.mismatch(P::CURVE).flip())
+    }
+
+    fn mismatch(&self, found: CurveId) -> CurveMismatch {
+        CurveMismatch {
+            expected: self.curve(),
+            found,
+        }
+    }
+}
+
+impl DynScalar {
+    pub fn new<P: DynCurve>(scalar: P::Scalar) -> DynScalar {
+        P::into_dyn_scalar(scalar)
+    }
+
+    /// Returns the scalar if it belongs to curve `P`
+    pub fn downcast<P: DynCurve>(&self) -> Result<P::Scalar, CurveMismatch> {
+        P::from_dyn_scalar(self).ok_or_else(|| self.mismatch(P::CURVE).flip())
+    }
+
+    fn mismatch(&self, found: CurveId) -> CurveMismatch {
+        CurveMismatch {
+            expected: self.curve(),
+            found,
+        }
+    }
+}
+
+impl CurveMismatch {
+    fn flip(self) -> CurveMismatch {
+        CurveMismatch {
+            expected: self.found,
+            found: self.expected,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elliptic::curves::{ed25519, secp256_k1};
+
+    #[test]
+    fn curve_names_roundtrip() {
+        for curve in CurveId::ALL {
+            assert_eq!(curve.name().parse::<CurveId>(), Ok(*curve));
+            assert_eq!(
+                serde_json::to_string(curve).unwrap(),
+                format!("\"{}\"", curve)
+            );
+        }
+        assert_eq!(
+            "secp256r1".parse::<CurveId>(),
+            Err(UnknownCurve("secp256r1".to_string()))
+        );
+    }
+
+    #[test]
+    fn arithmetic_on_every_curve() {
+        for curve in CurveId::ALL.iter().copied() {
+            let a = DynScalar::new_random(curve);
+            let b = DynScalar::new_random(curve);
+            let g = DynPoint::generator(curve);
+            assert_eq!(g.curve(), curve);
+
+            let a_plus_b = a.try_add(&b).unwrap();
+            let lhs = g.try_mul(&a_plus_b).unwrap();
+            let rhs = g
+                .try_mul(&a)
+                .unwrap()
+                .try_add(&g.try_mul(&b).unwrap())
+                .unwrap();
+            assert_eq!(lhs, rhs, "{}", curve);
+            assert_eq!(a_plus_b.try_sub(&b).unwrap(), a);
+            assert_eq!(
+                lhs.try_sub(&g.try_mul(&b).unwrap()).unwrap(),
+                g.try_mul(&a).unwrap()
+            );
+
+            let one = DynScalar::from_big_int(curve, &BigInt::from(1));
+            assert_eq!(a.try_mul(&a.invert()).unwrap(), one, "{}", curve);
+            assert_eq!(a_plus_b.curve(), curve);
+        }
+    }
+
+    #[test]
+    fn serialization_is_tagged_with_curve() {
+        for curve in CurveId::ALL.iter().copied() {
+            let point = DynPoint::base_point2(curve);
+            let json = serde_json::to_string(&point).unwrap();
+            assert!(json.starts_with(&format!("{{\"curve\":\"{}\",\"point\":", curve)));
+            assert_eq!(serde_json::from_str::<DynPoint>(&json).unwrap(), point);
+            let encoded = bincode::serialize(&point).unwrap();
+            assert_eq!(bincode::deserialize::<DynPoint>(&encoded).unwrap(), point);
+
+            let scalar = DynScalar::new_random(curve);
+            let json = serde_json::to_string(&scalar).unwrap();
+            assert!(json.starts_with(&format!("{{\"curve\":\"{}\",\"scalar\":", curve)));
+            assert_eq!(serde_json::from_str::<DynScalar>(&json).unwrap(), scalar);
+
+            assert_eq!(
+                DynPoint::from_bytes(curve, &point.to_bytes()).unwrap(),
+                point,
+                "{}",
+                curve
+            );
+        }
+
+        // point serialized as secp256k1 point can't be read as a point of another curve
+        let g = serde_json::to_value(DynPoint::generator(CurveId::Secp256k1)).unwrap();
+        let mut relabeled = g.clone();
+        relabeled["curve"] = "p256".into();
+        assert!(serde_json::from_value::<DynPoint>(relabeled).is_err());
+        let mut unknown = g;
+        unknown["curve"] = "secp256r1".into();
+        assert!(serde_json::from_value::<DynPoint>(unknown).is_err());
+    }
+
+    #[test]
+    fn curve_field_must_come_first() {
+        let point = DynPoint::generator(CurveId::Ed25519);
+        let json = serde_json::to_value(&point).unwrap();
+        let curve = json["curve"].to_string();
+        let content = json["point"].to_string();
+
+        let reordered = format!("{{\"point\":{},\"curve\":{}}}", content, curve);
+        let err = serde_json::from_str::<DynPoint>(&reordered).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("field `curve` must precede field `point`"),
+            "{}",
+            err
+        );
+
+        let missing = format!("{{\"point\":{}}}", content);
+        let err = serde_json::from_str::<DynPoint>(&missing).unwrap_err();
+        assert!(err.to_string().contains("missing field `curve`"), "{}", err);
+
+        let unknown = format!("{{\"curv\":{},\"point\":{}}}", curve, content);
+        let err = serde_json::from_str::<DynPoint>(&unknown).unwrap_err();
+        assert!(err.to_string().contains("unknown field `curv`"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_mixed_curves() {
+        let secp = DynScalar::new_random(CurveId::Secp256k1);
+        let ed = DynScalar::new_random(CurveId::Ed25519);
+        let mismatch = CurveMismatch {
+            expected: CurveId::Secp256k1,
+            found: CurveId::Ed25519,
+        };
+        assert_eq!(secp.try_add(&ed), Err(mismatch));
+        assert_eq!(secp.try_mul(&ed), Err(mismatch));
+
+        let secp_g = DynPoint::generator(CurveId::Secp256k1);
+        let ed_g = DynPoint::generator(CurveId::Ed25519);
+        assert_eq!(secp_g.try_mul(&ed), Err(mismatch));
+        assert_eq!(secp_g.try_add(&ed_g), Err(mismatch));
+        assert_eq!(secp_g.try_sub(&ed_g), Err(mismatch));
+        assert_ne!(secp_g, ed_g);
+
+        assert!(secp_g.downcast::<secp256_k1::GE>().is_ok());
+        assert_eq!(
+            secp_g.downcast::<ed25519::GE>(),
+            Err(CurveMismatch {
+                expected: CurveId::Ed25519,
+                found: CurveId::Secp256k1,
+            })
+        );
+        assert_eq!(
+            ed.downcast::<ed25519::GE>()
+                .map(DynScalar::new::<ed25519::GE>),
+            Ok(ed)
+        );
+    }
+
+    #[test]
+    fn dispatch_runs_generic_code() {
+        struct GeneratorBytes;
+
+        impl CurveVisitor for GeneratorBytes {
+            type Output = (CurveId, Vec<u8>);
+
+            fn visit<P>(self) -> Self::Output
+            where
+                P: DynCurve + Debug + Serialize + DeserializeOwned,
+                P::Scalar: Clone + PartialEq + Debug + Zeroize + Serialize + DeserializeOwned,
+            {
+                let g: P = ECPoint::generator();
+                (P::CURVE, g.pk_to_key_slice())
+            }
+        }
+
+        for curve in CurveId::ALL.iter().copied() {
+            let (visited, bytes) = curve.dispatch(GeneratorBytes);
+            assert_eq!(visited, curve);
+            assert_eq!(bytes, DynPoint::generator(curve).to_bytes());
+        }
+    }
+}
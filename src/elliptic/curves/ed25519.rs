@@ -6,12 +6,12 @@
 */
 
 // paper: https://ed25519.cr.yp.to/ed25519-20110926.pdf
-// based on https://docs.rs/cryptoxide/0.1.0/cryptoxide/curve25519/index.html
+// based on https://docs.rs/curve25519-dalek/1.2.3/curve25519_dalek/edwards/index.html
 // https://cr.yp.to/ecdh/curve25519-20060209.pdf
 use std::fmt::Debug;
 use std::str;
 pub const TWO_TIMES_SECRET_KEY_SIZE: usize = 64;
-use super::traits::{ECPoint, ECScalar};
+use super::traits::{try_and_increment, BasePointTable, ECPoint, ECScalar};
 use crate::cryptographic_primitives::hashing::hash_sha256::HSha256;
 use crate::cryptographic_primitives::hashing::traits::Hash;
 use serde::de::{self, Error, MapAccess, SeqAccess, Visitor};
@@ -20,8 +20,8 @@ use serde::ser::{Serialize, Serializer};
 use serde::{Deserialize, Deserializer};
 use std::fmt;
 use std::ops::{Add, Mul};
-pub type SK = Fe;
-pub type PK = GeP3;
+pub type SK = Scalar;
+pub type PK = EdwardsPoint;
 use crate::arithmetic::traits::*;
 use crate::BigInt;
 use crate::ErrorKey::{self, InvalidPublicKey};
@@ -29,7 +29,13 @@ use crate::ErrorKey::{self, InvalidPublicKey};
 use crypto::digest::Digest;
 #[cfg(feature = "merkle")]
 use crypto::sha3::Sha3;
-use cryptoxide::curve25519::*;
+use curve25519_dalek::constants::{
+    BASEPOINT_ORDER, ED25519_BASEPOINT_POINT, ED25519_BASEPOINT_TABLE,
+};
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsBasepointTable, EdwardsPoint};
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::{MultiscalarMul, VartimeMultiscalarMul};
 #[cfg(feature = "merkle")]
 use merkle::Hashable;
 use std::ptr;
@@ -41,6 +47,7 @@ pub struct Ed25519Scalar {
     purpose: &'static str,
     fe: SK,
 }
+/// Point is kept decompressed, it's only compressed for serialization and hashing
 #[derive(Clone, Copy)]
 pub struct Ed25519Point {
     purpose: &'static str,
@@ -59,19 +66,14 @@ impl Zeroize for Ed25519Scalar {
 impl ECScalar for Ed25519Scalar {
     type SecretKey = SK;
 
-    // we chose to multiply by 8 (co-factor) all group elements to work in the prime order sub group.
-    // each random fe is having its 3 first bits zeroed
     fn new_random() -> Ed25519Scalar {
-        let rnd_bn = BigInt::sample_below(&FE::q());
-        let rnd_bn_mul_8 = BigInt::mod_mul(&rnd_bn, &BigInt::from(8), &FE::q());
-        ECScalar::from(&rnd_bn_mul_8)
+        ECScalar::from(&BigInt::sample_below(&FE::q()))
     }
 
     fn zero() -> Ed25519Scalar {
-        let q_fe: FE = ECScalar::from(&FE::q());
         Ed25519Scalar {
             purpose: "zero",
-            fe: q_fe.get_element(),
+            fe: SK::zero(),
         }
     }
 
@@ -83,85 +85,52 @@ impl ECScalar for Ed25519Scalar {
     }
 
     fn from(n: &BigInt) -> Ed25519Scalar {
-        let mut v = BigInt::to_bytes(&n);
+        let mut v = n.to_bytes_le();
         if v.len() > TWO_TIMES_SECRET_KEY_SIZE {
-            v = v[0..TWO_TIMES_SECRET_KEY_SIZE].to_vec();
+            v.drain(..v.len() - TWO_TIMES_SECRET_KEY_SIZE);
         }
-
-        let mut template = vec![0; TWO_TIMES_SECRET_KEY_SIZE - v.len()];
-        template.extend_from_slice(&v);
-        v = template;
-        v.reverse();
-        sc_reduce(&mut v[..]);
+        let mut bytes_array_64 = [0u8; TWO_TIMES_SECRET_KEY_SIZE];
+        bytes_array_64[..v.len()].copy_from_slice(&v);
         Ed25519Scalar {
             purpose: "from_big_int",
-            fe: SK::from_bytes(&v[..]),
+            fe: SK::from_bytes_mod_order_wide(&bytes_array_64),
         }
     }
 
     fn to_big_int(&self) -> BigInt {
-        let t1 = &self.fe.to_bytes()[0..self.fe.to_bytes().len()];
-        let mut t2 = t1.to_vec();
-        t2.reverse();
-        BigInt::from_bytes(&t2[0..self.fe.to_bytes().len()])
+        BigInt::from_bytes_le(&self.fe.to_bytes())
     }
 
     fn q() -> BigInt {
-        let q_bytes_array: [u8; 32];
-        q_bytes_array = [
-            237, 211, 245, 92, 26, 99, 18, 88, 214, 156, 247, 162, 222, 249, 222, 20, 0, 0, 0, 0,
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 16,
-        ];
-        let l_fe = SK::from_bytes(&q_bytes_array);
-        let l_fe = Ed25519Scalar {
-            purpose: "q",
-            fe: l_fe,
-        };
-        l_fe.to_big_int()
+        BigInt::from_bytes_le(&BASEPOINT_ORDER.to_bytes())
     }
 
     fn add(&self, other: &SK) -> Ed25519Scalar {
-        let other_point = Ed25519Scalar {
-            purpose: "other add",
-            fe: *other,
-        };
-        let lhs_bn = self.to_big_int();
-        let rhs_bn = other_point.to_big_int();
-        let sum = BigInt::mod_add(&lhs_bn, &rhs_bn, &FE::q());
-        let sum_fe: FE = ECScalar::from(&sum);
-
-        sum_fe
+        Ed25519Scalar {
+            purpose: "add",
+            fe: self.get_element() + other,
+        }
     }
 
     fn mul(&self, other: &SK) -> Ed25519Scalar {
-        let other_point = Ed25519Scalar {
-            purpose: "other mul",
-            fe: *other,
-        };
-        let lhs_bn = self.to_big_int();
-        let rhs_bn = other_point.to_big_int();
-        let mul = BigInt::mod_mul(&lhs_bn, &rhs_bn, &FE::q());
-        let mul_fe: FE = ECScalar::from(&mul);
-        mul_fe
+        Ed25519Scalar {
+            purpose: "mul",
+            fe: self.get_element() * other,
+        }
     }
 
     fn sub(&self, other: &SK) -> Ed25519Scalar {
-        let other_point = Ed25519Scalar {
-            purpose: "other sub",
-            fe: *other,
-        };
-        let lhs_bn = self.to_big_int();
-        let rhs_bn = other_point.to_big_int();
-        let sub = BigInt::mod_sub(&lhs_bn, &rhs_bn, &FE::q());
-        let sub_fe: FE = ECScalar::from(&sub);
-        sub_fe
+        Ed25519Scalar {
+            purpose: "sub",
+            fe: self.get_element() - other,
+        }
     }
 
     fn invert(&self) -> Ed25519Scalar {
-        let self_bn = self.to_big_int();
-        let inv = BigInt::mod_inv(&self_bn, &FE::q()).unwrap();
-        let inv_fe: FE = ECScalar::from(&inv);
-        inv_fe
+        Ed25519Scalar {
+            purpose: "invert",
+            fe: self.get_element().invert(),
+        }
     }
 }
 
@@ -249,14 +218,14 @@ impl Debug for Ed25519Point {
             f,
             "Point {{ purpose: {:?}, bytes: {:?} }}",
             self.purpose,
-            self.ge.to_bytes()
+            self.ge.compress().to_bytes()
         )
     }
 }
 
 impl PartialEq for Ed25519Point {
     fn eq(&self, other: &Ed25519Point) -> bool {
-        self.get_element().to_bytes() == other.get_element().to_bytes()
+        self.get_element() == other.get_element()
     }
 }
 
@@ -272,28 +241,16 @@ impl ECPoint for Ed25519Point {
     type SecretKey = SK;
     type PublicKey = PK;
     type Scalar = Ed25519Scalar;
+    type Table = Ed25519Table;
 
     fn base_point2() -> Ed25519Point {
-        let g: GE = ECPoint::generator();
-        let hash = HSha256::create_hash(&[&g.bytes_compressed_to_big_int()]);
-        let hash = HSha256::create_hash(&[&hash]);
-        let bytes = BigInt::to_bytes(&hash);
-        let h: GE = ECPoint::from_bytes(&bytes[..]).unwrap();
-        Ed25519Point {
-            purpose: "random",
-            ge: h.get_element(),
-        }
+        BASE_POINT2_TABLE.base()
     }
 
     fn generator() -> Ed25519Point {
-        let vec_1: [u8; 32];
-        vec_1 = [
-            1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 0,
-        ];
         Ed25519Point {
             purpose: "base_fe",
-            ge: ge_scalarmult_base(&vec_1[..]),
+            ge: ED25519_BASEPOINT_POINT,
         }
     }
 
@@ -301,136 +258,261 @@ impl ECPoint for Ed25519Point {
         self.ge
     }
 
+    // curve25519-dalek keeps projective coordinates of `EdwardsPoint` and its `FieldElement`
+    // crate-private, so the only public way to get coordinates is the compressed encoding (y and
+    // the sign of x). x has to be recovered from it with BigInt arithmetic.
     fn x_coor(&self) -> Option<BigInt> {
-        let y = self.y_coor().unwrap();
-        Some(xrecover(y))
+        let bytes = self.ge.compress().to_bytes();
+        let y = self.y_coor()?;
+        recover_x(&y, bytes[31] >> 7 == 1)
     }
 
     fn y_coor(&self) -> Option<BigInt> {
-        let y_fe = SK::from_bytes(self.ge.to_bytes()[0..self.ge.to_bytes().len()].as_ref());
-        let y = Ed25519Scalar {
-            purpose: "base_fe",
-            fe: y_fe,
-        };
-        Some(y.to_big_int())
+        let mut bytes = self.ge.compress().to_bytes();
+        bytes[31] &= 0x7f;
+        Some(BigInt::from_bytes_le(&bytes))
     }
 
     fn bytes_compressed_to_big_int(&self) -> BigInt {
-        BigInt::from_bytes(self.ge.to_bytes()[0..self.ge.to_bytes().len()].as_ref())
+        BigInt::from_bytes(self.ge.compress().as_bytes())
     }
 
-    // from_bytes will return Ok only if the bytes encode a valid point.
-    // since valid point are not necessarily in the sub group of prime order this needs to be checked
-    // as well such that Ok will be returned only for valid point of the sub group prime order.
-    // currently we change the encoded point by multiply by 8 to make sure it is in the sub group of prime order.
-    // This is because for our use cases so far it doesn't matter and multiply by 8 is faster than testing for a point
-    // in the sub group prime order
+    // from_bytes returns Ok only for points of the prime order subgroup, other than identity.
+    // Use from_bytes_unchecked and clear_cofactor to map any curve point into the subgroup.
     fn from_bytes(bytes: &[u8]) -> Result<Ed25519Point, ErrorKey> {
-        let bytes_vec = bytes.to_vec();
-        let mut bytes_array_32 = [0u8; 32];
-        let byte_len = bytes_vec.len();
-        match byte_len {
-            0..=32 => {
-                let mut template = vec![0; 32 - byte_len];
-                template.extend_from_slice(&bytes);
-                let bytes_vec = template;
-                let bytes_slice = &bytes_vec[0..32];
-                bytes_array_32.copy_from_slice(&bytes_slice);
-                let ge_from_bytes = PK::from_bytes_negate_vartime(&bytes_array_32);
-                match ge_from_bytes {
-                    Some(_x) => {
-                        let ge_bytes = ge_from_bytes.unwrap().to_bytes();
-                        let ge_from_bytes = PK::from_bytes_negate_vartime(&ge_bytes[..]);
-                        match ge_from_bytes {
-                            Some(y) => {
-                                let eight: FE = ECScalar::from(&BigInt::from(8));
-                                let new_point = Ed25519Point {
-                                    purpose: "random",
-                                    ge: y,
-                                };
-                                Ok(new_point * eight)
-                            }
-                            None => Err(InvalidPublicKey),
-                        }
-                    }
-                    None => Err(InvalidPublicKey),
-                }
-            }
-            _ => {
-                let bytes_slice = &bytes_vec[0..32];
-                bytes_array_32.copy_from_slice(&bytes_slice);
-                let ge_from_bytes = PK::from_bytes_negate_vartime(bytes);
-                match ge_from_bytes {
-                    Some(_x) => {
-                        let ge_bytes = ge_from_bytes.unwrap().to_bytes();
-                        let ge_from_bytes = PK::from_bytes_negate_vartime(&ge_bytes[..]);
-                        match ge_from_bytes {
-                            Some(y) => {
-                                let eight: FE = ECScalar::from(&BigInt::from(8));
-                                let new_point = Ed25519Point {
-                                    purpose: "random",
-                                    ge: y,
-                                };
-                                Ok(new_point * eight)
-                            }
-                            None => Err(InvalidPublicKey),
-                        }
-                    }
-                    None => Err(InvalidPublicKey),
-                }
-            }
+        let point = Ed25519Point::from_bytes_unchecked(bytes)?;
+        if point.is_small_order() || !point.is_torsion_free() {
+            return Err(InvalidPublicKey);
         }
+        Ok(point)
     }
 
     fn pk_to_key_slice(&self) -> Vec<u8> {
-        let result = self.ge.to_bytes();
-        result.to_vec()
+        self.ge.compress().to_bytes().to_vec()
     }
 
     fn scalar_mul(&self, fe: &SK) -> Ed25519Point {
-        let vec_0: [u8; 32];
-        vec_0 = [
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 0,
-        ];
-        let p2_point =
-            GeP2::double_scalarmult_vartime(&fe.to_bytes()[..], self.get_element(), &vec_0[..]);
-        let mut p2_bytes = p2_point.to_bytes();
-
-        p2_bytes[31] ^= 1 << 7;
-
-        let ge = GeP3::from_bytes_negate_vartime(&p2_bytes[..]).unwrap();
-
+        // multiplication of the basepoint uses precomputed table
+        let skpk = if self.ge == ED25519_BASEPOINT_POINT {
+            &ED25519_BASEPOINT_TABLE * fe
+        } else {
+            fe * self.ge
+        };
         Ed25519Point {
             purpose: "scalar_point_mul",
-            ge,
+            ge: skpk,
         }
     }
 
     fn add_point(&self, other: &PK) -> Ed25519Point {
-        let pkpk = self.ge + other.to_cached();
-        let mut pk_p2_bytes = pkpk.to_p2().to_bytes();
-        pk_p2_bytes[31] ^= 1 << 7;
         Ed25519Point {
             purpose: "combine",
-            ge: PK::from_bytes_negate_vartime(&pk_p2_bytes).unwrap(),
+            ge: self.ge + other,
         }
     }
 
     fn sub_point(&self, other: &PK) -> Ed25519Point {
-        let pkpk = self.ge - other.to_cached();
-        let mut pk_p2_bytes = pkpk.to_p2().to_bytes();
-        pk_p2_bytes[31] ^= 1 << 7;
-
         Ed25519Point {
             purpose: "sub",
-            ge: PK::from_bytes_negate_vartime(&pk_p2_bytes).unwrap(),
+            ge: self.ge - other,
         }
     }
 
     fn from_coor(_x: &BigInt, _y: &BigInt) -> Ed25519Point {
         unimplemented!();
     }
+
+    fn hash_to_curve(message: &[u8]) -> Ed25519Point {
+        try_and_increment(message, |digest| {
+            let point = Ed25519Point::from_bytes_unchecked(digest)
+                .ok()?
+                .clear_cofactor();
+            if point.is_small_order() {
+                None
+            } else {
+                Some(point)
+            }
+        })
+    }
+
+    fn mul_generator(scalar: &Ed25519Scalar) -> Ed25519Point {
+        Ed25519Point {
+            purpose: "mul_generator",
+            ge: &ED25519_BASEPOINT_TABLE * &scalar.fe,
+        }
+    }
+
+    fn mul_base_point2(scalar: &Ed25519Scalar) -> Ed25519Point {
+        BASE_POINT2_TABLE.mul(scalar)
+    }
+
+    fn multi_scalar_mul(scalars: &[Ed25519Scalar], points: &[Ed25519Point]) -> Self {
+        assert_eq!(
+            scalars.len(),
+            points.len(),
+            "number of scalars doesn't match number of points"
+        );
+        Ed25519Point {
+            purpose: "multi_scalar_mul",
+            ge: EdwardsPoint::multiscalar_mul(
+                scalars.iter().map(|s| s.fe),
+                points.iter().map(|p| p.ge),
+            ),
+        }
+    }
+
+    fn vartime_multi_scalar_mul(scalars: &[Ed25519Scalar], points: &[Ed25519Point]) -> Self {
+        assert_eq!(
+            scalars.len(),
+            points.len(),
+            "number of scalars doesn't match number of points"
+        );
+        Ed25519Point {
+            purpose: "multi_scalar_mul",
+            ge: EdwardsPoint::vartime_multiscalar_mul(
+                scalars.iter().map(|s| s.fe),
+                points.iter().map(|p| p.ge),
+            ),
+        }
+    }
+}
+
+/// Fixed-base table backed by curve25519-dalek's [EdwardsBasepointTable]
+pub struct Ed25519Table(Box<EdwardsBasepointTable>);
+
+impl BasePointTable<Ed25519Point> for Ed25519Table {
+    fn new(base: &Ed25519Point) -> Ed25519Table {
+        Ed25519Table(Box::new(EdwardsBasepointTable::create(&base.ge)))
+    }
+
+    fn base(&self) -> Ed25519Point {
+        Ed25519Point {
+            purpose: "random",
+            ge: self.0.basepoint(),
+        }
+    }
+
+    fn mul(&self, scalar: &Ed25519Scalar) -> Ed25519Point {
+        Ed25519Point {
+            purpose: "mul_table",
+            ge: &*self.0 * &scalar.fe,
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref BASE_POINT2_TABLE: Ed25519Table = {
+        let g: GE = ECPoint::generator();
+        let hash = HSha256::create_hash(&[&g.bytes_compressed_to_big_int()]);
+        let hash = HSha256::create_hash(&[&hash]);
+        let bytes = BigInt::to_bytes(&hash);
+        let h = Ed25519Point::from_bytes_unchecked(&bytes[..])
+            .unwrap()
+            .clear_cofactor();
+        Ed25519Table::new(&h)
+    };
+    /// Modulus of the base field, `2^255 - 19`
+    static ref FIELD_MODULUS: BigInt = BigInt::from(2u32).pow(255u32) - BigInt::from(19u32);
+    /// Coefficient `d = -121665/121666` of the curve equation `-x^2 + y^2 = 1 + d x^2 y^2`
+    static ref EDWARDS_D: BigInt = BigInt::mod_mul(
+        &(&*FIELD_MODULUS - BigInt::from(121_665u32)),
+        &BigInt::mod_inv(&BigInt::from(121_666u32), &FIELD_MODULUS).unwrap(),
+        &FIELD_MODULUS,
+    );
+    /// `2^((p-1)/4)`, a square root of `-1`
+    static ref SQRT_MINUS_ONE: BigInt = BigInt::mod_pow(
+        &BigInt::from(2u32),
+        &((&*FIELD_MODULUS - BigInt::one()) / BigInt::from(4u32)),
+        &FIELD_MODULUS,
+    );
+}
+
+/// Recovers `x` coordinate of a point from its `y` coordinate and the sign of `x`
+///
+/// Returns `None` if there is no point with such `y` coordinate, which doesn't happen for
+/// encodings of valid points.
+fn recover_x(y: &BigInt, negative: bool) -> Option<BigInt> {
+    let p = &*FIELD_MODULUS;
+    let y_sqr = BigInt::mod_mul(y, y, p);
+    // x^2 = (y^2 - 1) / (d y^2 + 1)
+    let u = BigInt::mod_sub(&y_sqr, &BigInt::one(), p);
+    let v = BigInt::mod_add(&BigInt::mod_mul(&EDWARDS_D, &y_sqr, p), &BigInt::one(), p);
+    let x_sqr = BigInt::mod_mul(&u, &BigInt::mod_inv(&v, p)?, p);
+
+    // p = 5 mod 8, candidate root is (x^2)^((p+3)/8), it might need fixing by sqrt(-1)
+    let mut x = BigInt::mod_pow(&x_sqr, &((p + BigInt::from(3u32)) / BigInt::from(8u32)), p);
+    if BigInt::mod_mul(&x, &x, p) != x_sqr {
+        x = BigInt::mod_mul(&x, &SQRT_MINUS_ONE, p);
+    }
+    if BigInt::mod_mul(&x, &x, p) != x_sqr {
+        return None;
+    }
+    let is_odd = BigInt::modulus(&x, &BigInt::from(2u32)) == BigInt::one();
+    if is_odd != negative && x != BigInt::zero() {
+        x = p - x;
+    }
+    Some(x)
+}
+
+impl Ed25519Point {
+    /// Decodes any point of the curve, including points of small order and points having
+    /// a small order component
+    ///
+    /// Shorter inputs are padded with leading zeros, longer inputs are truncated to 32 bytes.
+    pub fn from_bytes_unchecked(bytes: &[u8]) -> Result<Ed25519Point, ErrorKey> {
+        let mut bytes_array_32 = [0u8; 32];
+        if bytes.len() < 32 {
+            bytes_array_32[32 - bytes.len()..].copy_from_slice(bytes);
+        } else {
+            bytes_array_32.copy_from_slice(&bytes[..32]);
+        }
+        let ge = CompressedEdwardsY(bytes_array_32)
+            .decompress()
+            .ok_or(InvalidPublicKey)?;
+        Ok(Ed25519Point {
+            purpose: "from_bytes_unchecked",
+            ge,
+        })
+    }
+
+    /// Checks whether the point has order dividing 8 (the cofactor), identity included
+    pub fn is_small_order(&self) -> bool {
+        self.ge.is_small_order()
+    }
+
+    /// Checks whether the point belongs to the prime order subgroup
+    pub fn is_torsion_free(&self) -> bool {
+        self.ge.is_torsion_free()
+    }
+
+    /// Multiplies the point by the cofactor 8, result belongs to the prime order subgroup
+    pub fn clear_cofactor(&self) -> Ed25519Point {
+        Ed25519Point {
+            purpose: "clear_cofactor",
+            ge: self.ge.mul_by_cofactor(),
+        }
+    }
+
+    /// Returns u-coordinate of the point on the birationally equivalent Montgomery curve, that's
+    /// the point's X25519 public key (see [x25519](super::x25519))
+    pub fn to_montgomery_u(&self) -> [u8; 32] {
+        self.ge.to_montgomery().to_bytes()
+    }
+
+    /// Maps X25519 public key `u` to one of the two Edwards points having that u-coordinate,
+    /// `negative` chooses the sign of `x`
+    ///
+    /// Returns error if `u` lies on the quadratic twist. Resulting point isn't multiplied by
+    /// the cofactor.
+    pub fn from_montgomery_u(u: &[u8; 32], negative: bool) -> Result<Ed25519Point, ErrorKey> {
+        let ge = MontgomeryPoint(*u)
+            .to_edwards(negative as u8)
+            .ok_or(InvalidPublicKey)?;
+        Ok(Ed25519Point {
+            purpose: "from_montgomery_u",
+            ge,
+        })
+    }
 }
 
 impl Mul<Ed25519Scalar> for Ed25519Point {
@@ -550,6 +632,8 @@ impl<'de> Visitor<'de> for Ed25519PointVisitor {
 }
 
 #[allow(clippy::many_single_char_names)]
+#[allow(deprecated)]
+#[deprecated(note = "ignores the sign of x, use `Ed25519Point::x_coor` instead")]
 //helper function, based on https://ed25519.cr.yp.to/python/ed25519.py
 pub fn xrecover(y_coor: BigInt) -> BigInt {
     //   let d = "37095705934669439343138083508754565189542113879843219016388785533085940283555";
@@ -570,7 +654,7 @@ pub fn xrecover(y_coor: BigInt) -> BigInt {
 
     let mut x = expmod(&x_sqr, &q_plus_3_div_8, &q);
     if BigInt::mod_sub(&(x.clone() * x.clone()), &x_sqr, &q) != BigInt::zero() {
-        let q_minus_1_div_4 = (q.clone() - BigInt::from(3i32)) / BigInt::from(4i32);
+        let q_minus_1_div_4 = (q.clone() - BigInt::from(1i32)) / BigInt::from(4i32);
         let i = expmod(&BigInt::from(2i32), &q_minus_1_div_4, &q);
         x = BigInt::mod_mul(&x, &i, &q);
     }
@@ -581,12 +665,14 @@ pub fn xrecover(y_coor: BigInt) -> BigInt {
     x
 }
 
+#[deprecated(note = "use `BigInt::mod_pow` instead")]
 //helper function, based on https://ed25519.cr.yp.to/python/ed25519.py
 pub fn expmod(b: &BigInt, e: &BigInt, m: &BigInt) -> BigInt {
     let one = BigInt::one();
     if e.clone() == BigInt::zero() {
         return one;
     };
+    #[allow(deprecated)]
     let t_temp = expmod(b, &(e.clone() / BigInt::from(2u32)), m);
     let mut t = BigInt::mod_pow(&t_temp, &BigInt::from(2u32), m);
 
@@ -603,37 +689,34 @@ mod tests {
     use crate::elliptic::curves::traits::ECPoint;
     use crate::elliptic::curves::traits::ECScalar;
     use crate::BigInt;
+    use rand::Rng;
 
     type GE = Ed25519Point;
     type FE = Ed25519Scalar;
 
     #[test]
-    #[allow(clippy::op_ref)] // Enables type inference.
     fn test_serdes_pk() {
         let mut pk = GE::generator();
         let mut s = serde_json::to_string(&pk).expect("Failed in serialization");
         let mut des_pk: GE = serde_json::from_str(&s).expect("Failed in deserialization");
-        let eight = ECScalar::from(&BigInt::from(8));
-        assert_eq!(des_pk, pk * &eight);
+        assert_eq!(des_pk, pk);
 
         pk = GE::base_point2();
         s = serde_json::to_string(&pk).expect("Failed in serialization");
         des_pk = serde_json::from_str(&s).expect("Failed in deserialization");
-        assert_eq!(des_pk, pk * &eight);
+        assert_eq!(des_pk, pk);
 
         // deserialize serialization of bytes_str < 64 hex
         s = "{\"bytes_str\":\"2c42d43e1a277e8f3d7d5aacde519c80b913341e425b624d867f790d1578e0\"}"
             .to_string();
         des_pk = serde_json::from_str(&s).expect("Failed in deserialization");
-        let eight_inverse: FE = eight.invert();
-        let des_pk_mul = des_pk * &eight_inverse;
         assert_eq!(
-            des_pk_mul.bytes_compressed_to_big_int().to_hex(),
+            des_pk.bytes_compressed_to_big_int().to_hex(),
             "2c42d43e1a277e8f3d7d5aacde519c80b913341e425b624d867f790d1578e0"
         );
 
         // serialize with padding
-        let ser_pk = serde_json::to_string(&des_pk_mul).expect("Failed in serialization");
+        let ser_pk = serde_json::to_string(&des_pk).expect("Failed in serialization");
         assert_eq!(
             &ser_pk,
             "{\"bytes_str\":\"002c42d43e1a277e8f3d7d5aacde519c80b913341e425b624d867f790d1578e0\"}"
@@ -641,30 +724,26 @@ mod tests {
 
         // deserialize a padded serialization
         let des_pk2: GE = serde_json::from_str(&ser_pk).expect("Failed in deserialization");
-        assert_eq!(des_pk_mul, des_pk2 * &eight_inverse);
+        assert_eq!(des_pk, des_pk2);
     }
 
     #[test]
-    #[allow(clippy::op_ref)] // Enables type inference.
     fn bincode_pk() {
         let pk = GE::generator();
         let encoded = bincode::serialize(&pk).unwrap();
         let decoded: Ed25519Point = bincode::deserialize(encoded.as_slice()).unwrap();
-        let eight = ECScalar::from(&BigInt::from(8));
-        assert_eq!(pk * &eight, decoded);
+        assert_eq!(pk, decoded);
     }
 
     #[test]
     #[should_panic]
-    #[allow(clippy::op_ref)] // Enables type inference.
     fn test_serdes_bad_pk() {
         let pk = GE::generator();
         let s = serde_json::to_string(&pk).expect("Failed in serialization");
         // we make sure that the string encodes invalid point:
         let s: String = s.replace("5866", "5867");
         let des_pk: GE = serde_json::from_str(&s).expect("Failed in deserialization");
-        let eight = ECScalar::from(&BigInt::from(8));
-        assert_eq!(des_pk, pk * &eight);
+        assert_eq!(des_pk, pk);
     }
 
     #[test]
@@ -760,8 +839,15 @@ mod tests {
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4, 5,
             6,
         ];
-        let result = Ed25519Point::from_bytes(&test_vec);
-        assert!(result.is_ok())
+        // encodes a point of the curve which has a small order component
+        let point = Ed25519Point::from_bytes_unchecked(&test_vec).unwrap();
+        assert!(!point.is_torsion_free());
+        assert!(Ed25519Point::from_bytes(&test_vec).is_err());
+        let cleared = point.clear_cofactor();
+        assert_eq!(
+            Ed25519Point::from_bytes(&cleared.pk_to_key_slice()).unwrap(),
+            cleared
+        );
     }
     #[test]
     fn test_from_bytes_3() {
@@ -769,8 +855,65 @@ mod tests {
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
             0, 0, 0, 1, 2, 3, 4, 5, 6,
         ];
-        let result = Ed25519Point::from_bytes(&test_vec);
-        assert!(result.is_ok())
+        // first 32 bytes encode y = 0, that's a point of order 4
+        let point = Ed25519Point::from_bytes_unchecked(&test_vec).unwrap();
+        assert!(point.is_small_order());
+        assert!(Ed25519Point::from_bytes(&test_vec).is_err());
+    }
+
+    #[test]
+    fn small_order_points() {
+        // all 8 points of order dividing 8: identity, order 2, two points of order 4 and
+        // four points of order 8
+        let torsion = [
+            "0100000000000000000000000000000000000000000000000000000000000000",
+            "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000080",
+            "26e8958fc2b227b045c3f489f2ef98f0d5dfac05d3c63339b13802886d53fc05",
+            "26e8958fc2b227b045c3f489f2ef98f0d5dfac05d3c63339b13802886d53fc85",
+            "c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac037a",
+            "c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac03fa",
+        ];
+        let identity = hex::decode(torsion[0]).unwrap();
+        let g = GE::generator();
+        for t in torsion.iter() {
+            let bytes = hex::decode(t).unwrap();
+            let point = Ed25519Point::from_bytes_unchecked(&bytes).unwrap();
+            assert_eq!(point.pk_to_key_slice(), bytes);
+            assert!(point.is_small_order(), "{}", t);
+            assert!(Ed25519Point::from_bytes(&bytes).is_err(), "{}", t);
+            assert_eq!(point.clear_cofactor().pk_to_key_slice(), identity);
+
+            // mixed order point: generator plus a torsion component
+            let mixed = g + point;
+            assert!(!mixed.is_small_order());
+            assert_eq!(mixed.is_torsion_free(), bytes == identity);
+            assert_eq!(
+                Ed25519Point::from_bytes(&mixed.pk_to_key_slice()).is_ok(),
+                bytes == identity
+            );
+            assert_eq!(mixed.clear_cofactor(), g.clear_cofactor());
+        }
+        assert!(g.is_torsion_free());
+        assert!(!g.is_small_order());
+    }
+
+    #[test]
+    fn clear_cofactor_multiplies_by_eight() {
+        let p: GE = GE::generator() * FE::new_random();
+        let eight: FE = ECScalar::from(&BigInt::from(8));
+        assert_eq!(p.clear_cofactor(), p * eight);
+    }
+
+    #[test]
+    fn random_scalars_are_uniform() {
+        // scalars used to be multiplied by 8, so all of them were divisible by 8
+        let not_divisible_by_8 = (0..64)
+            .map(|_| FE::new_random().to_big_int())
+            .filter(|n| BigInt::modulus(n, &BigInt::from(8)) != BigInt::zero())
+            .count();
+        assert!(not_divisible_by_8 > 0);
     }
 
     #[test]
@@ -793,11 +936,10 @@ mod tests {
         let tv_bn = BigInt::from_bytes(&test_vec[..]);
         let test_fe: FE = ECScalar::from(&tv_bn);
         let test_ge = g * test_fe;
-        let test_ge_bytes = test_ge.get_element().to_bytes();
+        let test_ge_bytes = test_ge.get_element().compress().to_bytes();
         let test_ge2: GE = ECPoint::from_bytes(&test_ge_bytes[..]).unwrap();
-        let eight: FE = ECScalar::from(&BigInt::from(8));
 
-        assert_eq!(test_ge2, test_ge * eight);
+        assert_eq!(test_ge2, test_ge);
     }
 
     #[test]
@@ -814,5 +956,255 @@ mod tests {
             g.x_coor().unwrap().to_hex(),
             "216936d3cd6e53fec0a4e231fdd6dc5c692cc7609525a7b2c9562d608f25d51a"
         );
+        assert_eq!(
+            g.y_coor().unwrap().to_hex(),
+            "6666666666666666666666666666666666666666666666666666666666666658"
+        );
+    }
+
+    // The vectors below were produced by the previous implementation built on cryptoxide, they
+    // make sure that serialized points and scalars are still accepted and produced the same way.
+    #[test]
+    fn wire_format_is_unchanged() {
+        let g = GE::generator();
+        assert_eq!(
+            serde_json::to_string(&g).unwrap(),
+            "{\"bytes_str\":\"5866666666666666666666666666666666666666666666666666666666666666\"}"
+        );
+        assert_eq!(
+            hex::encode(bincode::serialize(&g).unwrap()),
+            "4000000000000000353836363636363636363636363636363636363636363636\
+             3636363636363636363636363636363636363636363636363636363636363636\
+             3636363636363636"
+        );
+
+        let s_hex = "102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20";
+        let s: FE = ECScalar::from(&BigInt::from_hex(s_hex).unwrap());
+        assert_eq!(serde_json::to_string(&s).unwrap(), format!("\"{}\"", s_hex));
+        let des_s: FE = serde_json::from_str(&format!("\"{}\"", s_hex)).unwrap();
+        assert_eq!(des_s, s);
+
+        let p = g * s;
+        assert_eq!(
+            hex::encode(p.pk_to_key_slice()),
+            "80334024b705b5fd76b1bce1b26d96234ab7b2d989987895fa72c43b3e5c5f85"
+        );
+        assert_eq!(
+            p.y_coor().unwrap().to_hex(),
+            "55f5c3e3bc472fa95789889d9b2b74a23966db2e1bcb176fdb505b724403380"
+        );
+        // the previous implementation ignored the sign of x and returned p - x here
+        assert_eq!(
+            p.x_coor().unwrap().to_hex(),
+            "2a6c6a91efa486226f04bd36d09237f9e16273181f24417febe171d572760f"
+        );
+
+        assert_eq!(
+            hex::encode(GE::base_point2().pk_to_key_slice()),
+            "c614d58236b79079b6745bb2fdfcab404efa6721fa2eb7b0d77c7b8d24452b08"
+        );
+        assert_eq!(
+            hex::encode(GE::hash_to_curve(b"abc").pk_to_key_slice()),
+            "81358482b8d9d1679aaac3876243dd3dd3711f8155d40d3d6cd22ccdc9b33e32"
+        );
+
+        // scalars wider than 512 bits keep their 64 most significant bytes
+        let wide: FE = ECScalar::from(&(BigInt::from(2).pow(600) + BigInt::from(12345)));
+        assert_eq!(
+            wide.to_big_int().to_hex(),
+            "1b399411b7c309a3dceec73d217f5be680392762298a31de2edf685ab128969"
+        );
+    }
+
+    /// Reference implementation the backend used to be built on
+    mod cryptoxide_reference {
+        use cryptoxide::curve25519::{ge_scalarmult_base, sc_muladd, sc_reduce, Fe, GeP2, GeP3};
+
+        pub fn decode(bytes: &[u8; 32]) -> Option<GeP3> {
+            // cryptoxide decodes negation of the point, so we decode it twice
+            let negated = GeP3::from_bytes_negate_vartime(bytes)?;
+            GeP3::from_bytes_negate_vartime(&negated.to_bytes())
+        }
+
+        pub fn mul_generator(scalar: &[u8; 32]) -> [u8; 32] {
+            ge_scalarmult_base(scalar).to_bytes()
+        }
+
+        pub fn scalar_mul(point: &[u8; 32], scalar: &[u8; 32]) -> [u8; 32] {
+            GeP2::double_scalarmult_vartime(scalar, decode(point).unwrap(), &[0u8; 32]).to_bytes()
+        }
+
+        pub fn add(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+            (decode(a).unwrap() + decode(b).unwrap().to_cached())
+                .to_p2()
+                .to_bytes()
+        }
+
+        pub fn sub(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+            (decode(a).unwrap() - decode(b).unwrap().to_cached())
+                .to_p2()
+                .to_bytes()
+        }
+
+        pub fn y_coor(point: &[u8; 32]) -> [u8; 32] {
+            Fe::from_bytes(point).to_bytes()
+        }
+
+        pub fn reduce(bytes_le: &[u8; 64]) -> [u8; 32] {
+            let mut bytes = *bytes_le;
+            sc_reduce(&mut bytes);
+            let mut reduced = [0u8; 32];
+            reduced.copy_from_slice(&bytes[..32]);
+            reduced
+        }
+
+        /// Computes `a * b + c`
+        pub fn muladd(a: &[u8; 32], b: &[u8; 32], c: &[u8; 32]) -> [u8; 32] {
+            let mut result = [0u8; 32];
+            sc_muladd(&mut result, a, b, c);
+            result
+        }
+    }
+
+    fn random_bytes<A: AsMut<[u8]> + Default>() -> A {
+        let mut bytes = A::default();
+        rand::thread_rng().fill(bytes.as_mut());
+        bytes
+    }
+
+    fn scalar_bytes(scalar: &FE) -> [u8; 32] {
+        scalar.get_element().to_bytes()
+    }
+
+    fn point_bytes(point: &GE) -> [u8; 32] {
+        point.get_element().compress().to_bytes()
+    }
+
+    #[test]
+    fn differential_scalar_arithmetic() {
+        let one = <FE as ECScalar>::from(&BigInt::one());
+        for _ in 0..64 {
+            let wide: [u8; 32] = random_bytes();
+            let mut wide_64 = [0u8; 64];
+            wide_64[..32].copy_from_slice(&wide);
+            wide_64[32..].copy_from_slice(&random_bytes::<[u8; 32]>());
+            let a: FE = ECScalar::from(&BigInt::from_bytes_le(&wide_64));
+            assert_eq!(scalar_bytes(&a), cryptoxide_reference::reduce(&wide_64));
+
+            let b = FE::new_random();
+            let zero = [0u8; 32];
+            let (a_bytes, b_bytes) = (scalar_bytes(&a), scalar_bytes(&b));
+            assert_eq!(
+                scalar_bytes(&(a * b)),
+                cryptoxide_reference::muladd(&a_bytes, &b_bytes, &zero)
+            );
+            assert_eq!(
+                scalar_bytes(&(a + b)),
+                cryptoxide_reference::muladd(&a_bytes, &scalar_bytes(&one), &b_bytes)
+            );
+            assert_eq!(
+                cryptoxide_reference::muladd(
+                    &scalar_bytes(&a.sub(&b.get_element())),
+                    &scalar_bytes(&one),
+                    &b_bytes
+                ),
+                a_bytes
+            );
+            assert_eq!(
+                cryptoxide_reference::muladd(&a_bytes, &scalar_bytes(&a.invert()), &zero),
+                scalar_bytes(&one)
+            );
+        }
+    }
+
+    #[test]
+    fn differential_point_arithmetic() {
+        for _ in 0..32 {
+            let a = FE::new_random();
+            let b = FE::new_random();
+            let p = GE::generator() * a;
+            let q = GE::base_point2() * b;
+            let (p_bytes, q_bytes) = (point_bytes(&p), point_bytes(&q));
+
+            assert_eq!(
+                p_bytes,
+                cryptoxide_reference::mul_generator(&scalar_bytes(&a))
+            );
+            assert_eq!(point_bytes(&GE::mul_generator(&a)), p_bytes);
+            assert_eq!(
+                point_bytes(&(q * a)),
+                cryptoxide_reference::scalar_mul(&q_bytes, &scalar_bytes(&a))
+            );
+            assert_eq!(
+                point_bytes(&(p + q)),
+                cryptoxide_reference::add(&p_bytes, &q_bytes)
+            );
+            assert_eq!(
+                point_bytes(&p.sub_point(&q.get_element())),
+                cryptoxide_reference::sub(&p_bytes, &q_bytes)
+            );
+
+            let y = p.y_coor().unwrap();
+            assert_eq!(
+                y,
+                BigInt::from_bytes_le(&cryptoxide_reference::y_coor(&p_bytes))
+            );
+            // x coordinate is recovered from the compressed point, so besides comparing it with
+            // the old `xrecover` (which always returns even x) we check the curve equation
+            // -x^2 + y^2 = 1 + d x^2 y^2 and that the sign bit matches parity of x
+            let x = p.x_coor().unwrap();
+            let modulus = &*super::FIELD_MODULUS;
+            #[allow(deprecated)]
+            let old_x = super::xrecover(y.clone());
+            if p_bytes[31] >> 7 == 0 {
+                assert_eq!(x, old_x);
+            } else {
+                assert_eq!(x, modulus - old_x);
+            }
+            let x_sqr = BigInt::mod_mul(&x, &x, modulus);
+            let y_sqr = BigInt::mod_mul(&y, &y, modulus);
+            assert_eq!(
+                BigInt::mod_sub(&y_sqr, &x_sqr, modulus),
+                BigInt::mod_add(
+                    &BigInt::one(),
+                    &BigInt::mod_mul(
+                        &super::EDWARDS_D,
+                        &BigInt::mod_mul(&x_sqr, &y_sqr, modulus),
+                        modulus
+                    ),
+                    modulus
+                )
+            );
+            assert_eq!(
+                BigInt::modulus(&x, &BigInt::from(2)),
+                BigInt::from(u32::from(p_bytes[31] >> 7))
+            );
+        }
+    }
+
+    #[test]
+    fn differential_decoding() {
+        let mut encodings: Vec<[u8; 32]> = (0..256).map(|_| random_bytes()).collect();
+        for special in [
+            // identity with the sign bit set
+            "0100000000000000000000000000000000000000000000000000000000000080",
+            // non-canonical encodings of y = 0 and y = 1
+            "edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+            "eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        ]
+        .iter()
+        {
+            let mut bytes = [0u8; 32];
+            bytes.copy_from_slice(&hex::decode(special).unwrap());
+            encodings.push(bytes);
+        }
+        for bytes in encodings.iter() {
+            let new = Ed25519Point::from_bytes_unchecked(bytes).ok();
+            let old = cryptoxide_reference::decode(bytes);
+            assert_eq!(new.is_some(), old.is_some(), "{}", hex::encode(bytes));
+            if let (Some(new), Some(old)) = (new, old) {
+                assert_eq!(point_bytes(&new), old.to_bytes(), "{}", hex::encode(bytes));
+            }
+        }
     }
 }
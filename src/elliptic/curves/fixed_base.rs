@@ -0,0 +1,192 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+//! Radix-16 fixed-base table shared by backends that don't have a native one
+//!
+//! Row `i` holds `(j + 1) * 16^i * B` for `j` in `0..16`. Scalar `k = Σ d_i * 16^i` is
+//! multiplied by summing `rows[i][d_i]` (that is `(d_i + 1) * 16^i * B`) over all rows, plus a
+//! correction `-(Σ 16^i) * B`. Every row contributes exactly one point and none of the table
+//! entries is the point at infinity, so the table also suits backends that can only sum valid
+//! points.
+//!
+//! Lookups don't depend on digits of the scalar: every entry of a row is read and the right one
+//! is picked by constant-time conditional selection (see [CtLookup]).
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+use crate::arithmetic::traits::*;
+use crate::BigInt;
+
+const WINDOW_SIZE: usize = 16;
+
+/// Point which can be stored in [WindowTable]
+///
+/// Table keeps entries in a representation that can be conditionally selected in constant time
+/// (it may differ from the point type, eg. raw coordinates or encoding) and converts the
+/// selected entry back into a point.
+pub(crate) trait CtLookup: Sized {
+    type Entry: Clone;
+
+    fn to_entry(&self) -> Self::Entry;
+    fn from_entry(entry: &Self::Entry) -> Self;
+    /// Returns `a` if `choice` is 0 and `b` if `choice` is 1, without branching on `choice`
+    fn conditional_select(a: &Self::Entry, b: &Self::Entry, choice: Choice) -> Self::Entry;
+}
+
+/// Implements [CtLookup] for points that implement [ConditionallySelectable]
+macro_rules! impl_ct_lookup_via_subtle {
+    ($($point:ty),+ $(,)?) => {$(
+        impl $crate::elliptic::curves::fixed_base::CtLookup for $point {
+            type Entry = $point;
+
+            fn to_entry(&self) -> $point {
+                *self
+            }
+
+            fn from_entry(entry: &$point) -> $point {
+                *entry
+            }
+
+            fn conditional_select(a: &$point, b: &$point, choice: ::subtle::Choice) -> $point {
+                ::subtle::ConditionallySelectable::conditional_select(a, b, choice)
+            }
+        }
+    )+};
+}
+pub(crate) use impl_ct_lookup_via_subtle;
+
+/// Element-wise constant-time selection of arrays (eg. limbs of coordinates or encodings)
+pub(crate) fn select_array<T, const N: usize>(a: &[T; N], b: &[T; N], choice: Choice) -> [T; N]
+where
+    T: ConditionallySelectable,
+{
+    let mut selected = *a;
+    for (s, b) in selected.iter_mut().zip(b.iter()) {
+        s.conditional_assign(b, choice);
+    }
+    selected
+}
+
+pub(crate) struct WindowTable<T: CtLookup> {
+    rows: Vec<Vec<T::Entry>>,
+    correction: T::Entry,
+}
+
+impl<T: CtLookup + Clone> WindowTable<T> {
+    /// Precomputes table of `base` for scalars of up to `bits` bits
+    pub fn new(base: T, bits: usize, add: impl Fn(&T, &T) -> T, neg: impl Fn(&T) -> T) -> Self {
+        let num_rows = usize::div_ceil(bits, 4);
+        let mut rows: Vec<Vec<T>> = Vec::with_capacity(num_rows);
+        let mut row_base = base;
+        for _ in 0..num_rows {
+            let mut row = Vec::with_capacity(WINDOW_SIZE);
+            row.push(row_base);
+            while row.len() < WINDOW_SIZE {
+                row.push(add(&row[row.len() - 1], &row[0]));
+            }
+            row_base = row[WINDOW_SIZE - 1].clone();
+            rows.push(row);
+        }
+        let offset = rows[1..]
+            .iter()
+            .fold(rows[0][0].clone(), |acc, row| add(&acc, &row[0]));
+        let correction = neg(&offset).to_entry();
+        let rows = rows
+            .iter()
+            .map(|row| row.iter().map(T::to_entry).collect())
+            .collect();
+        WindowTable { rows, correction }
+    }
+
+    /// Returns points that sum up to `k * B`
+    ///
+    /// Memory access pattern and the number of returned points don't depend on `k`.
+    ///
+    /// ## Panics
+    /// Panics if `k` is negative or doesn't fit into the table
+    pub fn lookup(&self, k: &BigInt) -> impl Iterator<Item = T> + '_ {
+        assert!(
+            *k >= BigInt::zero() && k.bit_length() <= 4 * self.rows.len(),
+            "scalar doesn't fit into the table"
+        );
+        let mut bytes = k.to_bytes_be_padded(usize::div_ceil(self.rows.len(), 2));
+        bytes.reverse();
+        self.rows
+            .iter()
+            .enumerate()
+            .map(move |(i, row)| {
+                let digit = (bytes[i / 2] >> (4 * (i % 2))) & 0x0f;
+                let mut selected = row[0].clone();
+                for (j, entry) in row.iter().enumerate().skip(1) {
+                    selected = T::conditional_select(&selected, entry, (j as u8).ct_eq(&digit));
+                }
+                T::from_entry(&selected)
+            })
+            .chain(std::iter::once(T::from_entry(&self.correction)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arithmetic::traits::*;
+    use crate::elliptic::curves::traits::*;
+    use crate::test_for_all_curves;
+    use crate::BigInt;
+
+    fn test_scalars<S: ECScalar>() -> Vec<S> {
+        let q = S::q();
+        let mut scalars: Vec<S> = [1, 2, 15, 16, 17, 255, 256]
+            .iter()
+            .map(|n| S::from(&BigInt::from(*n)))
+            .collect();
+        scalars.push(S::from(&(&q - BigInt::one())));
+        scalars.push(S::from(&(&q >> 1)));
+        scalars.extend((0..5).map(|_| S::new_random()));
+        scalars
+    }
+
+    test_for_all_curves!(mul_generator_matches_scalar_mul);
+    fn mul_generator_matches_scalar_mul<P>()
+    where
+        P: ECPoint + Clone + std::fmt::Debug,
+        P::Scalar: Clone,
+    {
+        for s in test_scalars::<P::Scalar>() {
+            assert_eq!(P::mul_generator(&s), P::generator() * s.clone());
+            assert_eq!(P::mul_base_point2(&s), P::base_point2() * s);
+        }
+    }
+
+    test_for_all_curves!(user_table_matches_scalar_mul);
+    fn user_table_matches_scalar_mul<P>()
+    where
+        P: ECPoint + Clone + std::fmt::Debug,
+        P::Scalar: Clone,
+    {
+        let h = P::hash_to_curve(b"fixed base table test");
+        let table = P::Table::new(&h);
+        assert_eq!(table.base(), h);
+        for s in test_scalars::<P::Scalar>() {
+            assert_eq!(table.mul(&s), h.clone() * s);
+        }
+    }
+
+    test_for_all_curves!(scalar_mul_table_matches_curve_table);
+    fn scalar_mul_table_matches_curve_table<P>()
+    where
+        P: ECPoint + Clone + std::fmt::Debug,
+        P::Scalar: Clone,
+    {
+        let h = P::hash_to_curve(b"fixed base table test");
+        let table = P::Table::new(&h);
+        let fallback = ScalarMulTable::new(&h);
+        assert_eq!(fallback.base(), h);
+        for s in test_scalars::<P::Scalar>() {
+            assert_eq!(fallback.mul(&s), table.mul(&s));
+        }
+    }
+}
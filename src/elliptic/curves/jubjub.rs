@@ -0,0 +1,200 @@
+//! Jubjub: twisted Edwards curve defined over the scalar field of BLS12-381
+//!
+//! Points are elements of the prime order subgroup, encoded the same way as in Zcash Sapling
+//! (32 bytes: little-endian `v` coordinate with the sign of `u` in the most significant bit).
+
+use super::fixed_base::{impl_ct_lookup_via_subtle, WindowTable};
+use super::prime_group::{
+    ff_modulus, ff_scalar_from_bytes_be, ff_scalar_to_bytes_be, impl_prime_group_curve,
+    to_bytes_le_32, PrimeGroupBackend,
+};
+use super::traits::{try_and_increment, BasePointTable, ECPoint, ECScalar};
+use crate::arithmetic::traits::*;
+use crate::BigInt;
+use crate::ErrorKey;
+use ff::Field;
+use group::cofactor::CofactorGroup;
+use group::{Group, GroupEncoding};
+use jubjub::{AffinePoint, ExtendedPoint, Fr, SubgroupPoint};
+
+pub type SK = Fr;
+pub type PK = SubgroupPoint;
+
+struct Jubjub;
+
+impl PrimeGroupBackend for Jubjub {
+    type Scalar = SK;
+    type Point = PK;
+
+    fn curve_order() -> BigInt {
+        ff_modulus::<Fr>()
+    }
+
+    fn scalar_from_bytes_be(bytes: &[u8; 32]) -> SK {
+        ff_scalar_from_bytes_be(bytes).expect("scalar is reduced")
+    }
+
+    fn scalar_to_bytes_be(scalar: &SK) -> [u8; 32] {
+        ff_scalar_to_bytes_be(scalar)
+    }
+
+    fn scalar_invert(scalar: &SK) -> Option<SK> {
+        scalar.invert().into()
+    }
+
+    fn scalar_zero() -> SK {
+        Fr::ZERO
+    }
+
+    fn identity() -> PK {
+        SubgroupPoint::identity()
+    }
+
+    fn generator() -> PK {
+        SubgroupPoint::generator()
+    }
+
+    fn point_to_bytes(point: &PK) -> [u8; 32] {
+        point.to_bytes()
+    }
+
+    fn point_from_bytes(bytes: &[u8; 32]) -> Option<PK> {
+        SubgroupPoint::from_bytes(bytes).into()
+    }
+
+    fn coordinates(point: &PK) -> Option<(BigInt, BigInt)> {
+        let affine = AffinePoint::from(ExtendedPoint::from(*point));
+        Some((
+            BigInt::from_bytes_le(&affine.get_u().to_bytes()),
+            BigInt::from_bytes_le(&affine.get_v().to_bytes()),
+        ))
+    }
+
+    fn point_from_coordinates(u: &BigInt, v: &BigInt) -> Option<PK> {
+        let u_bytes = to_bytes_le_32(u)?;
+        let mut encoding = to_bytes_le_32(v)?;
+        if encoding[31] & 0x80 != 0 {
+            return None;
+        }
+        encoding[31] |= (u_bytes[0] & 1) << 7;
+        let point = Self::point_from_bytes(&encoding)?;
+        // encoding only carries the sign of `u`, so we make sure `u` is the one we were given
+        match Self::coordinates(&point) {
+            Some((point_u, _)) if point_u == *u => Some(point),
+            _ => None,
+        }
+    }
+
+    fn hash_to_point(message: &[u8]) -> PK {
+        try_and_increment(message, |digest| {
+            let mut encoding = [0u8; 32];
+            encoding.copy_from_slice(digest);
+            let point: Option<AffinePoint> = AffinePoint::from_bytes(encoding).into();
+            let point = ExtendedPoint::from(point?).clear_cofactor();
+            if bool::from(point.is_identity()) {
+                None
+            } else {
+                Some(point)
+            }
+        })
+    }
+}
+
+impl_ct_lookup_via_subtle!(PK);
+
+impl_prime_group_curve! {
+    backend: Jubjub,
+    scalar: JubjubScalar,
+    point: JubjubPoint,
+    table: JubjubTable,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JubjubPoint, FE, GE};
+    use crate::arithmetic::traits::*;
+    use crate::elliptic::curves::traits::{ECPoint, ECScalar};
+    use crate::BigInt;
+
+    #[test]
+    fn serialize_deserialize_point() {
+        let point = GE::generator() * FE::new_random();
+        let s = serde_json::to_string(&point).expect("Failed in serialization");
+        let des_point: GE = serde_json::from_str(&s).expect("Failed in deserialization");
+        assert_eq!(des_point, point);
+        let encoded = bincode::serialize(&point).unwrap();
+        let decoded: JubjubPoint = bincode::deserialize(encoded.as_slice()).unwrap();
+        assert_eq!(point, decoded);
+    }
+
+    #[test]
+    fn serialize_deserialize_scalar() {
+        let scalar: FE = ECScalar::new_random();
+        let s = serde_json::to_string(&scalar).expect("Failed in serialization");
+        let des_scalar: FE = serde_json::from_str(&s).expect("Failed in deserialization");
+        assert_eq!(des_scalar, scalar);
+    }
+
+    #[test]
+    fn curve_order() {
+        let q =
+            BigInt::from_hex("0e7db4ea6533afa906673b0101343b00a6682093ccc81082d0970e5ed6f72cb7")
+                .unwrap();
+        assert_eq!(FE::q(), q);
+        assert_eq!(
+            GE::generator() * <FE as ECScalar>::from(&(q - 1)),
+            -GE::generator()
+        );
+    }
+
+    #[test]
+    fn point_encoding_roundtrip() {
+        let g = GE::generator();
+        assert_eq!(GE::from_bytes(&g.pk_to_key_slice()).unwrap(), g);
+        let (u, v) = (g.x_coor().unwrap(), g.y_coor().unwrap());
+        assert_eq!(GE::from_coor(&u, &v), g);
+    }
+
+    #[test]
+    fn rejects_points_out_of_subgroup() {
+        // (0, -1) has order 2
+        let mut encoding = [0u8; 32];
+        encoding.copy_from_slice(
+            &hex::decode("73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000000")
+                .unwrap(),
+        );
+        encoding.reverse();
+        assert!(bool::from(
+            jubjub::AffinePoint::from_bytes(encoding).is_some()
+        ));
+        assert!(GE::from_bytes(&encoding).is_err());
+        // non-canonical encoding of `v`
+        assert!(GE::from_bytes(&[0xff; 32]).is_err());
+        assert!(GE::from_bytes(&[0; 31]).is_err());
+    }
+
+    #[test]
+    fn base_point2_nothing_up_my_sleeve() {
+        let g = GE::generator();
+        let h = GE::hash_to_curve(&g.pk_to_key_slice());
+        assert_eq!(GE::base_point2(), h);
+        assert_ne!(h, g);
+    }
+
+    #[test]
+    fn hash_to_curve_is_deterministic() {
+        assert_eq!(GE::hash_to_curve(b"abc"), GE::hash_to_curve(b"abc"));
+        assert_ne!(GE::hash_to_curve(b"abc"), GE::hash_to_curve(b"abd"));
+        // result is in the prime order subgroup
+        let h = GE::hash_to_curve(b"abc");
+        assert_eq!(GE::from_bytes(&h.pk_to_key_slice()).unwrap(), h);
+    }
+
+    #[test]
+    fn test_invert() {
+        let a: FE = ECScalar::new_random();
+        let a_inv = a.invert();
+        let one: FE = ECScalar::from(&BigInt::one());
+        assert_eq!(a * a_inv, one);
+    }
+}
@@ -1,6 +1,16 @@
+pub mod baby_jubjub;
 pub mod bls12_381;
+pub mod bn254;
 pub mod curve_ristretto;
+pub mod dynamic;
 pub mod ed25519;
+mod fixed_base;
+pub mod jubjub;
 pub mod p256;
+pub mod p384;
+pub mod p521;
+pub mod pasta;
+mod prime_group;
 pub mod secp256_k1;
 pub mod traits;
+pub mod x25519;
@@ -1,6 +1,7 @@
 // NIST P-256 elliptic curve utility functions.
 
-use super::traits::{ECPoint, ECScalar};
+use super::fixed_base::{impl_ct_lookup_via_subtle, WindowTable};
+use super::traits::{try_and_increment, BasePointTable, ECPoint, ECScalar};
 use crate::arithmetic::traits::*;
 use crate::BigInt;
 use crate::ErrorKey;
@@ -28,7 +29,7 @@ pub struct Secp256r1Scalar {
     fe: SK,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy)]
 pub struct Secp256r1Point {
     purpose: &'static str,
     ge: PK,
@@ -89,14 +90,8 @@ impl ECScalar for Secp256r1Scalar {
     fn from(n: &BigInt) -> Secp256r1Scalar {
         let curve_order = Secp256r1Scalar::q();
         let n_reduced = BigInt::mod_add(n, &BigInt::from(0), &curve_order);
-        let mut v = BigInt::to_bytes(&n_reduced);
         const SECRET_KEY_SIZE: usize = 32;
-
-        if v.len() < SECRET_KEY_SIZE {
-            let mut template = vec![0; SECRET_KEY_SIZE - v.len()];
-            template.extend_from_slice(&v);
-            v = template;
-        }
+        let v = n_reduced.to_bytes_be_padded(SECRET_KEY_SIZE);
         let arr: GenericArray<u8, U32> = *GenericArray::from_slice(&v);
 
         Secp256r1Scalar {
@@ -228,6 +223,23 @@ impl PartialEq for Secp256r1Scalar {
     }
 }
 
+impl PartialEq for Secp256r1Point {
+    fn eq(&self, other: &Secp256r1Point) -> bool {
+        self.get_element() == other.get_element()
+    }
+}
+
+impl fmt::Debug for Secp256r1Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Point {{ purpose: {:?}, bytes: {:?} }}",
+            self.purpose,
+            self.bytes_compressed_to_big_int().to_hex()
+        )
+    }
+}
+
 impl Zeroize for Secp256r1Point {
     fn zeroize(&mut self) {
         unsafe { ptr::write_volatile(self, GE::generator()) };
@@ -240,20 +252,14 @@ impl ECPoint for Secp256r1Point {
     type SecretKey = SK;
     type PublicKey = PK;
     type Scalar = Secp256r1Scalar;
+    type Table = Secp256r1Table;
 
     fn base_point2() -> Secp256r1Point {
-        let mut v = vec![4_u8];
-        v.extend(BASE_POINT2_X.as_ref());
-        v.extend(BASE_POINT2_Y.as_ref());
-        Secp256r1Point::from_bytes(&v).unwrap()
+        *BASE_POINT2
     }
 
     fn generator() -> Secp256r1Point {
-        Secp256r1Point {
-            purpose: "base_fe",
-            ge: VerifyKey::from_encoded_point(&AffinePoint::generator().to_encoded_point(true))
-                .unwrap(),
-        }
+        *GENERATOR
     }
 
     fn get_element(&self) -> PK {
@@ -337,23 +343,9 @@ impl ECPoint for Secp256r1Point {
     }
 
     fn from_coor(x: &BigInt, y: &BigInt) -> Secp256r1Point {
-        let mut vec_x = BigInt::to_bytes(x);
-        let mut vec_y = BigInt::to_bytes(y);
         const COORDINATE_SIZE: usize = 32;
-        assert!(vec_x.len() <= COORDINATE_SIZE, "x coordinate is too big.");
-        assert!(vec_x.len() <= COORDINATE_SIZE, "y coordinate is too big.");
-        if vec_x.len() < COORDINATE_SIZE {
-            // pad
-            let mut x_buffer = vec![0; COORDINATE_SIZE - vec_x.len()];
-            x_buffer.extend_from_slice(&vec_x);
-            vec_x = x_buffer
-        }
-        if vec_y.len() < COORDINATE_SIZE {
-            // pad
-            let mut y_buffer = vec![0; COORDINATE_SIZE - vec_y.len()];
-            y_buffer.extend_from_slice(&vec_y);
-            vec_y = y_buffer
-        }
+        let vec_x = x.to_bytes_be_padded(COORDINATE_SIZE);
+        let vec_y = y.to_bytes_be_padded(COORDINATE_SIZE);
 
         let x_arr: GenericArray<u8, U32> = *GenericArray::from_slice(&vec_x);
         let y_arr: GenericArray<u8, U32> = *GenericArray::from_slice(&vec_y);
@@ -365,6 +357,68 @@ impl ECPoint for Secp256r1Point {
             .unwrap(),
         }
     }
+
+    fn hash_to_curve(message: &[u8]) -> Secp256r1Point {
+        try_and_increment(message, |digest| {
+            // compressed point with even y
+            let mut compressed = vec![2u8];
+            compressed.extend_from_slice(digest);
+            Secp256r1Point::from_bytes(&compressed).ok()
+        })
+    }
+
+    fn mul_generator(scalar: &Secp256r1Scalar) -> Secp256r1Point {
+        GENERATOR_TABLE.mul(scalar)
+    }
+
+    fn mul_base_point2(scalar: &Secp256r1Scalar) -> Secp256r1Point {
+        BASE_POINT2_TABLE.mul(scalar)
+    }
+}
+
+impl_ct_lookup_via_subtle!(ProjectivePoint);
+
+pub struct Secp256r1Table {
+    base: Secp256r1Point,
+    table: WindowTable<ProjectivePoint>,
+}
+
+impl BasePointTable<Secp256r1Point> for Secp256r1Table {
+    fn new(base: &Secp256r1Point) -> Secp256r1Table {
+        let point = ProjectivePoint::from(
+            AffinePoint::from_encoded_point(&EncodedPoint::from(&base.ge)).unwrap(),
+        );
+        let table = WindowTable::new(point, FE::q().bit_length(), |a, b| a + b, |a| -*a);
+        Secp256r1Table { base: *base, table }
+    }
+
+    fn base(&self) -> Secp256r1Point {
+        self.base
+    }
+
+    fn mul(&self, scalar: &Secp256r1Scalar) -> Secp256r1Point {
+        let point: ProjectivePoint = self.table.lookup(&scalar.to_big_int()).sum();
+        Secp256r1Point {
+            purpose: "mul_table",
+            ge: VerifyKey::from_encoded_point(&point.to_affine().to_encoded_point(true)).unwrap(),
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref GENERATOR: Secp256r1Point = Secp256r1Point {
+        purpose: "base_fe",
+        ge: VerifyKey::from_encoded_point(&AffinePoint::generator().to_encoded_point(true))
+            .unwrap(),
+    };
+    static ref BASE_POINT2: Secp256r1Point = {
+        let mut v = vec![4_u8];
+        v.extend(BASE_POINT2_X.as_ref());
+        v.extend(BASE_POINT2_Y.as_ref());
+        Secp256r1Point::from_bytes(&v).unwrap()
+    };
+    static ref GENERATOR_TABLE: Secp256r1Table = Secp256r1Table::new(&GENERATOR);
+    static ref BASE_POINT2_TABLE: Secp256r1Table = Secp256r1Table::new(&BASE_POINT2);
 }
 
 impl Secp256r1Point {
@@ -0,0 +1,640 @@
+// NIST P-384 elliptic curve utility functions.
+
+use super::fixed_base::{impl_ct_lookup_via_subtle, WindowTable};
+use super::traits::{try_and_increment, BasePointTable, ECPoint, ECScalar};
+use crate::arithmetic::traits::*;
+use crate::BigInt;
+use crate::ErrorKey;
+use p384::elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
+use p384::elliptic_curve::PrimeField;
+use p384::{AffinePoint, EncodedPoint, FieldBytes, ProjectivePoint, Scalar};
+use serde::de;
+use serde::de::Visitor;
+use serde::ser::{Serialize, Serializer};
+use serde::{Deserialize, Deserializer};
+use sha2::{Digest, Sha512};
+use std::ops::{Add, Mul, Sub};
+use std::sync::atomic;
+use std::{fmt, ptr};
+use zeroize::Zeroize;
+
+pub type SK = Scalar;
+pub type PK = AffinePoint;
+
+/// Size of scalars and of point coordinates in bytes
+const FIELD_SIZE: usize = 48;
+
+#[derive(Clone, Copy)]
+pub struct Secp384r1Scalar {
+    purpose: &'static str,
+    fe: SK,
+}
+
+#[derive(Clone, Copy)]
+pub struct Secp384r1Point {
+    purpose: &'static str,
+    ge: PK,
+}
+pub type GE = Secp384r1Point;
+pub type FE = Secp384r1Scalar;
+
+impl fmt::Debug for Secp384r1Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Scalar {{ purpose: {:?}, fe: {:?} }}",
+            self.purpose, self.fe
+        )
+    }
+}
+
+impl fmt::Debug for Secp384r1Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Point {{ purpose: {:?}, bytes: {:?} }}",
+            self.purpose,
+            self.bytes_compressed_to_big_int().to_hex()
+        )
+    }
+}
+
+impl Zeroize for Secp384r1Scalar {
+    fn zeroize(&mut self) {
+        unsafe { ptr::write_volatile(self, Secp384r1Scalar::zero()) };
+        atomic::fence(atomic::Ordering::SeqCst);
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+    }
+}
+
+impl ECScalar for Secp384r1Scalar {
+    type SecretKey = SK;
+
+    fn new_random() -> Secp384r1Scalar {
+        ECScalar::from(&BigInt::sample_below(&Secp384r1Scalar::q()))
+    }
+
+    fn zero() -> Secp384r1Scalar {
+        Secp384r1Scalar {
+            purpose: "zero",
+            fe: Scalar::ZERO,
+        }
+    }
+
+    fn get_element(&self) -> SK {
+        self.fe
+    }
+
+    fn set_element(&mut self, element: SK) {
+        self.fe = element
+    }
+
+    fn from(n: &BigInt) -> Secp384r1Scalar {
+        let curve_order = Secp384r1Scalar::q();
+        let n_reduced = BigInt::mod_add(n, &BigInt::from(0), &curve_order);
+        let v = n_reduced.to_bytes_be_padded(FIELD_SIZE);
+
+        Secp384r1Scalar {
+            purpose: "from_big_int",
+            fe: Scalar::from_repr(field_bytes(&v)).unwrap(),
+        }
+    }
+
+    fn to_big_int(&self) -> BigInt {
+        BigInt::from_bytes(&self.fe.to_repr()[..])
+    }
+
+    fn q() -> BigInt {
+        const CURVE_ORDER: [u8; FIELD_SIZE] = [
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xc7, 0x63, 0x4d, 0x81,
+            0xf4, 0x37, 0x2d, 0xdf, 0x58, 0x1a, 0x0d, 0xb2, 0x48, 0xb0, 0xa7, 0x7a, 0xec, 0xec,
+            0x19, 0x6a, 0xcc, 0xc5, 0x29, 0x73,
+        ];
+        BigInt::from_bytes(CURVE_ORDER.as_ref())
+    }
+
+    fn add(&self, other: &SK) -> Secp384r1Scalar {
+        Secp384r1Scalar {
+            purpose: "add",
+            fe: self.get_element() + other,
+        }
+    }
+
+    fn mul(&self, other: &SK) -> Secp384r1Scalar {
+        Secp384r1Scalar {
+            purpose: "mul",
+            fe: self.get_element() * other,
+        }
+    }
+
+    fn sub(&self, other: &SK) -> Secp384r1Scalar {
+        Secp384r1Scalar {
+            purpose: "sub",
+            fe: self.get_element() - other,
+        }
+    }
+
+    fn invert(&self) -> Secp384r1Scalar {
+        Secp384r1Scalar {
+            purpose: "invert",
+            fe: self.fe.invert().unwrap(),
+        }
+    }
+}
+
+impl Mul<Secp384r1Scalar> for Secp384r1Scalar {
+    type Output = Secp384r1Scalar;
+    fn mul(self, other: Secp384r1Scalar) -> Secp384r1Scalar {
+        ECScalar::mul(&self, &other.get_element())
+    }
+}
+
+impl<'o> Mul<&'o Secp384r1Scalar> for Secp384r1Scalar {
+    type Output = Secp384r1Scalar;
+    fn mul(self, other: &'o Secp384r1Scalar) -> Secp384r1Scalar {
+        ECScalar::mul(&self, &other.get_element())
+    }
+}
+
+impl Add<Secp384r1Scalar> for Secp384r1Scalar {
+    type Output = Secp384r1Scalar;
+    fn add(self, other: Secp384r1Scalar) -> Secp384r1Scalar {
+        ECScalar::add(&self, &other.get_element())
+    }
+}
+
+impl<'o> Add<&'o Secp384r1Scalar> for Secp384r1Scalar {
+    type Output = Secp384r1Scalar;
+    fn add(self, other: &'o Secp384r1Scalar) -> Secp384r1Scalar {
+        ECScalar::add(&self, &other.get_element())
+    }
+}
+
+impl Sub<Secp384r1Scalar> for Secp384r1Scalar {
+    type Output = Secp384r1Scalar;
+    fn sub(self, other: Secp384r1Scalar) -> Secp384r1Scalar {
+        ECScalar::sub(&self, &other.get_element())
+    }
+}
+
+impl<'o> Sub<&'o Secp384r1Scalar> for Secp384r1Scalar {
+    type Output = Secp384r1Scalar;
+    fn sub(self, other: &'o Secp384r1Scalar) -> Secp384r1Scalar {
+        ECScalar::sub(&self, &other.get_element())
+    }
+}
+
+impl Serialize for Secp384r1Scalar {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!(
+            "{:0>width$}",
+            self.to_big_int().to_hex(),
+            width = 2 * FIELD_SIZE
+        ))
+    }
+}
+
+impl<'de> Deserialize<'de> for Secp384r1Scalar {
+    fn deserialize<D>(deserializer: D) -> Result<Secp384r1Scalar, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(Secp384r1ScalarVisitor)
+    }
+}
+
+struct Secp384r1ScalarVisitor;
+
+impl<'de> Visitor<'de> for Secp384r1ScalarVisitor {
+    type Value = Secp384r1Scalar;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Secp384r1Scalar")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Secp384r1Scalar, E> {
+        let v = BigInt::from_hex(s).map_err(E::custom)?;
+        Ok(ECScalar::from(&v))
+    }
+}
+
+impl PartialEq for Secp384r1Scalar {
+    fn eq(&self, other: &Secp384r1Scalar) -> bool {
+        self.get_element() == other.get_element()
+    }
+}
+
+impl PartialEq for Secp384r1Point {
+    fn eq(&self, other: &Secp384r1Point) -> bool {
+        self.get_element() == other.get_element()
+    }
+}
+
+impl Zeroize for Secp384r1Point {
+    fn zeroize(&mut self) {
+        unsafe { ptr::write_volatile(self, GE::generator()) };
+        atomic::fence(atomic::Ordering::SeqCst);
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+    }
+}
+
+impl ECPoint for Secp384r1Point {
+    type SecretKey = SK;
+    type PublicKey = PK;
+    type Scalar = Secp384r1Scalar;
+    type Table = Secp384r1Table;
+
+    fn base_point2() -> Secp384r1Point {
+        *BASE_POINT2
+    }
+
+    fn generator() -> Secp384r1Point {
+        Secp384r1Point {
+            purpose: "base_fe",
+            ge: AffinePoint::GENERATOR,
+        }
+    }
+
+    fn get_element(&self) -> PK {
+        self.ge
+    }
+
+    fn x_coor(&self) -> Option<BigInt> {
+        let encoded = self.ge.to_encoded_point(false);
+        encoded.x().map(|x| BigInt::from_bytes(&x[..]))
+    }
+
+    fn y_coor(&self) -> Option<BigInt> {
+        let encoded = self.ge.to_encoded_point(false);
+        encoded.y().map(|y| BigInt::from_bytes(&y[..]))
+    }
+
+    fn bytes_compressed_to_big_int(&self) -> BigInt {
+        BigInt::from_bytes(self.ge.to_encoded_point(true).as_bytes())
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Secp384r1Point, ErrorKey> {
+        let encoded = EncodedPoint::from_bytes(bytes).map_err(|_| ErrorKey::InvalidPublicKey)?;
+        let point: Option<AffinePoint> = AffinePoint::from_encoded_point(&encoded).into();
+        match point {
+            Some(ge) if !bool::from(ge.is_identity()) => Ok(Secp384r1Point {
+                purpose: "random",
+                ge,
+            }),
+            _ => Err(ErrorKey::InvalidPublicKey),
+        }
+    }
+
+    fn pk_to_key_slice(&self) -> Vec<u8> {
+        self.ge.to_encoded_point(false).as_bytes().to_vec()
+    }
+
+    fn scalar_mul(&self, fe: &SK) -> Secp384r1Point {
+        Secp384r1Point {
+            purpose: "mul",
+            ge: (ProjectivePoint::from(self.ge) * fe).to_affine(),
+        }
+    }
+
+    fn add_point(&self, other: &PK) -> Secp384r1Point {
+        Secp384r1Point {
+            purpose: "combine",
+            ge: (ProjectivePoint::from(self.ge) + other).to_affine(),
+        }
+    }
+
+    fn sub_point(&self, other: &PK) -> Secp384r1Point {
+        Secp384r1Point {
+            purpose: "sub",
+            ge: (ProjectivePoint::from(self.ge) - other).to_affine(),
+        }
+    }
+
+    fn from_coor(x: &BigInt, y: &BigInt) -> Secp384r1Point {
+        let vec_x = x.to_bytes_be_padded(FIELD_SIZE);
+        let vec_y = y.to_bytes_be_padded(FIELD_SIZE);
+        let encoded = EncodedPoint::from_affine_coordinates(
+            &field_bytes(&vec_x),
+            &field_bytes(&vec_y),
+            false,
+        );
+        Secp384r1Point {
+            purpose: "base_fe",
+            ge: AffinePoint::from_encoded_point(&encoded).unwrap(),
+        }
+    }
+
+    fn hash_to_curve(message: &[u8]) -> Secp384r1Point {
+        try_and_increment(message, |digest| {
+            // compressed point with even y, x is derived from the digest
+            let mut compressed = vec![2u8];
+            compressed.extend_from_slice(&Sha512::digest(digest)[..FIELD_SIZE]);
+            Secp384r1Point::from_bytes(&compressed).ok()
+        })
+    }
+
+    fn mul_generator(scalar: &Secp384r1Scalar) -> Secp384r1Point {
+        GENERATOR_TABLE.mul(scalar)
+    }
+
+    fn mul_base_point2(scalar: &Secp384r1Scalar) -> Secp384r1Point {
+        BASE_POINT2_TABLE.mul(scalar)
+    }
+}
+
+impl_ct_lookup_via_subtle!(ProjectivePoint);
+
+pub struct Secp384r1Table {
+    base: Secp384r1Point,
+    table: WindowTable<ProjectivePoint>,
+}
+
+impl BasePointTable<Secp384r1Point> for Secp384r1Table {
+    fn new(base: &Secp384r1Point) -> Secp384r1Table {
+        let table = WindowTable::new(
+            ProjectivePoint::from(base.ge),
+            FE::q().bit_length(),
+            |a, b| a + b,
+            |a| -*a,
+        );
+        Secp384r1Table { base: *base, table }
+    }
+
+    fn base(&self) -> Secp384r1Point {
+        self.base
+    }
+
+    fn mul(&self, scalar: &Secp384r1Scalar) -> Secp384r1Point {
+        let point: ProjectivePoint = self.table.lookup(&scalar.to_big_int()).sum();
+        Secp384r1Point {
+            purpose: "mul_table",
+            ge: point.to_affine(),
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Hash of the compressed generator, so nobody knows its discrete logarithm
+    static ref BASE_POINT2: Secp384r1Point = {
+        let g = Secp384r1Point::generator();
+        let h = Secp384r1Point::hash_to_curve(g.ge.to_encoded_point(true).as_bytes());
+        Secp384r1Point {
+            purpose: "random",
+            ge: h.ge,
+        }
+    };
+    static ref GENERATOR_TABLE: Secp384r1Table =
+        Secp384r1Table::new(&Secp384r1Point::generator());
+    static ref BASE_POINT2_TABLE: Secp384r1Table = Secp384r1Table::new(&BASE_POINT2);
+}
+
+/// Copies big-endian encoding of `FIELD_SIZE` bytes into `FieldBytes`
+fn field_bytes(bytes: &[u8]) -> FieldBytes {
+    let mut field_bytes = FieldBytes::default();
+    field_bytes.copy_from_slice(bytes);
+    field_bytes
+}
+
+impl Secp384r1Point {
+    // derive point from BigInt
+    fn from_bigint(i: &BigInt) -> Result<Secp384r1Point, ()> {
+        let vec = BigInt::to_bytes(i);
+        Secp384r1Point::from_bytes(&vec).map_err(|_| ())
+    }
+}
+
+impl Mul<Secp384r1Scalar> for Secp384r1Point {
+    type Output = Secp384r1Point;
+    fn mul(self, other: Secp384r1Scalar) -> Self::Output {
+        self.scalar_mul(&other.get_element())
+    }
+}
+
+impl<'o> Mul<&'o Secp384r1Scalar> for Secp384r1Point {
+    type Output = Secp384r1Point;
+    fn mul(self, other: &'o Secp384r1Scalar) -> Self::Output {
+        self.scalar_mul(&other.get_element())
+    }
+}
+
+impl<'o> Mul<&'o Secp384r1Scalar> for &'o Secp384r1Point {
+    type Output = Secp384r1Point;
+    fn mul(self, other: &'o Secp384r1Scalar) -> Self::Output {
+        self.scalar_mul(&other.get_element())
+    }
+}
+
+impl Add<Secp384r1Point> for Secp384r1Point {
+    type Output = Secp384r1Point;
+    fn add(self, other: Secp384r1Point) -> Self::Output {
+        self.add_point(&other.get_element())
+    }
+}
+
+impl<'o> Add<&'o Secp384r1Point> for Secp384r1Point {
+    type Output = Secp384r1Point;
+    fn add(self, other: &'o Secp384r1Point) -> Self::Output {
+        self.add_point(&other.get_element())
+    }
+}
+
+impl<'o> Add<&'o Secp384r1Point> for &'o Secp384r1Point {
+    type Output = Secp384r1Point;
+    fn add(self, other: &'o Secp384r1Point) -> Self::Output {
+        self.add_point(&other.get_element())
+    }
+}
+
+impl Sub<Secp384r1Point> for Secp384r1Point {
+    type Output = Secp384r1Point;
+    fn sub(self, other: Secp384r1Point) -> Self::Output {
+        self.sub_point(&other.get_element())
+    }
+}
+
+impl<'o> Sub<&'o Secp384r1Point> for Secp384r1Point {
+    type Output = Secp384r1Point;
+    fn sub(self, other: &'o Secp384r1Point) -> Self::Output {
+        self.sub_point(&other.get_element())
+    }
+}
+
+impl<'o> Sub<&'o Secp384r1Point> for &'o Secp384r1Point {
+    type Output = Secp384r1Point;
+    fn sub(self, other: &'o Secp384r1Point) -> Self::Output {
+        self.sub_point(&other.get_element())
+    }
+}
+
+impl Serialize for Secp384r1Point {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!(
+            "{:0>width$}",
+            self.bytes_compressed_to_big_int().to_hex(),
+            width = 2 * (FIELD_SIZE + 1)
+        ))
+    }
+}
+
+impl<'de> Deserialize<'de> for Secp384r1Point {
+    fn deserialize<D>(deserializer: D) -> Result<Secp384r1Point, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(Secp384r1PointVisitor)
+    }
+}
+
+struct Secp384r1PointVisitor;
+
+impl<'de> Visitor<'de> for Secp384r1PointVisitor {
+    type Value = Secp384r1Point;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Secp384r1Point")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Secp384r1Point, E>
+    where
+        E: de::Error,
+    {
+        let bn = BigInt::from_hex(s).map_err(E::custom)?;
+        match Secp384r1Point::from_bigint(&bn) {
+            Ok(v) => Ok(v),
+            Err(_) => Err(E::custom(format!(
+                "Error deriving Secp384r1Point from string: {}",
+                s
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BigInt, ErrorKey};
+    use super::{Secp384r1Point, Secp384r1Scalar};
+    use crate::arithmetic::traits::*;
+    use crate::elliptic::curves::traits::{ECPoint, ECScalar};
+
+    #[test]
+    fn serialize_sk() {
+        let scalar: Secp384r1Scalar = ECScalar::from(&BigInt::from(123456));
+        let s = serde_json::to_string(&scalar).expect("Failed in serialization");
+        assert_eq!(s, format!("\"{:0>96}\"", "1e240"));
+        let des: Secp384r1Scalar = serde_json::from_str(&s).expect("Failed in deserialization");
+        assert_eq!(des, scalar);
+    }
+
+    #[test]
+    fn test_serdes_pk() {
+        for pk in &[Secp384r1Point::generator(), Secp384r1Point::base_point2()] {
+            let s = serde_json::to_string(pk).expect("Failed in serialization");
+            let des_pk: Secp384r1Point =
+                serde_json::from_str(&s).expect("Failed in deserialization");
+            assert_eq!(des_pk, *pk);
+
+            let bin = bincode::serialize(pk).unwrap();
+            let decoded: Secp384r1Point = bincode::deserialize(bin.as_slice()).unwrap();
+            assert_eq!(decoded, *pk);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_serdes_bad_pk() {
+        let pk = Secp384r1Point::generator();
+        let mut s = serde_json::to_string(&pk).expect("Failed in serialization");
+        // we make sure that the string encodes invalid point:
+        s = s.replace("aa87", "aa88");
+        let des_pk: Secp384r1Point = serde_json::from_str(&s).expect("Failed in deserialization");
+        assert_eq!(des_pk, pk);
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let vec = BigInt::to_bytes(&BigInt::from(1337));
+        let result = Secp384r1Point::from_bytes(&vec);
+        assert_eq!(result.unwrap_err(), ErrorKey::InvalidPublicKey)
+    }
+
+    #[test]
+    fn test_scalar_mul_generator() {
+        // test vectors from http://point-at-infinity.org/ecc/nisttv
+        let base_point = Secp384r1Point::generator();
+        let int: Secp384r1Scalar = ECScalar::from(&BigInt::from(2));
+        let test = base_point * int;
+        let x = BigInt::from_hex(
+            "08D999057BA3D2D969260045C55B97F089025959A6F434D651D207D19FB96E9E\
+             4FE0E86EBE0E64F85B96A9C75295DF61",
+        )
+        .unwrap();
+        let y = BigInt::from_hex(
+            "8E80F1FA5B1B3CEDB7BFE8DFFD6DBA74B275D875BC6CC43E904E505F256AB425\
+             5FFD43E94D39E22D61501E700A940E80",
+        )
+        .unwrap();
+        assert_eq!(test.x_coor().unwrap(), x);
+        assert_eq!(test.y_coor().unwrap(), y);
+    }
+
+    #[test]
+    fn test_pk_to_key_slice() {
+        for _ in 1..20 {
+            let r = Secp384r1Scalar::new_random();
+            let rg = Secp384r1Point::generator() * r;
+            let key_slice = rg.pk_to_key_slice();
+            assert!(key_slice.len() == 97);
+            assert!(key_slice[0] == 4);
+            let rg_prime: Secp384r1Point = ECPoint::from_bytes(&key_slice).unwrap();
+            assert_eq!(rg_prime, rg);
+            let rg_coor = Secp384r1Point::from_coor(&rg.x_coor().unwrap(), &rg.y_coor().unwrap());
+            assert_eq!(rg_coor, rg);
+        }
+    }
+
+    #[test]
+    fn test_base_point2() {
+        // base_point2 is derived by hashing the generator to curve
+        let g = Secp384r1Point::generator();
+        let g_bytes = BigInt::to_bytes(&g.bytes_compressed_to_big_int());
+        assert_eq!(
+            Secp384r1Point::base_point2(),
+            Secp384r1Point::hash_to_curve(&g_bytes)
+        );
+        assert!(Secp384r1Point::base_point2() != g);
+    }
+
+    #[test]
+    fn test_invert() {
+        let a_bn = BigInt::sample(384);
+        let a: Secp384r1Scalar = ECScalar::from(&a_bn);
+        let a_inv = a.invert();
+        let a_inv_bn_1 = BigInt::mod_inv(&a_bn, &Secp384r1Scalar::q()).unwrap();
+        let a_inv_bn_2 = a_inv.to_big_int();
+        assert_eq!(a_inv_bn_1, a_inv_bn_2);
+    }
+
+    #[test]
+    fn scalar_bigint_conversion() {
+        let int = BigInt::sample_below(&Secp384r1Scalar::q());
+        let scalar: Secp384r1Scalar = ECScalar::from(&int);
+        assert_eq!(scalar.to_big_int(), int);
+        let q: Secp384r1Scalar = ECScalar::from(&Secp384r1Scalar::q());
+        assert_eq!(q, Secp384r1Scalar::zero());
+    }
+
+    #[test]
+    fn add_sub_point() {
+        let g = Secp384r1Point::generator();
+        let i: Secp384r1Scalar = ECScalar::from(&BigInt::from(3));
+        assert_eq!(g + g + g, g * i);
+        assert_eq!(g + g, g + g - g + g);
+    }
+}
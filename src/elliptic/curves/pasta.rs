@@ -0,0 +1,208 @@
+//! Pallas and Vesta: the Pasta cycle of curves
+//!
+//! Base field of each curve is the scalar field of the other. Points are encoded in 32 bytes
+//! (little-endian `x` coordinate with the sign of `y` in the most significant bit), identity
+//! is encoded as all zeros. Hashing to the curves is the one used by Halo 2 (simplified SWU).
+
+/// Domain separation tag used when hashing to Pallas and Vesta
+const HASH_TO_CURVE_DOMAIN: &str = "curv";
+
+macro_rules! pasta_curve {
+    ($curve:ident, $backend:ident, $scalar:ident, $point:ident, $table:ident) => {
+        use super::super::fixed_base::{impl_ct_lookup_via_subtle, WindowTable};
+        use super::super::prime_group::{
+            ff_modulus, ff_scalar_from_bytes_be, ff_scalar_to_bytes_be, impl_prime_group_curve,
+            to_bytes_le_32, PrimeGroupBackend,
+        };
+        use super::super::traits::{BasePointTable, ECPoint, ECScalar};
+        use super::HASH_TO_CURVE_DOMAIN;
+        use crate::arithmetic::traits::*;
+        use crate::BigInt;
+        use crate::ErrorKey;
+        use ff::{Field, PrimeField};
+        use group::{Curve, Group, GroupEncoding};
+        use pasta_curves::arithmetic::{CurveAffine, CurveExt};
+        use pasta_curves::$curve::{Affine, Base, Point, Scalar};
+
+        pub type SK = Scalar;
+        pub type PK = Point;
+
+        struct $backend;
+
+        impl PrimeGroupBackend for $backend {
+            type Scalar = SK;
+            type Point = PK;
+
+            fn curve_order() -> BigInt {
+                ff_modulus::<Scalar>()
+            }
+
+            fn scalar_from_bytes_be(bytes: &[u8; 32]) -> SK {
+                ff_scalar_from_bytes_be(bytes).expect("scalar is reduced")
+            }
+
+            fn scalar_to_bytes_be(scalar: &SK) -> [u8; 32] {
+                ff_scalar_to_bytes_be(scalar)
+            }
+
+            fn scalar_invert(scalar: &SK) -> Option<SK> {
+                scalar.invert().into()
+            }
+
+            fn scalar_zero() -> SK {
+                Scalar::ZERO
+            }
+
+            fn identity() -> PK {
+                Point::identity()
+            }
+
+            fn generator() -> PK {
+                Point::generator()
+            }
+
+            fn point_to_bytes(point: &PK) -> [u8; 32] {
+                point.to_bytes()
+            }
+
+            fn point_from_bytes(bytes: &[u8; 32]) -> Option<PK> {
+                Point::from_bytes(bytes).into()
+            }
+
+            fn coordinates(point: &PK) -> Option<(BigInt, BigInt)> {
+                let coordinates: Option<_> = point.to_affine().coordinates().into();
+                coordinates.map(|c: pasta_curves::arithmetic::Coordinates<Affine>| {
+                    (
+                        BigInt::from_bytes_le(&c.x().to_repr()),
+                        BigInt::from_bytes_le(&c.y().to_repr()),
+                    )
+                })
+            }
+
+            fn point_from_coordinates(x: &BigInt, y: &BigInt) -> Option<PK> {
+                let x: Option<Base> = Base::from_repr(to_bytes_le_32(x)?).into();
+                let y: Option<Base> = Base::from_repr(to_bytes_le_32(y)?).into();
+                let point: Option<Affine> = Affine::from_xy(x?, y?).into();
+                point.map(Point::from)
+            }
+
+            fn hash_to_point(message: &[u8]) -> PK {
+                Point::hash_to_curve(HASH_TO_CURVE_DOMAIN)(message)
+            }
+        }
+
+        impl_ct_lookup_via_subtle!(PK);
+
+        impl_prime_group_curve! {
+            backend: $backend,
+            scalar: $scalar,
+            point: $point,
+            table: $table,
+        }
+    };
+}
+
+pub mod pallas {
+    pasta_curve!(pallas, Pallas, PallasScalar, PallasPoint, PallasTable);
+}
+
+pub mod vesta {
+    pasta_curve!(vesta, Vesta, VestaScalar, VestaPoint, VestaTable);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pallas, vesta};
+    use crate::arithmetic::traits::*;
+    use crate::elliptic::curves::traits::{ECPoint, ECScalar};
+    use crate::BigInt;
+
+    #[test]
+    fn serialize_deserialize_point() {
+        let point = pallas::GE::generator() * pallas::FE::new_random();
+        let s = serde_json::to_string(&point).expect("Failed in serialization");
+        let des_point: pallas::GE = serde_json::from_str(&s).expect("Failed in deserialization");
+        assert_eq!(des_point, point);
+        let encoded = bincode::serialize(&point).unwrap();
+        let decoded: pallas::GE = bincode::deserialize(encoded.as_slice()).unwrap();
+        assert_eq!(point, decoded);
+    }
+
+    #[test]
+    fn serialize_deserialize_scalar() {
+        let scalar: vesta::FE = ECScalar::new_random();
+        let s = serde_json::to_string(&scalar).expect("Failed in serialization");
+        let des_scalar: vesta::FE = serde_json::from_str(&s).expect("Failed in deserialization");
+        assert_eq!(des_scalar, scalar);
+    }
+
+    #[test]
+    fn curves_form_a_cycle() {
+        let p =
+            BigInt::from_hex("40000000000000000000000000000000224698fc094cf91b992d30ed00000001")
+                .unwrap();
+        let q =
+            BigInt::from_hex("40000000000000000000000000000000224698fc0994a8dd8c46eb2100000001")
+                .unwrap();
+        // order of Pallas is the modulus of Vesta base field and vice versa
+        assert_eq!(pallas::FE::q(), q);
+        assert_eq!(vesta::FE::q(), p);
+    }
+
+    #[test]
+    fn generators() {
+        // (-1, 2) is the generator of both curves
+        let g = pallas::GE::generator();
+        assert_eq!(g.x_coor().unwrap(), vesta::FE::q() - BigInt::one());
+        assert_eq!(g.y_coor().unwrap(), BigInt::from(2));
+        assert_eq!(
+            pallas::GE::from_coor(&g.x_coor().unwrap(), &BigInt::from(2)),
+            g
+        );
+
+        let g = vesta::GE::generator();
+        assert_eq!(g.x_coor().unwrap(), pallas::FE::q() - BigInt::one());
+        assert_eq!(g.y_coor().unwrap(), BigInt::from(2));
+        assert_eq!(
+            vesta::GE::from_coor(&g.x_coor().unwrap(), &BigInt::from(2)),
+            g
+        );
+    }
+
+    #[test]
+    fn identity_encoding() {
+        let zero = pallas::GE::generator() * pallas::FE::zero();
+        assert_eq!(zero.pk_to_key_slice(), vec![0u8; 32]);
+        assert_eq!(pallas::GE::from_bytes(&[0u8; 32]).unwrap(), zero);
+        assert_eq!(zero.x_coor(), None);
+    }
+
+    #[test]
+    fn rejects_invalid_points() {
+        // x = 0 gives y^2 = 5 which isn't a square in either field
+        assert!(pallas::GE::from_bytes(&{
+            let mut bytes = [0u8; 32];
+            bytes[31] = 0x80;
+            bytes
+        })
+        .is_err());
+        // x >= p
+        assert!(vesta::GE::from_bytes(&[0xff; 32]).is_err());
+        assert!(vesta::GE::from_bytes(&[1; 33]).is_err());
+    }
+
+    #[test]
+    fn base_point2_nothing_up_my_sleeve() {
+        let g = vesta::GE::generator();
+        let h = vesta::GE::hash_to_curve(&g.pk_to_key_slice());
+        assert_eq!(vesta::GE::base_point2(), h);
+        assert_ne!(h, g);
+    }
+
+    #[test]
+    fn test_invert() {
+        let a: pallas::FE = ECScalar::new_random();
+        let one: pallas::FE = ECScalar::from(&BigInt::one());
+        assert_eq!(a * a.invert(), one);
+    }
+}
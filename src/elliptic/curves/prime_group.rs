@@ -0,0 +1,535 @@
+// Shared ECScalar/ECPoint implementation for prime order groups with 32 bytes encodings.
+//
+// Jubjub, Baby Jubjub, Pallas and Vesta backends only differ in the underlying arithmetic
+// library, so each of them implements `PrimeGroupBackend` on top of its library and invokes
+// `impl_prime_group_curve!` which defines the scalar, point and table types.
+
+use crate::arithmetic::traits::*;
+use crate::BigInt;
+
+/// Curve specific operations the types generated by `impl_prime_group_curve!` are built from
+pub(super) trait PrimeGroupBackend {
+    type Scalar;
+    type Point;
+
+    /// Order of the group
+    fn curve_order() -> BigInt;
+    /// Parses big-endian scalar, input is guaranteed to be less than the curve order
+    fn scalar_from_bytes_be(bytes: &[u8; 32]) -> Self::Scalar;
+    fn scalar_to_bytes_be(scalar: &Self::Scalar) -> [u8; 32];
+    fn scalar_invert(scalar: &Self::Scalar) -> Option<Self::Scalar>;
+    fn scalar_zero() -> Self::Scalar;
+
+    fn identity() -> Self::Point;
+    fn generator() -> Self::Point;
+    /// Canonical compressed encoding of the point
+    fn point_to_bytes(point: &Self::Point) -> [u8; 32];
+    /// Parses compressed encoding, returns `None` if it's not canonical or point is not in the
+    /// prime order group
+    fn point_from_bytes(bytes: &[u8; 32]) -> Option<Self::Point>;
+    /// Affine coordinates, `None` if point has no affine representation (ie. it's identity
+    /// of a short Weierstrass curve)
+    fn coordinates(point: &Self::Point) -> Option<(BigInt, BigInt)>;
+    fn point_from_coordinates(x: &BigInt, y: &BigInt) -> Option<Self::Point>;
+    fn hash_to_point(message: &[u8]) -> Self::Point;
+}
+
+/// Big-endian bytes of a scalar of `ff` prime field (which uses little-endian representation)
+pub(super) fn ff_scalar_to_bytes_be<F: ff::PrimeField<Repr = [u8; 32]>>(scalar: &F) -> [u8; 32] {
+    let mut bytes = scalar.to_repr();
+    bytes.reverse();
+    bytes
+}
+
+pub(super) fn ff_scalar_from_bytes_be<F: ff::PrimeField<Repr = [u8; 32]>>(
+    bytes: &[u8; 32],
+) -> Option<F> {
+    let mut repr = *bytes;
+    repr.reverse();
+    F::from_repr(repr).into()
+}
+
+pub(super) fn ff_modulus<F: ff::PrimeField>() -> BigInt {
+    BigInt::from_hex(F::MODULUS.trim_start_matches("0x")).expect("modulus is a valid hex")
+}
+
+/// Little-endian 32 bytes representation of a field element, `None` if it doesn't fit
+pub(super) fn to_bytes_le_32(n: &BigInt) -> Option<[u8; 32]> {
+    if *n < BigInt::from(0) || n.bit_length() > 256 {
+        return None;
+    }
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&n.to_bytes_be_padded(32));
+    bytes.reverse();
+    Some(bytes)
+}
+
+/// Defines scalar, point and table types of the curve implemented by `$backend`
+///
+/// Type aliases `SK` and `PK` must be defined in the invoking module and be the same as
+/// `$backend`'s `Scalar` and `Point`, `PK` must implement [CtLookup]. Defines `FE`, `GE`
+/// aliases as well.
+///
+/// [CtLookup]: super::fixed_base::CtLookup
+macro_rules! impl_prime_group_curve {
+    (
+        backend: $backend:ty,
+        scalar: $scalar:ident,
+        point: $point:ident,
+        table: $table:ident,
+    ) => {
+        #[derive(Clone, Copy)]
+        pub struct $scalar {
+            purpose: &'static str,
+            fe: SK,
+        }
+
+        #[derive(Clone, Copy)]
+        pub struct $point {
+            purpose: &'static str,
+            ge: PK,
+        }
+        pub type GE = $point;
+        pub type FE = $scalar;
+
+        impl ::std::fmt::Debug for $scalar {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                write!(
+                    f,
+                    "Scalar {{ purpose: {:?}, fe: {:?} }}",
+                    self.purpose, self.fe
+                )
+            }
+        }
+
+        impl ::std::fmt::Debug for $point {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                write!(
+                    f,
+                    "Point {{ purpose: {:?}, bytes: {:?} }}",
+                    self.purpose,
+                    self.bytes_compressed_to_big_int().to_hex()
+                )
+            }
+        }
+
+        impl ::zeroize::Zeroize for $scalar {
+            fn zeroize(&mut self) {
+                use ::std::sync::atomic;
+                unsafe { ::std::ptr::write_volatile(self, <$scalar as ECScalar>::zero()) };
+                atomic::fence(atomic::Ordering::SeqCst);
+                atomic::compiler_fence(atomic::Ordering::SeqCst);
+            }
+        }
+
+        impl ECScalar for $scalar {
+            type SecretKey = SK;
+
+            fn new_random() -> $scalar {
+                ECScalar::from(&BigInt::sample_below(&$scalar::q()))
+            }
+
+            fn zero() -> $scalar {
+                $scalar {
+                    purpose: "zero",
+                    fe: <$backend as PrimeGroupBackend>::scalar_zero(),
+                }
+            }
+
+            fn get_element(&self) -> SK {
+                self.fe
+            }
+
+            fn set_element(&mut self, element: SK) {
+                self.fe = element
+            }
+
+            fn from(n: &BigInt) -> $scalar {
+                let n_reduced = BigInt::modulus(n, &$scalar::q());
+                let mut bytes = [0u8; 32];
+                bytes.copy_from_slice(&n_reduced.to_bytes_be_padded(32));
+                $scalar {
+                    purpose: "from_big_int",
+                    fe: <$backend as PrimeGroupBackend>::scalar_from_bytes_be(&bytes),
+                }
+            }
+
+            fn to_big_int(&self) -> BigInt {
+                BigInt::from_bytes(&<$backend as PrimeGroupBackend>::scalar_to_bytes_be(
+                    &self.fe,
+                ))
+            }
+
+            fn q() -> BigInt {
+                CURVE_ORDER.clone()
+            }
+
+            fn add(&self, other: &SK) -> $scalar {
+                $scalar {
+                    purpose: "add",
+                    fe: self.fe + *other,
+                }
+            }
+
+            fn mul(&self, other: &SK) -> $scalar {
+                $scalar {
+                    purpose: "mul",
+                    fe: self.fe * *other,
+                }
+            }
+
+            fn sub(&self, other: &SK) -> $scalar {
+                $scalar {
+                    purpose: "sub",
+                    fe: self.fe - *other,
+                }
+            }
+
+            fn invert(&self) -> $scalar {
+                $scalar {
+                    purpose: "invert",
+                    fe: <$backend as PrimeGroupBackend>::scalar_invert(&self.fe)
+                        .expect("zero is not invertible"),
+                }
+            }
+        }
+
+        impl ::std::ops::Mul<$scalar> for $scalar {
+            type Output = $scalar;
+            fn mul(self, other: $scalar) -> $scalar {
+                ECScalar::mul(&self, &other.get_element())
+            }
+        }
+
+        impl<'o> ::std::ops::Mul<&'o $scalar> for $scalar {
+            type Output = $scalar;
+            fn mul(self, other: &'o $scalar) -> $scalar {
+                ECScalar::mul(&self, &other.get_element())
+            }
+        }
+
+        impl ::std::ops::Add<$scalar> for $scalar {
+            type Output = $scalar;
+            fn add(self, other: $scalar) -> $scalar {
+                ECScalar::add(&self, &other.get_element())
+            }
+        }
+
+        impl<'o> ::std::ops::Add<&'o $scalar> for $scalar {
+            type Output = $scalar;
+            fn add(self, other: &'o $scalar) -> $scalar {
+                ECScalar::add(&self, &other.get_element())
+            }
+        }
+
+        impl ::std::ops::Sub<$scalar> for $scalar {
+            type Output = $scalar;
+            fn sub(self, other: $scalar) -> $scalar {
+                ECScalar::sub(&self, &other.get_element())
+            }
+        }
+
+        impl<'o> ::std::ops::Sub<&'o $scalar> for $scalar {
+            type Output = $scalar;
+            fn sub(self, other: &'o $scalar) -> $scalar {
+                ECScalar::sub(&self, &other.get_element())
+            }
+        }
+
+        impl ::serde::Serialize for $scalar {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&format!("{:0>64}", self.to_big_int().to_hex()))
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for $scalar {
+            fn deserialize<D: ::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<$scalar, D::Error> {
+                struct ScalarVisitor;
+
+                impl<'de> ::serde::de::Visitor<'de> for ScalarVisitor {
+                    type Value = $scalar;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut ::std::fmt::Formatter,
+                    ) -> ::std::fmt::Result {
+                        formatter.write_str(stringify!($scalar))
+                    }
+
+                    fn visit_str<E: ::serde::de::Error>(self, s: &str) -> Result<$scalar, E> {
+                        let v = BigInt::from_hex(s).map_err(E::custom)?;
+                        Ok(ECScalar::from(&v))
+                    }
+                }
+
+                deserializer.deserialize_str(ScalarVisitor)
+            }
+        }
+
+        impl PartialEq for $scalar {
+            fn eq(&self, other: &$scalar) -> bool {
+                self.fe == other.fe
+            }
+        }
+
+        impl PartialEq for $point {
+            fn eq(&self, other: &$point) -> bool {
+                self.ge == other.ge
+            }
+        }
+
+        impl ::zeroize::Zeroize for $point {
+            fn zeroize(&mut self) {
+                use ::std::sync::atomic;
+                unsafe { ::std::ptr::write_volatile(self, <$point as ECPoint>::generator()) };
+                atomic::fence(atomic::Ordering::SeqCst);
+                atomic::compiler_fence(atomic::Ordering::SeqCst);
+            }
+        }
+
+        impl ECPoint for $point {
+            type SecretKey = SK;
+            type PublicKey = PK;
+            type Scalar = $scalar;
+            type Table = $table;
+
+            fn base_point2() -> $point {
+                *BASE_POINT2
+            }
+
+            fn generator() -> $point {
+                $point {
+                    purpose: "base_fe",
+                    ge: <$backend as PrimeGroupBackend>::generator(),
+                }
+            }
+
+            fn get_element(&self) -> PK {
+                self.ge
+            }
+
+            fn x_coor(&self) -> Option<BigInt> {
+                <$backend as PrimeGroupBackend>::coordinates(&self.ge).map(|(x, _)| x)
+            }
+
+            fn y_coor(&self) -> Option<BigInt> {
+                <$backend as PrimeGroupBackend>::coordinates(&self.ge).map(|(_, y)| y)
+            }
+
+            fn bytes_compressed_to_big_int(&self) -> BigInt {
+                BigInt::from_bytes(&<$backend as PrimeGroupBackend>::point_to_bytes(&self.ge))
+            }
+
+            fn from_bytes(bytes: &[u8]) -> Result<$point, ErrorKey> {
+                let mut encoding = [0u8; 32];
+                if bytes.len() != encoding.len() {
+                    return Err(ErrorKey::InvalidPublicKey);
+                }
+                encoding.copy_from_slice(bytes);
+                let ge = <$backend as PrimeGroupBackend>::point_from_bytes(&encoding)
+                    .ok_or(ErrorKey::InvalidPublicKey)?;
+                Ok($point {
+                    purpose: "from_bytes",
+                    ge,
+                })
+            }
+
+            fn pk_to_key_slice(&self) -> Vec<u8> {
+                <$backend as PrimeGroupBackend>::point_to_bytes(&self.ge).to_vec()
+            }
+
+            fn scalar_mul(&self, fe: &SK) -> $point {
+                $point {
+                    purpose: "mul",
+                    ge: self.ge * *fe,
+                }
+            }
+
+            fn add_point(&self, other: &PK) -> $point {
+                $point {
+                    purpose: "combine",
+                    ge: self.ge + *other,
+                }
+            }
+
+            fn sub_point(&self, other: &PK) -> $point {
+                $point {
+                    purpose: "sub",
+                    ge: self.ge - *other,
+                }
+            }
+
+            fn from_coor(x: &BigInt, y: &BigInt) -> $point {
+                $point {
+                    purpose: "base_fe",
+                    ge: <$backend as PrimeGroupBackend>::point_from_coordinates(x, y)
+                        .expect("point is not in the group"),
+                }
+            }
+
+            fn hash_to_curve(message: &[u8]) -> $point {
+                $point {
+                    purpose: "hash_to_curve",
+                    ge: <$backend as PrimeGroupBackend>::hash_to_point(message),
+                }
+            }
+
+            fn mul_generator(scalar: &$scalar) -> $point {
+                GENERATOR_TABLE.mul(scalar)
+            }
+
+            fn mul_base_point2(scalar: &$scalar) -> $point {
+                BASE_POINT2_TABLE.mul(scalar)
+            }
+        }
+
+        pub struct $table {
+            base: $point,
+            table: WindowTable<PK>,
+        }
+
+        impl BasePointTable<$point> for $table {
+            fn new(base: &$point) -> $table {
+                let table =
+                    WindowTable::new(base.ge, CURVE_ORDER.bit_length(), |a, b| *a + *b, |a| -*a);
+                $table { base: *base, table }
+            }
+
+            fn base(&self) -> $point {
+                self.base
+            }
+
+            fn mul(&self, scalar: &$scalar) -> $point {
+                let ge = self
+                    .table
+                    .lookup(&scalar.to_big_int())
+                    .fold(<$backend as PrimeGroupBackend>::identity(), |acc, p| {
+                        acc + p
+                    });
+                $point {
+                    purpose: "mul_table",
+                    ge,
+                }
+            }
+        }
+
+        ::lazy_static::lazy_static! {
+            static ref CURVE_ORDER: BigInt = <$backend as PrimeGroupBackend>::curve_order();
+            /// Hash of the encoded generator, so nobody knows its discrete logarithm
+            static ref BASE_POINT2: $point = {
+                let g = <$backend as PrimeGroupBackend>::generator();
+                let h = <$backend as PrimeGroupBackend>::hash_to_point(
+                    &<$backend as PrimeGroupBackend>::point_to_bytes(&g),
+                );
+                $point {
+                    purpose: "random",
+                    ge: h,
+                }
+            };
+            static ref GENERATOR_TABLE: $table = $table::new(&<$point as ECPoint>::generator());
+            static ref BASE_POINT2_TABLE: $table = $table::new(&BASE_POINT2);
+        }
+
+        impl ::std::ops::Mul<$scalar> for $point {
+            type Output = $point;
+            fn mul(self, other: $scalar) -> $point {
+                self.scalar_mul(&other.get_element())
+            }
+        }
+
+        impl<'o> ::std::ops::Mul<&'o $scalar> for $point {
+            type Output = $point;
+            fn mul(self, other: &'o $scalar) -> $point {
+                self.scalar_mul(&other.get_element())
+            }
+        }
+
+        impl<'o> ::std::ops::Mul<&'o $scalar> for &'o $point {
+            type Output = $point;
+            fn mul(self, other: &'o $scalar) -> $point {
+                self.scalar_mul(&other.get_element())
+            }
+        }
+
+        impl ::std::ops::Add<$point> for $point {
+            type Output = $point;
+            fn add(self, other: $point) -> $point {
+                self.add_point(&other.get_element())
+            }
+        }
+
+        impl<'o> ::std::ops::Add<&'o $point> for $point {
+            type Output = $point;
+            fn add(self, other: &'o $point) -> $point {
+                self.add_point(&other.get_element())
+            }
+        }
+
+        impl<'o> ::std::ops::Add<&'o $point> for &'o $point {
+            type Output = $point;
+            fn add(self, other: &'o $point) -> $point {
+                self.add_point(&other.get_element())
+            }
+        }
+
+        impl ::std::ops::Sub<$point> for $point {
+            type Output = $point;
+            fn sub(self, other: $point) -> $point {
+                self.sub_point(&other.get_element())
+            }
+        }
+
+        impl<'o> ::std::ops::Sub<&'o $point> for $point {
+            type Output = $point;
+            fn sub(self, other: &'o $point) -> $point {
+                self.sub_point(&other.get_element())
+            }
+        }
+
+        impl ::std::ops::Neg for $point {
+            type Output = $point;
+            fn neg(self) -> $point {
+                $point {
+                    purpose: "neg",
+                    ge: -self.ge,
+                }
+            }
+        }
+
+        impl ::serde::Serialize for $point {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&hex::encode(self.pk_to_key_slice()))
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for $point {
+            fn deserialize<D: ::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<$point, D::Error> {
+                struct PointVisitor;
+
+                impl<'de> ::serde::de::Visitor<'de> for PointVisitor {
+                    type Value = $point;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut ::std::fmt::Formatter,
+                    ) -> ::std::fmt::Result {
+                        formatter.write_str(stringify!($point))
+                    }
+
+                    fn visit_str<E: ::serde::de::Error>(self, s: &str) -> Result<$point, E> {
+                        let bytes = hex::decode(s).map_err(E::custom)?;
+                        <$point as ECPoint>::from_bytes(&bytes)
+                            .map_err(|_| E::custom("invalid point"))
+                    }
+                }
+
+                deserializer.deserialize_str(PointVisitor)
+            }
+        }
+    };
+}
+pub(super) use impl_prime_group_curve;
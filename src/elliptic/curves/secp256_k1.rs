@@ -16,7 +16,8 @@
 // The Public Key codec: Point <> SecretKey
 //
 
-use super::traits::{ECPoint, ECScalar};
+use super::fixed_base::{select_array, CtLookup, WindowTable};
+use super::traits::{try_and_increment, BasePointTable, ECPoint, ECScalar};
 use crate::arithmetic::traits::*;
 use crate::BigInt;
 use crate::ErrorKey;
@@ -31,7 +32,7 @@ use rand::thread_rng;
 use secp256k1::constants::{
     CURVE_ORDER, GENERATOR_X, GENERATOR_Y, SECRET_KEY_SIZE, UNCOMPRESSED_PUBLIC_KEY_SIZE,
 };
-use secp256k1::{PublicKey, Secp256k1, SecretKey, VerifyOnly};
+use secp256k1::{ffi, PublicKey, Secp256k1, SecretKey, SignOnly, VerifyOnly};
 use serde::de::{self, Error, MapAccess, SeqAccess, Visitor};
 use serde::ser::SerializeStruct;
 use serde::ser::{Serialize, Serializer};
@@ -40,6 +41,7 @@ use std::fmt;
 use std::ops::{Add, Mul};
 use std::ptr;
 use std::sync::{atomic, Once};
+use subtle::Choice;
 use zeroize::Zeroize;
 /* X coordinate of a point of unknown discrete logarithm.
 Computed using a deterministic algorithm with the generator as input.
@@ -120,13 +122,11 @@ impl ECScalar for Secp256k1Scalar {
     fn from(n: &BigInt) -> Secp256k1Scalar {
         let curve_order = FE::q();
         let n_reduced = BigInt::mod_add(n, &BigInt::from(0), &curve_order);
-        let mut v = BigInt::to_bytes(&n_reduced);
-
-        if v.len() < SECRET_KEY_SIZE {
-            let mut template = vec![0; SECRET_KEY_SIZE - v.len()];
-            template.extend_from_slice(&v);
-            v = template;
+        // secp256k1::SecretKey refuses to be zero, though zero is a valid scalar
+        if n_reduced.is_zero() {
+            return FE::zero();
         }
+        let v = n_reduced.to_bytes_be_padded(SECRET_KEY_SIZE);
 
         Secp256k1Scalar {
             purpose: "from_big_int",
@@ -275,25 +275,14 @@ impl ECPoint for Secp256k1Point {
     type SecretKey = SK;
     type PublicKey = PK;
     type Scalar = Secp256k1Scalar;
+    type Table = Secp256k1Table;
 
     fn base_point2() -> Secp256k1Point {
-        let mut v = vec![4_u8];
-        v.extend(BASE_POINT2_X.as_ref());
-        v.extend(BASE_POINT2_Y.as_ref());
-        Secp256k1Point {
-            purpose: "random",
-            ge: PK::from_slice(&v).unwrap(),
-        }
+        *BASE_POINT2
     }
 
     fn generator() -> Secp256k1Point {
-        let mut v = vec![4_u8];
-        v.extend(GENERATOR_X.as_ref());
-        v.extend(GENERATOR_Y.as_ref());
-        Secp256k1Point {
-            purpose: "base_fe",
-            ge: PK::from_slice(&v).unwrap(),
-        }
+        *GENERATOR
     }
 
     fn get_element(&self) -> PK {
@@ -382,19 +371,8 @@ impl ECPoint for Secp256k1Point {
     }
     fn pk_to_key_slice(&self) -> Vec<u8> {
         let mut v = vec![4_u8];
-        let x_vec = BigInt::to_bytes(&self.x_coor().unwrap());
-        let y_vec = BigInt::to_bytes(&self.y_coor().unwrap());
-
-        let mut raw_x: Vec<u8> = Vec::new();
-        let mut raw_y: Vec<u8> = Vec::new();
-        raw_x.extend(vec![0u8; 32 - x_vec.len()]);
-        raw_x.extend(x_vec);
-
-        raw_y.extend(vec![0u8; 32 - y_vec.len()]);
-        raw_y.extend(y_vec);
-
-        v.extend(raw_x);
-        v.extend(raw_y);
+        v.extend(self.x_coor().unwrap().to_bytes_be_padded(32));
+        v.extend(self.y_coor().unwrap().to_bytes_be_padded(32));
         v
     }
 
@@ -428,18 +406,8 @@ impl ECPoint for Secp256k1Point {
         let y = point.y_coor().unwrap();
         let minus_y = BigInt::mod_sub(&order, &y, &order);
 
-        let x_vec = BigInt::to_bytes(&x);
-        let y_vec = BigInt::to_bytes(&minus_y);
-
-        let mut template_x = vec![0; 32 - x_vec.len()];
-        template_x.extend_from_slice(&x_vec);
-        let mut x_vec = template_x;
-
-        let mut template_y = vec![0; 32 - y_vec.len()];
-        template_y.extend_from_slice(&y_vec);
-        let y_vec = template_y;
-
-        x_vec.extend_from_slice(&y_vec);
+        let mut x_vec = x.to_bytes_be_padded(32);
+        x_vec.extend(minus_y.to_bytes_be_padded(32));
 
         let minus_point: GE = ECPoint::from_bytes(&x_vec).unwrap();
         //let minus_point: GE = ECPoint::from_coor(&x, &y_inv);
@@ -447,23 +415,9 @@ impl ECPoint for Secp256k1Point {
     }
 
     fn from_coor(x: &BigInt, y: &BigInt) -> Secp256k1Point {
-        let mut vec_x = BigInt::to_bytes(x);
-        let mut vec_y = BigInt::to_bytes(y);
         let coor_size = (UNCOMPRESSED_PUBLIC_KEY_SIZE - 1) / 2;
-
-        if vec_x.len() < coor_size {
-            // pad
-            let mut x_buffer = vec![0; coor_size - vec_x.len()];
-            x_buffer.extend_from_slice(&vec_x);
-            vec_x = x_buffer
-        }
-
-        if vec_y.len() < coor_size {
-            // pad
-            let mut y_buffer = vec![0; coor_size - vec_y.len()];
-            y_buffer.extend_from_slice(&vec_y);
-            vec_y = y_buffer
-        }
+        let vec_x = x.to_bytes_be_padded(coor_size);
+        let vec_y = y.to_bytes_be_padded(coor_size);
 
         assert_eq!(x, &BigInt::from_bytes(vec_x.as_ref()));
         assert_eq!(y, &BigInt::from_bytes(vec_y.as_ref()));
@@ -477,6 +431,102 @@ impl ECPoint for Secp256k1Point {
             ge: PK::from_slice(&v).unwrap(),
         }
     }
+
+    fn mul_generator(scalar: &Secp256k1Scalar) -> Secp256k1Point {
+        // libsecp256k1 has its own precomputed table for the generator. Zero scalar gives the
+        // point at infinity which PublicKey can't represent, so it panics the same way as
+        // `generator() * zero` (libsecp256k1 would silently return an invalid key instead)
+        assert!(scalar.fe[..] != [0u8; 32], "Assignment expected");
+        Secp256k1Point {
+            purpose: "mul_generator",
+            ge: PK::from_secret_key(&SIGNING_CONTEXT, &scalar.fe),
+        }
+    }
+
+    fn mul_base_point2(scalar: &Secp256k1Scalar) -> Secp256k1Point {
+        BASE_POINT2_TABLE.mul(scalar)
+    }
+
+    fn hash_to_curve(message: &[u8]) -> Secp256k1Point {
+        // from_bytes interprets 32 bytes as x coordinate of a point with even y
+        try_and_increment(message, |digest| Secp256k1Point::from_bytes(digest).ok())
+    }
+}
+
+/// Table entries are libsecp256k1 internal representations of the points
+impl CtLookup for PK {
+    type Entry = [u8; 64];
+
+    fn to_entry(&self) -> [u8; 64] {
+        unsafe { *self.as_ptr() }.underlying_bytes()
+    }
+
+    fn from_entry(entry: &[u8; 64]) -> PK {
+        // SAFETY: entries are obtained from valid public keys by `to_entry` of the same library
+        PK::from(unsafe { ffi::PublicKey::from_array_unchecked(*entry) })
+    }
+
+    fn conditional_select(a: &[u8; 64], b: &[u8; 64], choice: Choice) -> [u8; 64] {
+        select_array(a, b, choice)
+    }
+}
+
+pub struct Secp256k1Table {
+    base: Secp256k1Point,
+    table: WindowTable<PK>,
+}
+
+impl BasePointTable<Secp256k1Point> for Secp256k1Table {
+    fn new(base: &Secp256k1Point) -> Secp256k1Table {
+        let table = WindowTable::new(
+            base.ge,
+            FE::q().bit_length(),
+            |a, b| a.combine(b).expect("table entry is the point at infinity"),
+            |a| {
+                let mut neg = *a;
+                neg.negate_assign(get_context());
+                neg
+            },
+        );
+        Secp256k1Table { base: *base, table }
+    }
+
+    fn base(&self) -> Secp256k1Point {
+        self.base
+    }
+
+    fn mul(&self, scalar: &Secp256k1Scalar) -> Secp256k1Point {
+        // combine_keys sums all points in jacobian coordinates and normalizes the result once
+        let points: Vec<PK> = self.table.lookup(&scalar.to_big_int()).collect();
+        let points: Vec<&PK> = points.iter().collect();
+        Secp256k1Point {
+            purpose: "mul_table",
+            ge: PK::combine_keys(&points).expect("Assignment expected"),
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref GENERATOR: Secp256k1Point = {
+        let mut v = vec![4_u8];
+        v.extend(GENERATOR_X.as_ref());
+        v.extend(GENERATOR_Y.as_ref());
+        Secp256k1Point {
+            purpose: "base_fe",
+            ge: PK::from_slice(&v).unwrap(),
+        }
+    };
+    static ref BASE_POINT2: Secp256k1Point = {
+        let mut v = vec![4_u8];
+        v.extend(BASE_POINT2_X.as_ref());
+        v.extend(BASE_POINT2_Y.as_ref());
+        Secp256k1Point {
+            purpose: "random",
+            ge: PK::from_slice(&v).unwrap(),
+        }
+    };
+    static ref BASE_POINT2_TABLE: Secp256k1Table = Secp256k1Table::new(&BASE_POINT2);
+    static ref SIGNING_CONTEXT: Secp256k1<SignOnly> = Secp256k1::signing_only();
 }
 
 static mut CONTEXT: Option<Secp256k1<VerifyOnly>> = None;
@@ -659,6 +709,16 @@ mod tests {
         assert_eq!(r.y_coor().unwrap(), r_expected.y_coor().unwrap());
     }
 
+    #[test]
+    fn scalar_from_multiple_of_order_is_zero() {
+        let zero: FE = ECScalar::zero();
+        for n in [BigInt::zero(), FE::q(), FE::q() * 2, -FE::q()].iter() {
+            let scalar: FE = ECScalar::from(n);
+            assert_eq!(scalar, zero);
+            assert_eq!(scalar.to_big_int(), BigInt::zero());
+        }
+    }
+
     #[test]
     fn deserialize_sk() {
         let s = "\"1e240\"";
@@ -7,6 +7,9 @@
 
 use std::ops::{Add, Mul};
 
+use sha2::{Digest, Sha256};
+
+use crate::arithmetic::traits::*;
 use crate::BigInt;
 use crate::ErrorKey;
 
@@ -36,6 +39,10 @@ where
     type PublicKey;
 
     type Scalar: ECScalar<SecretKey = Self::SecretKey>;
+    /// Precomputed multiples of a fixed point, see [BasePointTable]
+    ///
+    /// Curves that don't have a table of their own can use [ScalarMulTable].
+    type Table: BasePointTable<Self>;
 
     fn base_point2() -> Self;
     fn generator() -> Self;
@@ -49,4 +56,115 @@ where
     fn add_point(&self, other: &Self::PublicKey) -> Self;
     fn sub_point(&self, other: &Self::PublicKey) -> Self;
     fn from_coor(x: &BigInt, y: &BigInt) -> Self;
+
+    /// Computes `scalar * generator()` using a table precomputed once per process (or fixed-base
+    /// multiplication provided by the curve library)
+    ///
+    /// Default implementation does plain scalar multiplication.
+    fn mul_generator(scalar: &Self::Scalar) -> Self {
+        Self::generator().scalar_mul(&scalar.get_element())
+    }
+    /// Computes `scalar * base_point2()` using a table precomputed once per process
+    ///
+    /// Default implementation does plain scalar multiplication.
+    fn mul_base_point2(scalar: &Self::Scalar) -> Self {
+        Self::base_point2().scalar_mul(&scalar.get_element())
+    }
+
+    /// Deterministically maps `message` to a point of the (prime order) group, such that nobody
+    /// knows its discrete logarithm w.r.t. [generator](Self::generator)
+    ///
+    /// Points derived from distinct messages are independent "nothing up my sleeve" generators.
+    ///
+    /// Required since 0.8.0. There's no default: mapping bytes to a point depends on the curve
+    /// and its encoding, and deriving the point from [generator](Self::generator) would reveal
+    /// its discrete logarithm.
+    fn hash_to_curve(message: &[u8]) -> Self;
+
+    /// Computes `scalars[0] * points[0] + ... + scalars[n-1] * points[n-1]`
+    ///
+    /// Default implementation does a scalar multiplication per point. Curves that support
+    /// multi-scalar multiplication natively override it with a faster algorithm.
+    ///
+    /// ## Panics
+    /// Panics if `scalars` and `points` have different lengths, or if all scalars are zero (not
+    /// every curve can represent the point at infinity).
+    fn multi_scalar_mul(scalars: &[Self::Scalar], points: &[Self]) -> Self {
+        assert_eq!(
+            scalars.len(),
+            points.len(),
+            "number of scalars doesn't match number of points"
+        );
+        scalars
+            .iter()
+            .zip(points)
+            .filter(|(s, _)| !s.to_big_int().is_zero())
+            .map(|(s, p)| p.scalar_mul(&s.get_element()))
+            .reduce(|sum, p| sum.add_point(&p.get_element()))
+            .expect("all scalars are zero")
+    }
+
+    /// Variable-time version of [multi_scalar_mul](Self::multi_scalar_mul)
+    ///
+    /// Must only be used when scalars and points are public, e.g. when verifying proofs. Default
+    /// implementation falls back to [multi_scalar_mul](Self::multi_scalar_mul).
+    fn vartime_multi_scalar_mul(scalars: &[Self::Scalar], points: &[Self]) -> Self {
+        Self::multi_scalar_mul(scalars, points)
+    }
+}
+
+/// Precomputed multiples of a fixed point
+///
+/// Building a table costs much more than a single scalar multiplication, so it pays off for
+/// points that are multiplied many times, e.g. bases of a commitment scheme.
+///
+/// ## Example
+/// ```
+/// use curv::elliptic::curves::secp256_k1::{FE, GE};
+/// use curv::elliptic::curves::traits::*;
+///
+/// let h = GE::hash_to_curve(b"my base point");
+/// let table = <GE as ECPoint>::Table::new(&h);
+/// let s: FE = ECScalar::new_random();
+/// assert!(table.mul(&s) == h * s);
+/// ```
+pub trait BasePointTable<P: ECPoint> {
+    fn new(base: &P) -> Self;
+    /// Point the table was built for
+    fn base(&self) -> P;
+    /// Computes `scalar * base()`
+    fn mul(&self, scalar: &P::Scalar) -> P;
+}
+
+/// [BasePointTable] that doesn't precompute anything and does plain scalar multiplication
+pub struct ScalarMulTable<P> {
+    base: P,
+}
+
+impl<P: ECPoint + Clone> BasePointTable<P> for ScalarMulTable<P> {
+    fn new(base: &P) -> Self {
+        ScalarMulTable { base: base.clone() }
+    }
+
+    fn base(&self) -> P {
+        self.base.clone()
+    }
+
+    fn mul(&self, scalar: &P::Scalar) -> P {
+        self.base.scalar_mul(&scalar.get_element())
+    }
+}
+
+/// Try-and-increment hashing to curve: hashes `message` along with a counter until `decode` accepts
+/// the digest as an encoding of a point
+pub(crate) fn try_and_increment<P>(message: &[u8], decode: impl Fn(&[u8]) -> Option<P>) -> P {
+    (0u32..)
+        .find_map(|counter| {
+            let digest = Sha256::new()
+                .chain(message)
+                .chain(counter.to_be_bytes())
+                .result();
+            decode(&digest)
+        })
+        .expect("hashing to curve didn't succeed")
 }
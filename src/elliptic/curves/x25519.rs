@@ -0,0 +1,218 @@
+//! X25519 key agreement ([RFC 7748])
+//!
+//! Curve25519 in Montgomery form is birationally equivalent to the twisted Edwards curve used by
+//! [Ed25519Point], so keys of one can be converted into keys of another (see
+//! [Ed25519Point::to_montgomery_u] and [Ed25519Point::from_montgomery_u]). Public keys are
+//! 32 bytes little-endian u-coordinates.
+//!
+//! [Ed25519Point]: super::ed25519::Ed25519Point
+//! [Ed25519Point::to_montgomery_u]: super::ed25519::Ed25519Point::to_montgomery_u
+//! [Ed25519Point::from_montgomery_u]: super::ed25519::Ed25519Point::from_montgomery_u
+//!
+//! [RFC 7748]: https://tools.ietf.org/html/rfc7748
+
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use curve25519_dalek::scalar::Scalar;
+
+use super::ed25519::Ed25519Scalar;
+use super::traits::ECScalar;
+
+/// u-coordinate of the base point (`u = 9`)
+pub const X25519_BASEPOINT: [u8; 32] = [
+    9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+
+/// Clamps a private key as specified in RFC 7748: clears three lowest bits (so the result is
+/// a multiple of the cofactor) and the highest bit, sets the second highest bit
+pub fn clamp_scalar(mut k: [u8; 32]) -> [u8; 32] {
+    k[0] &= 248;
+    k[31] &= 127;
+    k[31] |= 64;
+    k
+}
+
+/// The X25519 function of RFC 7748: clamps `k` and multiplies point `u` by it using
+/// the Montgomery ladder
+///
+/// Most significant bit of `u` is ignored and non-canonical values are reduced modulo
+/// `2^255 - 19`, points on the quadratic twist are accepted, as the RFC requires.
+pub fn x25519(k: [u8; 32], u: [u8; 32]) -> [u8; 32] {
+    (MontgomeryPoint(u) * Scalar::from_bits(clamp_scalar(k))).to_bytes()
+}
+
+/// Computes X25519 public key `x25519(k, 9)`
+pub fn x25519_public_key(k: [u8; 32]) -> [u8; 32] {
+    x25519(k, X25519_BASEPOINT)
+}
+
+/// Multiplies point `u` by `scalar` using the Montgomery ladder, without clamping
+///
+/// Allows Ed25519 secrets to take part in X25519 key agreement: for `A = a * G`,
+/// `mul_u(a, u)` equals `x25519(k, A.to_montgomery_u())` if `u = x25519(k, 9)`.
+///
+/// The point is multiplied by `a / 8 mod q` and then by the cofactor `8`, which gives `a * U`
+/// for points of the prime order subgroup and clears the small order component of any other
+/// point. That way the result doesn't reveal `a mod 8`, which is what clamping achieves in
+/// X25519.
+pub fn mul_u(scalar: &Ed25519Scalar, u: &[u8; 32]) -> [u8; 32] {
+    let cofactor = Scalar::from(8u64);
+    let scalar_div_cofactor = scalar.get_element() * cofactor.invert();
+    (MontgomeryPoint(*u) * scalar_div_cofactor * cofactor).to_bytes()
+}
+
+/// Checks whether `u` is a point of small order (order dividing 8) on the curve or its twist
+///
+/// Both [x25519] and [mul_u] map such points to the all-zero output regardless of the secret,
+/// so they should be rejected by protocols that need the peer to contribute to the shared
+/// secret. Points having a small order component but not of small order themselves aren't
+/// detected, [mul_u] clears that component.
+pub fn is_small_order_u(u: &[u8; 32]) -> bool {
+    (MontgomeryPoint(*u) * Scalar::from(8u64)).to_bytes() == [0u8; 32]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elliptic::curves::ed25519::{FE, GE};
+    use crate::elliptic::curves::traits::{ECPoint, ECScalar};
+    use crate::BigInt;
+
+    fn bytes(hex: &str) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&hex::decode(hex).unwrap());
+        bytes
+    }
+
+    #[test]
+    fn rfc7748_test_vectors() {
+        // RFC 7748, section 5.2
+        let k = bytes("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4");
+        let u = bytes("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c");
+        assert_eq!(
+            x25519(k, u),
+            bytes("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552")
+        );
+
+        let k = bytes("4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d");
+        let u = bytes("e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493");
+        assert_eq!(
+            x25519(k, u),
+            bytes("95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957")
+        );
+    }
+
+    #[test]
+    fn rfc7748_iterated_test_vectors() {
+        let mut k = X25519_BASEPOINT;
+        let mut u = X25519_BASEPOINT;
+        for i in 1..=1000 {
+            let result = x25519(k, u);
+            u = k;
+            k = result;
+            if i == 1 {
+                assert_eq!(
+                    k,
+                    bytes("422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079")
+                );
+            }
+        }
+        assert_eq!(
+            k,
+            bytes("684cf59ba83309552800ef566f2f4d3c1c3887c49360e3875f2eb94d99532c51")
+        );
+    }
+
+    #[test]
+    fn rfc7748_diffie_hellman() {
+        // RFC 7748, section 6.1
+        let alice_sk = bytes("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+        let alice_pk = bytes("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a");
+        let bob_sk = bytes("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+        let bob_pk = bytes("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f");
+        let shared = bytes("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+
+        assert_eq!(x25519_public_key(alice_sk), alice_pk);
+        assert_eq!(x25519_public_key(bob_sk), bob_pk);
+        assert_eq!(x25519(alice_sk, bob_pk), shared);
+        assert_eq!(x25519(bob_sk, alice_pk), shared);
+    }
+
+    #[test]
+    fn base_point_is_ed25519_generator() {
+        assert_eq!(GE::generator().to_montgomery_u(), X25519_BASEPOINT);
+        let g = GE::from_montgomery_u(&X25519_BASEPOINT, false).unwrap();
+        assert_eq!(g, GE::generator());
+        // the other point with the same u is -G
+        let minus_g = GE::from_montgomery_u(&X25519_BASEPOINT, true).unwrap();
+        assert_ne!(minus_g, g);
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        assert_eq!((g + minus_g).pk_to_key_slice(), identity.to_vec());
+    }
+
+    #[test]
+    fn ed25519_keys_agree_with_x25519_peer() {
+        let a: FE = ECScalar::new_random();
+        let a_pub = GE::generator() * a;
+
+        let bob_sk = bytes("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+        let bob_pk = x25519_public_key(bob_sk);
+
+        assert_eq!(mul_u(&a, &bob_pk), x25519(bob_sk, a_pub.to_montgomery_u()));
+        // converting peer's key to Edwards form gives the same result, whatever the sign is
+        for sign in [false, true].iter() {
+            let bob_point = GE::from_montgomery_u(&bob_pk, *sign).unwrap();
+            assert_eq!((bob_point * a).to_montgomery_u(), mul_u(&a, &bob_pk));
+        }
+    }
+
+    #[test]
+    fn small_order_points() {
+        // u-coordinates of points of order 1, 2, 4 and 8 on the curve and its twist
+        let small_order = [
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0100000000000000000000000000000000000000000000000000000000000000",
+            "e0eb7a7c3b41b8ae1656e3faf19fc46ada098deb9c32b1fd866205165f49b800",
+            "5f9c95bca3508c24b1d0b1559c83ef5b04445cc4581c8e86d8224eddd09f1157",
+            "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        ];
+        for u in small_order.iter() {
+            assert!(is_small_order_u(&bytes(u)), "{}", u);
+            assert_eq!(x25519([0x42; 32], bytes(u)), [0u8; 32]);
+        }
+        assert!(!is_small_order_u(&X25519_BASEPOINT));
+    }
+
+    #[test]
+    fn mixed_order_points_do_not_leak_scalar_mod_cofactor() {
+        // point of order 8: u-coordinate from the list of small order points
+        let torsion_u = bytes("5f9c95bca3508c24b1d0b1559c83ef5b04445cc4581c8e86d8224eddd09f1157");
+        let torsion = GE::from_montgomery_u(&torsion_u, false).unwrap();
+        assert!(torsion.is_small_order());
+
+        let b: FE = ECScalar::new_random();
+        let honest = GE::generator() * b;
+        let mixed = (honest + torsion).to_montgomery_u();
+        assert!(!is_small_order_u(&mixed));
+
+        for k in 1..=8u32 {
+            // scalars a with different residues modulo 8
+            let a: FE = ECScalar::from(&(BigInt::from(8 * 1000 + k)));
+            let expected = (honest * a).to_montgomery_u();
+            assert_eq!(mul_u(&a, &honest.to_montgomery_u()), expected);
+            // the torsion component is cleared, the result is the same as for honest point
+            assert_eq!(mul_u(&a, &mixed), expected);
+            // plain multiplication would expose a mod 8
+            let plain = (MontgomeryPoint(mixed) * a.get_element()).to_bytes();
+            assert_eq!(plain == expected, k == 8, "{}", k);
+        }
+    }
+
+    #[test]
+    fn twist_points_have_no_edwards_form() {
+        // u = 2 is on the quadratic twist: u^3 + 486662u^2 + u is not a square
+        let mut u = [0u8; 32];
+        u[0] = 2;
+        assert!(GE::from_montgomery_u(&u, false).is_err());
+    }
+}
@@ -19,8 +19,12 @@ pub enum ErrorKey {
     InvalidPublicKey,
 }
 
+#[derive(Copy, PartialEq, Eq, Clone, Debug)]
 pub enum ErrorSS {
     VerifyShareError,
+    DuplicatePartyError,
+    TooFewPartiesError,
+    InvalidPartyError,
 }
 
 #[cfg(test)]
@@ -56,9 +60,44 @@ macro_rules! test_for_all_curves {
             }
             #[test]
             $($attrs)*
+            fn [<$fn _bn254>]() {
+                $fn::<crate::elliptic::curves::bn254::g1::GE>()
+            }
+            #[test]
+            $($attrs)*
             fn [<$fn _p256>]() {
                 $fn::<crate::elliptic::curves::p256::GE>()
             }
+            #[test]
+            $($attrs)*
+            fn [<$fn _p384>]() {
+                $fn::<crate::elliptic::curves::p384::GE>()
+            }
+            #[test]
+            $($attrs)*
+            fn [<$fn _p521>]() {
+                $fn::<crate::elliptic::curves::p521::GE>()
+            }
+            #[test]
+            $($attrs)*
+            fn [<$fn _jubjub>]() {
+                $fn::<crate::elliptic::curves::jubjub::GE>()
+            }
+            #[test]
+            $($attrs)*
+            fn [<$fn _baby_jubjub>]() {
+                $fn::<crate::elliptic::curves::baby_jubjub::GE>()
+            }
+            #[test]
+            $($attrs)*
+            fn [<$fn _pallas>]() {
+                $fn::<crate::elliptic::curves::pasta::pallas::GE>()
+            }
+            #[test]
+            $($attrs)*
+            fn [<$fn _vesta>]() {
+                $fn::<crate::elliptic::curves::pasta::vesta::GE>()
+            }
         }
     };
 }